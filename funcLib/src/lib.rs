@@ -1,7 +1,7 @@
 #![allow(non_snake_case)]
 #![allow(non_camel_case_types)]
 
-use std::{ffi::CString, io};
+use std::{ffi::{CStr, CString}, io, os::raw::c_char};
 
 
 
@@ -20,14 +20,20 @@ pub extern fn putfloat(val: f32) -> bool {
 
 
 #[no_mangle]
-pub extern fn bool(val: bool) -> bool {
+pub extern fn putbool(val: bool) -> bool {
     println!("{}", val);
     return true;
 }
 
+//Strings are passed around as pointers to null terminated text
 #[no_mangle]
-pub extern fn putstring(val: &CString) -> bool {
-    println!("{:?}", val);
+pub extern fn putstring(val: *const c_char) -> bool {
+    if val.is_null() {
+        println!();
+        return true;
+    }
+    let text = unsafe { CStr::from_ptr(val) };
+    println!("{}", text.to_string_lossy());
     return true;
 }
 
@@ -49,6 +55,16 @@ pub extern fn getfloat() -> f32 {
     return intVal;
 }
 
+//Reads a line and hands it back on the heap, the program owns it from then on
+#[no_mangle]
+pub extern fn getstring() -> *mut c_char {
+    let mut readIn = String::new();
+    let stdIn = io::stdin();
+    stdIn.read_line(&mut readIn).expect("No stdin value found");
+    let line = readIn.trim_end_matches(&['\r', '\n'][..]).replace('\0', "");
+    let strVal = CString::new(line).expect("Could not convert the input for getString");
+    return strVal.into_raw();
+}
 
 #[no_mangle]
 pub extern fn getbool() -> bool {
@@ -59,6 +75,14 @@ pub extern fn getbool() -> bool {
     return intVal;
 }
 
+//Called by the bounds checks the compiler puts before array accesses, it never returns
+#[no_mangle]
+pub extern fn boundserror(index: i32, name: *const c_char, size: i32, line: i32) {
+    let arrName = if name.is_null() { String::new() } else { unsafe { CStr::from_ptr(name) }.to_string_lossy().to_string() };
+    eprintln!("array index {} out of bounds for `{}[{}]` at line {}", index, arrName, size, line);
+    std::process::exit(1);
+}
+
 #[no_mangle]
 pub extern fn sqrt(input: i32) -> f64{
     let retval = f64::sqrt(input as f64);
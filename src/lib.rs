@@ -0,0 +1,23 @@
+//Rules
+#![allow(non_snake_case)]
+#![allow(non_camel_case_types)]
+#![allow(unused_imports)]
+#![allow(dead_code)]
+#![allow(unused_parens)]
+#![allow(unused_mut)]
+#![allow(unused_variables)]
+
+//Crate imports
+extern crate anyhow;
+extern crate parse_display;
+extern crate utf8_chars;
+extern crate unicode_segmentation;
+
+//The stages of the compiler, each in its own module
+pub mod models;
+
+//The token types are used by every stage through the crate root
+pub use crate::models::compiler::tokenTypeEnum;
+
+//The pipeline that runs the stages in order, the main entry point for using the compiler as a library
+pub use crate::models::pipeline::{compile_source, Artifacts, Diagnostics, EmitType, Options};
@@ -14,187 +14,387 @@ extern crate utf8_chars;
 extern crate unicode_segmentation;
 // extern crate funcLib;
 
-mod models;
-
 //package imports
 use {
-    crate::models::{lexer::Lexer, parser::{Expr, Parser, *}, typechecker::{
-        SymbolTable, SyntaxChecker
-    }, compiler::*,
-    }, anyhow::Result, inkwell::{builder::Builder, OptimizationLevel, passes::PassManager, context::Context, module::Module, types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum}, values::*, AddressSpace, FloatPredicate, IntPredicate}, parse_display::Display, std::{
-        collections::HashMap, env::{self, args}, ffi::CString, fmt, rc::Rc
-    }
+    compiler::{compile_source, EmitType, Options}, std::{env, fs, path::{Path, PathBuf}, process::{self, Command}},
 };
 
-///////////////////////// Setup /////////////////////////
-
-use std::fs::{self, File};
-//imports
-use std::{io::prelude::*, path::Path};
-use std::process::{self, Command};
-use inkwell::object_file::Symbol;
-use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple};
-// use llvm_sys::target_machine::LLVMTargetMachineOptionsSetRelocMode;
-
-///////////////////////// /Setup /////////////////////////
-
-
+///////////////////////// Command line /////////////////////////
+
+//The usage message printed when the arguments are wrong or --help is passed
+const USAGE: &str = "Usage: compiler [options] <file.src>
+
+Options:
+  -o <file>               Write the output to <file> (use - for stdout on text outputs)
+  --emit=<type>           What to produce: tokens, ast, llvm-ir, bc, asm, obj or exe (default exe)
+  --runtime-lib <path>    Path to the builtin function library (default ./funcLib.a)
+  --linker <cmd>          Command used to link the executable (default clang)
+  --target <triple>       Target triple to generate code for (default is the host)
+  --bounds-checks         Check every array index at runtime (default)
+  --no-bounds-checks      Do not check array indexes at runtime
+  -h, --help              Print this message";
+
+//The options the compiler was run with
+struct CompilerOptions {
+    inputPath: String,
+    outputPath: Option<String>,
+    emit: EmitType,
+    runtimeLib: String,
+    linker: String,
+    target: Option<String>,
+    boundsChecks: bool,
+}
+
+impl CompilerOptions {
+    //Parses the command line arguments (not including the program name) into the options
+    fn parseArgs(args: Vec<String>) -> Result<CompilerOptions, String> {
+        let mut inputPath: Option<String> = None;
+        let mut outputPath: Option<String> = None;
+        let mut emit = EmitType::Exe;
+        let mut runtimeLib = "./funcLib.a".to_string();
+        let mut linker = "clang".to_string();
+        let mut target: Option<String> = None;
+        //Checks are on in release builds too, a bad index is a bug in the program either way
+        let mut boundsChecks = true;
+
+        let mut i = 0;
+        while i < args.len() {
+            let arg = args[i].clone();
+
+            //Splits --flag=value into the flag and its value
+            let (flag, inlineVal) = match arg.split_once('=') {
+                Some((flag, val)) if arg.starts_with("--") => (flag.to_string(), Some(val.to_string())),
+                _ => (arg.clone(), None),
+            };
+
+            match flag.as_str() {
+                "-h" | "--help" => {
+                    return Err(USAGE.to_string());
+                }
+                "--bounds-checks" => {
+                    boundsChecks = true;
+                }
+                "--no-bounds-checks" => {
+                    boundsChecks = false;
+                }
+                "-o" | "--emit" | "--runtime-lib" | "--linker" | "--target" => {
+                    //Gets the value from after the = or from the next argument
+                    let value: String;
+                    match inlineVal {
+                        Some(val) => {
+                            value = val;
+                        }
+                        None => {
+                            i += 1;
+                            match args.get(i) {
+                                Some(val) => {
+                                    value = val.clone();
+                                }
+                                None => {
+                                    return Err(format!("Missing value for {}", flag));
+                                }
+                            }
+                        }
+                    }
+
+                    match flag.as_str() {
+                        "-o" => outputPath = Some(value),
+                        "--emit" => emit = EmitType::new(&value)?,
+                        "--runtime-lib" => runtimeLib = value,
+                        "--linker" => linker = value,
+                        _ => target = Some(value),
+                    }
+                }
+                _ => {
+                    if flag.starts_with('-') && flag != "-" {
+                        return Err(format!("Unknown option '{}'", flag));
+                    }
+                    if inputPath.is_some() {
+                        return Err(format!("Only one input file can be given, found '{}'", arg));
+                    }
+                    inputPath = Some(arg);
+                }
+            }
+            i += 1;
+        }
 
-//The main section of the code
-fn main() -> Result<(), Box<dyn std::error::Error>> {    
-    // Get the path from command line arguments
-    let path = env::args().nth(1).expect("Please specify an input file");
-    let mut myLexer = Lexer::new(&path);
-    println!("Lexer filename: {} \nCharacter count: {}", myLexer.inputFile.fileName, myLexer.inputFile.numChars);
+        match inputPath {
+            Some(inputPath) => Ok(CompilerOptions {
+                inputPath,
+                outputPath,
+                emit,
+                runtimeLib,
+                linker,
+                target,
+                boundsChecks,
+            }),
+            None => Err("Please specify an input file".to_string()),
+        }
+    }
 
-    // Scan through the input
-    myLexer.scanThrough();
+    //Gets the path the output is written to
+    //If no -o is given, it is the input file name with the extension for the emit type
+    fn getOutputPath(&self) -> PathBuf {
+        if let Some(outPath) = &self.outputPath {
+            return PathBuf::from(outPath);
+        }
+        let inPath = Path::new(&self.inputPath);
+        let stem = inPath.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or("a".to_string());
+        let mut outPath = PathBuf::from(stem);
+        outPath.set_extension(self.emit.extension());
+
+        //Makes sure an input without an extension is never overwritten by the executable
+        if outPath == inPath {
+            outPath.set_extension("out");
+        }
+        return outPath;
+    }
+}
 
-    // println!("Lexer reporting: {:?}", myLexer.reports.clone());
-    if (myLexer.reports.status) {
-        println!("Error in lexer: {:?}", myLexer.reports.clone());
+//Writes text output to the given path, a path of - writes to stdout
+fn writeText(outPath: &Path, contents: &str) -> Result<(), String> {
+    if outPath == Path::new("-") {
+        print!("{}", contents);
         return Ok(());
-    } else {
-        println!("Lexer returned successfully");
     }
+    match fs::write(outPath, contents) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(format!("Could not write {}: {}", outPath.display(), err)),
+    }
+}
 
-    // Initialize the parser
-    let mut myParser = Parser::new(&mut myLexer);
+///////////////////////// /Command line /////////////////////////
 
 
-    // // Print the parser's token list (for debugging)
-    // // println!("\n\nMy parser token list: ");
-    // // myParser.printTokenList();
 
-    //Parse the program and create the AST
-    let mut programAst: Stmt;
-    match myParser.startParse() {
-        Ok((reporting, Some(stmt))) => {
-            println!("Parsing completed successfully.");
-            programAst = stmt;
+//The main section of the code
+fn main() -> Result<(), Box<dyn std::error::Error>> {    
+    //Parse the command line arguments
+    let options: CompilerOptions;
+    match CompilerOptions::parseArgs(env::args().skip(1).collect()) {
+        Ok(opts) => {
+            options = opts;
         }
-        Ok((reporting, None)) => {
-            println!("\n\nParsing succeeded, but no programAST was returned.");
+        Err(msg) => {
+            eprintln!("{}", msg);
+            if msg != USAGE {
+                eprintln!("\n{}", USAGE);
+                process::exit(2);
+            }
             return Ok(());
         }
-        Err(reporting) => {
-            eprintln!("\n\nParsing failed.");
-            eprintln!("Reporting: {:?}", reporting);
-            return Ok(());
+    }
+    let outPath = options.getOutputPath();
+
+    //Reads the source file
+    let path = options.inputPath.clone();
+    let source: String;
+    match fs::read_to_string(&path) {
+        Ok(contents) => {
+            source = contents;
+        }
+        Err(err) => {
+            eprintln!("Could not read {}: {}", path, err);
+            process::exit(1);
         }
     }
 
-    //Display the program AST (for dev/debugging)
-    // programAst.display(0);
-
-    //Initialize the checker global table for the checker
-    let mut globalTable = SymbolTable::new();
-    
-    //Initialize the type checker
-    let mut myChecker = SyntaxChecker::new(programAst.clone(), &mut globalTable, "Main".to_string());
-    println!("\n\nTypeChecker Created");
-    
-    //Check the program
-    let programValid: bool = myChecker.checkProgram();
-
-    //Checks if the checker returned true
-    if(!programValid){
-        println!("\n\nError in program");
-        return Ok(());
-    } else {
-        println!("\n\nProgram is valid");
-    }
-
-    //Initialize the global symbol table
-    let mut globalTable: HashMap<String, PointerValue> = HashMap::new();
-
-    //Creates the llvm context and intializes the code generator struct
-    let context = Context::create();
-    let mut myGen = Compiler::new(programAst.clone(), &context, &mut globalTable, "Program".to_string());
-    println!("Created compiler");
-
-    //Run the code generator, this returns an LLVM module that contains LLVM IR
-    let ret = myGen.compileProgram();
-    
-    //Check the result of the code generator to ensure the module is valid
-    let mut finalMod: Module;
-    match ret{
-        Ok(module) => {
-            println!("\n\nModule generated");
-
-            //Uncomment this to print entire llvm IR module
-            // module.print_to_stderr();
-            
-            //Sets the final module
-            finalMod = module.clone();
+    //Runs the source through the compiler
+    let mut pipelineOptions = Options::new(&path, options.emit.clone());
+    pipelineOptions.target = options.target.clone();
+    pipelineOptions.boundsChecks = options.boundsChecks;
+    let artifacts = match compile_source(&source, pipelineOptions) {
+        Ok(artifacts) => artifacts,
+        Err(diagnostics) => {
+            eprint!("{}", diagnostics.render());
+
+            //The parser recovers from errors, so the partial tree can still be looked at
+            if options.emit == EmitType::Ast {
+                if let Some(ast) = &diagnostics.ast {
+                    writeText(&outPath, &ast.treeString(0))?;
+                }
+            }
+            eprintln!("Compilation of {} failed", path);
+            process::exit(1);
         }
-        Err(errMsg) => {
-            println!("Error with generation: {}", errMsg);
-            return Ok(());
+    };
+    eprint!("{}", artifacts.diagnostics.render());
+
+    //Writes the requested output
+    match options.emit {
+        //Text outputs can go to stdout
+        EmitType::Tokens | EmitType::Ast | EmitType::LlvmIr | EmitType::Asm => {
+            writeText(&outPath, &String::from_utf8_lossy(&artifacts.output))?;
+        }
+        //Binary outputs always go to a file
+        EmitType::Bitcode | EmitType::Object => {
+            if let Err(err) = fs::write(&outPath, &artifacts.output) {
+                eprintln!("Could not write {}: {}", outPath.display(), err);
+                process::exit(1);
+            }
+        }
+        //Creates the linked executable
+        EmitType::Exe => {
+            //The object file is only needed for linking so it goes in the temp directory
+            let objPath = env::temp_dir().join(format!("{}-{}.o", outPath.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or("out".to_string()), process::id()));
+
+            //Write the generated code to an object file
+            if let Err(err) = fs::write(&objPath, &artifacts.output) {
+                eprintln!("Error writing object file: {}", err);
+                process::exit(1);
+            }
+
+            //Defines the path of the library (where the builtins are defined)
+            let libPath = Path::new(&options.runtimeLib);
+            if !libPath.exists() {
+                let _ = fs::remove_file(&objPath);
+                eprintln!("Runtime library {} not found, use --runtime-lib to give its location", libPath.display());
+                process::exit(1);
+            }
+
+            //Create the final output by running the linker on the object file and the library
+            let mut linkCmd = Command::new(&options.linker);
+            linkCmd.arg(&objPath).arg(libPath).arg("-o").arg(&outPath);
+            if let Some(triple) = &options.target {
+                if options.linker.contains("clang") {
+                    linkCmd.arg(format!("--target={}", triple));
+                }
+            }
+            let finalOutput = linkCmd.output();
+            let _ = fs::remove_file(&objPath);
+
+            //Checks the success of the linker
+            match finalOutput {
+                Ok(output) => {
+                    if !output.status.success() {
+                        eprintln!("Error in linking:\n{}", String::from_utf8_lossy(&output.stderr));
+                        process::exit(1);
+                    }
+                }
+                Err(err) => {
+                    eprintln!("Could not run linker '{}': {}", options.linker, err);
+                    process::exit(1);
+                }
+            }
         }
     }
 
-    //Initialize LLVM targets
-    Target::initialize_all(&InitializationConfig::default());
-
-    //Define the llvm target platform
-    let targTriple = TargetMachine::get_default_triple();
-    // let targTriple = TargetTriple::create(targArch);
-
-    //Create the actual target object
-    let target = Target::from_triple(&targTriple).expect("Failed to get target");
-    let targetMachineCheck = target.create_target_machine(
-        &targTriple,
-        "generic",                        //For zen architecture machine
-        "",                         //No feature inclusion
-        OptimizationLevel::None,       //No optimizations
-        RelocMode::Default,                  //default relocation
-        CodeModel::Default,                  //default code model
-    );
-
-    //Extract the target machine value
-    let mut targetMachine: TargetMachine;
-    match targetMachineCheck{
-        Some(target) => {
-            targetMachine = target;
-        }
-        None => {
-            println!("no target machine");
-            return Ok(());
+    //Exit
+    Ok(())
+}
+
+
+
+///////////////////////// Tests /////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Parses a list of arguments the way they would come from the command line
+    fn parse(args: &[&str]) -> Result<CompilerOptions, String> {
+        return CompilerOptions::parseArgs(args.iter().map(|arg| arg.to_string()).collect());
+    }
+
+    #[test]
+    fn defaultsWithOnlyAnInput() {
+        let options = parse(&["prog.src"]).unwrap();
+        assert_eq!(options.inputPath, "prog.src");
+        assert_eq!(options.outputPath, None);
+        assert_eq!(options.emit, EmitType::Exe);
+        assert_eq!(options.runtimeLib, "./funcLib.a");
+        assert_eq!(options.linker, "clang");
+        assert_eq!(options.target, None);
+        assert!(options.boundsChecks);
+        assert_eq!(options.getOutputPath(), PathBuf::from("prog"));
+    }
+
+    #[test]
+    fn outputPath() {
+        assert_eq!(parse(&["-o", "build/out", "prog.src"]).unwrap().getOutputPath(), PathBuf::from("build/out"));
+        assert_eq!(parse(&["prog.src", "-o", "-"]).unwrap().outputPath, Some("-".to_string()));
+    }
+
+    #[test]
+    fn emitTypes() {
+        let cases = [
+            ("tokens", EmitType::Tokens, "prog.tokens"),
+            ("ast", EmitType::Ast, "prog.ast"),
+            ("llvm-ir", EmitType::LlvmIr, "prog.ll"),
+            ("bc", EmitType::Bitcode, "prog.bc"),
+            ("asm", EmitType::Asm, "prog.s"),
+            ("obj", EmitType::Object, "prog.o"),
+            ("exe", EmitType::Exe, "prog"),
+        ];
+        for (name, emit, outPath) in cases {
+            //Both --emit=type and --emit type are accepted
+            let inline = parse(&[&format!("--emit={}", name), "dir/prog.src"]).unwrap();
+            let separate = parse(&["--emit", name, "dir/prog.src"]).unwrap();
+            assert_eq!(inline.emit, emit);
+            assert_eq!(separate.emit, emit);
+            assert_eq!(inline.getOutputPath(), PathBuf::from(outPath));
         }
     }
 
-    //Define the path where the object file will be stored
-    let path = Path::new("output.o");
+    #[test]
+    fn inputWithoutExtensionIsNotOverwritten() {
+        assert_eq!(parse(&["prog"]).unwrap().getOutputPath(), PathBuf::from("prog.out"));
+    }
 
-    //Write the generated code to an object file
-    let writeCode = targetMachine.write_to_file(&finalMod, inkwell::targets::FileType::Object, &path);
-    if let Err(e) = writeCode {
-        println!("Error generating object file: {}", e);
+    #[test]
+    fn runtimeLibLinkerAndTarget() {
+        let options = parse(&["--runtime-lib", "/opt/lib/funcLib.a", "--linker=gcc", "--target", "x86_64-unknown-linux-gnu", "prog.src"]).unwrap();
+        assert_eq!(options.runtimeLib, "/opt/lib/funcLib.a");
+        assert_eq!(options.linker, "gcc");
+        assert_eq!(options.target, Some("x86_64-unknown-linux-gnu".to_string()));
+
+        let options = parse(&["prog.src", "--runtime-lib=lib.a", "--linker", "cc", "--target=aarch64-linux-gnu"]).unwrap();
+        assert_eq!(options.runtimeLib, "lib.a");
+        assert_eq!(options.linker, "cc");
+        assert_eq!(options.target, Some("aarch64-linux-gnu".to_string()));
     }
 
-    //Create the LLVM IR code file
-    let outPath = Path::new("./out").with_extension("ll");
-    finalMod.print_to_file(&outPath).expect("Could not print ll file");
+    #[test]
+    fn boundsChecksFlags() {
+        assert!(parse(&["--bounds-checks", "prog.src"]).unwrap().boundsChecks);
+        assert!(!parse(&["--no-bounds-checks", "prog.src"]).unwrap().boundsChecks);
 
-    //Defines the path of the library (where the builtins are defined)
-    let libPath = Path::new("./funcLib").with_extension("a");
+        //The last flag given wins
+        assert!(parse(&["--no-bounds-checks", "--bounds-checks", "prog.src"]).unwrap().boundsChecks);
+        assert!(!parse(&["--bounds-checks", "--no-bounds-checks", "prog.src"]).unwrap().boundsChecks);
+    }
 
-    //Create the final output by using clang as the linker for the libray
-    let finalOutput = Command::new("clang")
-        .current_dir("./")
-        .arg(&outPath)
-        .arg(&libPath)
-        .output()
-        .expect("Clang Linker failed");
+    #[test]
+    fn unknownOption() {
+        assert_eq!(parse(&["--optimize", "prog.src"]).err(), Some("Unknown option '--optimize'".to_string()));
+        assert_eq!(parse(&["-x", "prog.src"]).err(), Some("Unknown option '-x'".to_string()));
+    }
 
-    //Checks the success of the linker
-    let checkSuccess = finalOutput.status;
-    if !checkSuccess.success() {
-        println!("Error in linking");
-        return Ok(());
+    #[test]
+    fn missingValues() {
+        for flag in ["-o", "--emit", "--runtime-lib", "--linker", "--target"] {
+            assert_eq!(parse(&["prog.src", flag]).err(), Some(format!("Missing value for {}", flag)));
+        }
     }
 
-    //Exit
-    Ok(())
-}
\ No newline at end of file
+    #[test]
+    fn badEmitType() {
+        let err = parse(&["--emit=exec", "prog.src"]).err().unwrap();
+        assert!(err.starts_with("Unknown emit type 'exec'"), "{}", err);
+    }
+
+    #[test]
+    fn inputFileErrors() {
+        assert_eq!(parse(&[]).err(), Some("Please specify an input file".to_string()));
+        assert_eq!(parse(&["-o", "out"]).err(), Some("Please specify an input file".to_string()));
+        assert_eq!(parse(&["a.src", "b.src"]).err(), Some("Only one input file can be given, found 'b.src'".to_string()));
+    }
+
+    #[test]
+    fn helpGivesUsage() {
+        assert_eq!(parse(&["--help"]).err(), Some(USAGE.to_string()));
+        assert_eq!(parse(&["prog.src", "-h"]).err(), Some(USAGE.to_string()));
+    }
+}
+
+///////////////////////// /Tests /////////////////////////
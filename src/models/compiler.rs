@@ -18,8 +18,8 @@ extern crate unicode_segmentation;
 //package imports
 use {
     crate::models::{lexer::Lexer, parser::{Expr, Parser, *}, typechecker::{
-        SymbolTable, SyntaxChecker
-    }}, anyhow::Result, core::panic, inkwell::{builder::Builder, context::{self, Context}, module::Module, types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType, PointerType}, values::*, AddressSpace, FloatPredicate, IntPredicate}, parse_display::Display, std::{
+        Coercion, SymbolTable, SyntaxChecker
+    }, reporting::{Reporting, Diagnostic, Span}}, anyhow::Result, core::panic, inkwell::{basic_block::BasicBlock, builder::Builder, context::{self, Context}, module::{Linkage, Module}, types::{AnyTypeEnum, BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType, PointerType}, values::*, AddressSpace, FloatPredicate, IntPredicate}, parse_display::Display, std::{
         array, collections::HashMap, env::{self, args}, ffi::CString, fmt, rc::Rc
     }
 };
@@ -93,6 +93,16 @@ pub enum tokenTypeEnum{
     END_FOR,
     COMMA,
     FOR,
+    WHILE,
+    END_WHILE,
+    BREAK,
+    CONTINUE,
+    CASE,
+    WHEN,
+    OTHERS,
+    END_CASE,
+    ELSIF,
+    FORWARD,
 
     PROCEDURE_CALL,
     TRUE,
@@ -151,6 +161,16 @@ impl fmt::Display for tokenTypeEnum {
             tokenTypeEnum::COMMA => "COMMA",
             tokenTypeEnum::END_FOR => "END_FOR",
             tokenTypeEnum::FOR => "FOR",
+            tokenTypeEnum::WHILE => "WHILE",
+            tokenTypeEnum::END_WHILE => "END_WHILE",
+            tokenTypeEnum::BREAK => "BREAK",
+            tokenTypeEnum::CONTINUE => "CONTINUE",
+            tokenTypeEnum::CASE => "CASE",
+            tokenTypeEnum::WHEN => "WHEN",
+            tokenTypeEnum::OTHERS => "OTHERS",
+            tokenTypeEnum::END_CASE => "END_CASE",
+            tokenTypeEnum::ELSIF => "ELSIF",
+            tokenTypeEnum::FORWARD => "FORWARD",
             tokenTypeEnum::PROCEDURE_CALL => "PROCEDURE_CALL",
             tokenTypeEnum::AND => "AND",
             tokenTypeEnum::OR => "OR",
@@ -175,11 +195,19 @@ pub struct Compiler<'ctx> {
     module: Module<'ctx>,       //the llvm module
     builder: Builder<'ctx>,     //the llvm builder
     programAst: Stmt,           //the programAst that will be run through to generate llvm IR
-    scope: i32,                 //The scope (i am not sure i need this)
+    procScopes: Vec<HashMap<String, FunctionValue<'ctx>>>, //The procedures visible in each scope, innermost last, mirrors the SyntaxChecker scopes
+    procPath: Vec<String>,      //The names of the program and procedures enclosing the current scope, used to mangle procedure names
     pub localTable: HashMap<String, PointerValue<'ctx>>, // Local table for the current scope
     pub globalTable: &'ctx mut HashMap<String, PointerValue<'ctx>>, // Shared global table
     pub name: String,
     pub i: i32,
+    pub reports: Reporting,     //The errors and warnings found while generating code
+    pub fileName: String,       //The name of the source file, used for diagnostics
+    curLine: usize,            //The line of the statement currently being compiled
+    pub boundsChecks: bool,     //Whether array indexes are checked at runtime before every access, on unless turned off
+    arrayNames: HashMap<String, PointerValue<'ctx>>, //The name text of each array given to boundserror, so every check on an array shares one global
+    loopTargets: Vec<(BasicBlock<'ctx>, BasicBlock<'ctx>)>, //The (continue, break) blocks of each loop enclosing the current statement, innermost last
+    paramModes: HashMap<String, Vec<ParamMode>>, //How each parameter of the declared procedures is passed, by symbol name, built in functions take everything in
 }
 
 impl<'ctx> Compiler<'ctx> {
@@ -195,7 +223,8 @@ impl<'ctx> Compiler<'ctx> {
         let mut i: i32 = 0; 
         Compiler {
             programAst,
-            scope: 0,
+            procScopes: vec![HashMap::new()],
+            procPath: Vec::new(),
             localTable: HashMap::new(),
             globalTable,
             name,
@@ -203,6 +232,715 @@ impl<'ctx> Compiler<'ctx> {
             module,
             builder,
             i,
+            reports: Reporting::new(),
+            fileName: String::new(),
+            curLine: 0,
+            boundsChecks: true,
+            arrayNames: HashMap::new(),
+            loopTargets: Vec::new(),
+            paramModes: HashMap::new(),
+        }
+    }
+
+    //Reports a code generation error on the line of the statement currently being compiled
+    fn reportError(&mut self, message: String) {
+        let span = Span::line(self.fileName.clone(), self.curLine);
+        self.reports.report(Diagnostic::error("C001", message).withSpan(span));
+    }
+
+    //Reports an internal compiler error, code generation failed on something the checker let through
+    //These point at the statement being compiled when it happened
+    fn reportIce(&mut self, message: String) {
+        let span = Span::line(self.fileName.clone(), self.curLine);
+        let diagnostic = Diagnostic::error("C000", format!("internal compiler error: {}", message))
+            .withSpan(span)
+            .withNote("this is a bug in the compiler, not in the program being compiled".to_string());
+        self.reports.report(diagnostic);
+    }
+
+    //Strings are pointers to null terminated text, either a constant in the module or something
+    //getstring put on the heap. Assigning one just copies the pointer, they are never changed in place
+    fn stringType(&self) -> PointerType<'ctx> {
+        return self.context.i8_type().ptr_type(AddressSpace::default());
+    }
+
+    //Adds the text as a constant in the module and returns a string pointer to it
+    //This does not need a builder so it works for global initializers as well
+    fn constString(&self, text: &str) -> PointerValue<'ctx> {
+        let textVal = self.context.const_string(text.as_bytes(), true);
+        let textGlob = self.module.add_global(textVal.get_type(), Some(AddressSpace::default()), "str");
+        textGlob.set_initializer(&textVal);
+        textGlob.set_constant(true);
+        textGlob.set_linkage(Linkage::Private);
+        return textGlob.as_pointer_value().const_cast(self.stringType());
+    }
+
+
+    //Gets the name of an array as a string for a bounds check, made once per array and reused by every check on it
+    fn arrayNameString(&mut self, arrName: &str) -> PointerValue<'ctx> {
+        if let Some(namePtr) = self.arrayNames.get(arrName) {
+            return *namePtr;
+        }
+        let namePtr = self.constString(arrName);
+        self.arrayNames.insert(arrName.to_string(), namePtr);
+        return namePtr;
+    }
+
+    //Gets the LLVM type used to store a variable of the given type
+    //Arrays are stored whole with their declared size
+    fn llvmType(&self, varType: &VarType) -> Result<BasicTypeEnum<'ctx>, String> {
+        match varType {
+            VarType::Bool => Ok(self.context.bool_type().as_basic_type_enum()),
+            VarType::Float => Ok(self.context.f32_type().as_basic_type_enum()),
+            VarType::Int => Ok(self.context.i32_type().as_basic_type_enum()),
+            VarType::Str => Ok(self.stringType().as_basic_type_enum()),
+            VarType::Array(elemType, size) => Ok(self.llvmType(elemType)?.array_type(*size as u32).as_basic_type_enum()),
+            //Nothing is ever stored as void, procedures that return nothing get their type from procFnType instead
+            //The checker never lets a void value through, so getting here is a compiler bug
+            VarType::Void => Err(format!("void has no stored LLVM type")),
+        }
+    }
+
+    //Builds the LLVM function type for a procedure, a procedure that returns nothing becomes a void function
+    fn procFnType(&self, retType: &VarType, paramTypes: &[BasicMetadataTypeEnum<'ctx>]) -> Result<FunctionType<'ctx>, String> {
+        match retType {
+            VarType::Void => Ok(self.context.void_type().fn_type(paramTypes, false)),
+            _ => Ok(self.llvmType(retType)?.fn_type(paramTypes, false)),
+        }
+    }
+
+    //Gets the value a variable of the given type starts out with
+    //Everything is zeroed except strings, which start as the empty string
+    fn initialValue(&self, varType: &VarType) -> Result<BasicValueEnum<'ctx>, String> {
+        match varType {
+            VarType::Str => Ok(self.constString("").as_basic_value_enum()),
+            VarType::Array(elemType, size) if **elemType == VarType::Str => {
+                let emptyStr = self.constString("");
+                Ok(self.stringType().const_array(&vec![emptyStr; *size as usize]).as_basic_value_enum())
+            }
+            _ => Ok(self.llvmType(varType)?.const_zero()),
+        }
+    }
+
+    //Builds the symbol name for a procedure declared in the current scope from the path of enclosing procedures
+    //A number is added on the end if the name is somehow already in the module, so every procedure gets its own symbol
+    fn mangleProcName(&self, procName: &String) -> String {
+        let mut pathNames = self.procPath.clone();
+        pathNames.push(procName.clone());
+        let baseName = pathNames.join(".");
+        let mut mangledName = baseName.clone();
+        let mut suffix = 1;
+        while self.module.get_function(&mangledName).is_some() {
+            mangledName = format!("{}.{}", baseName, suffix);
+            suffix += 1;
+        }
+        return mangledName;
+    }
+
+    //Finds the procedure a call refers to, checking the innermost scope first
+    fn lookupProc(&self, procName: &String) -> Option<FunctionValue<'ctx>> {
+        for procScope in self.procScopes.iter().rev() {
+            if let Some(function) = procScope.get(procName) {
+                return Some(*function);
+            }
+        }
+        return None;
+    }
+
+    //Declares a procedure in the current scope without compiling its body, this is all a forward declaration does
+    //The function gets a mangled name with the path of enclosing procedures so nested and sibling procedures never
+    //collide with each other or with the built in functions
+    fn declareProc(&mut self, procRetType: &VarType, procName: &String, params: &Stmt) -> Result<FunctionValue<'ctx>, String> {
+        let mangledName = self.mangleProcName(procName);
+
+        //Creates a vec for the param types, and one for how each is passed
+        let mut paramTypes: Vec<BasicTypeEnum> = Vec::new();
+        let mut procModes: Vec<ParamMode> = Vec::new();
+
+        //Parses the params
+        match params.clone(){
+            Stmt::Block(params, lineNum) => {
+                for param in params{
+                    match param.clone(){
+                        //Out and in out parameters are passed as a pointer to the caller's variable
+                        Stmt::ParamDecl(varName, varType, paramMode, lineNum) => {
+                            let paramType = self.llvmType(&varType)?;
+                            if paramMode == ParamMode::In {
+                                paramTypes.push(paramType.clone());
+                            } else {
+                                paramTypes.push(paramType.ptr_type(AddressSpace::default()).as_basic_type_enum());
+                            }
+                            procModes.push(paramMode);
+                        }
+                        _ => {
+                            return Err(format!("Function delcaration parameters can only be local variable declarations"));
+                        }
+                    }
+                }
+            }
+            Stmt::VarDecl(varName, varType, lineNum) => {
+                let paramType = self.llvmType(&varType)?;
+                paramTypes.push(paramType.clone());
+                
+            }
+            _ => {
+                return Err(format!("Function delcaration parameters can only be local variable declarations"));
+            }
+        }
+            
+        // println!("Created param list");
+        
+        //Converts the parameters into a useable form for the inkwell function thing
+        let paramTypesSlice: Vec<BasicMetadataTypeEnum> = paramTypes.iter().map(|&ty| ty.into()).collect();
+        let paramTypesSlice = &paramTypesSlice[..];
+
+        //creates the function type structure from the procedure return type
+        let funcType = self.procFnType(&procRetType, paramTypesSlice)?;
+
+        //Adds the function to the module and registers it in the enclosing scope
+        let procFunVal = self.module.add_function(&mangledName, funcType, Some(Linkage::Internal));
+        self.paramModes.insert(mangledName.clone(), procModes);
+        if let Some(curScope) = self.procScopes.last_mut() {
+            curScope.insert(procName.clone(), procFunVal);
+        }
+        return Ok(procFunVal);
+    }
+
+    //Calls a procedure with the given arguments, converting each one to the type of its parameter
+    //The call site is returned as is, since a procedure that returns nothing has no value to give back
+    fn compileCall(&mut self, procName: &String, params: &Option<Vec<Expr>>, builder: &Builder<'ctx>, localTable: &mut HashMap<String, PointerValue<'ctx>>) -> Result<CallSiteValue<'ctx>, String> {
+        //Resolves the procedure lexically, from the innermost scope out to the built in functions
+        let function: FunctionValue;
+        match self.lookupProc(procName) {
+            Some(fun) => {
+                function = fun;
+            }
+            None => {
+                return Err(format!("Procedure: {} not found in this scope", procName.clone()));
+            }
+        }
+
+        //How each parameter is passed, the built in functions are not in the table and take everything in
+        let funcName = function.get_name().to_string_lossy().to_string();
+        let procModes = self.paramModes.get(&funcName).cloned().unwrap_or_default();
+
+        //Compile params and add their values to a vector
+        //Out and in out arguments are given as a pointer to the variable instead of its value
+        let mut compiledParams: Vec<BasicValueEnum> = Vec::new();
+        if let Some(paramExprs) = params.clone(){
+            for (paramInd, param) in paramExprs.into_iter().enumerate(){
+                if procModes.get(paramInd).map_or(false, |mode| *mode != ParamMode::In) {
+                    let argPtr = self.argumentPtr(&param, builder, localTable)?;
+                    compiledParams.push(argPtr.as_basic_value_enum());
+                    continue;
+                }
+                let paramCheck = self.compileExpr(&param.clone(), builder, localTable);
+                match paramCheck{
+                    Ok(val) => {
+                        compiledParams.push(val.clone());
+                    }
+                    Err(err) => {
+                        let errMsg = format!("Error parsing function call param: {}", err.clone());
+                        return Err(format!("{}", errMsg));
+                    }
+                }
+            }
+        }
+
+        //Converts each argument to the type of its parameter, pointers for out parameters already match exactly
+        let paramTypes = function.get_type().get_param_types();
+        let mut convertedParams: Vec<BasicValueEnum> = Vec::new();
+        for (paramInd, (paramVal, paramType)) in compiledParams.into_iter().zip(paramTypes.into_iter()).enumerate() {
+            if procModes.get(paramInd).map_or(false, |mode| *mode != ParamMode::In) {
+                convertedParams.push(paramVal);
+            } else {
+                convertedParams.push(self.convertValue(builder, paramVal, paramType)?);
+            }
+        }
+
+        //COnvert teh vector of params to correct type for calling function
+        let params: Vec<BasicMetadataValueEnum> = convertedParams.into_iter().map(|val| val.into()).collect();
+        let parmVals = params.as_slice();
+
+
+
+
+        //Create the function call
+        let procCallRes = builder.build_call(function, parmVals, "callProc");
+        
+        match procCallRes{
+            Ok(val) => {
+                return Ok(val);
+            }
+            Err(err) => {
+                let errMsg = format!("Error calling procedure");
+                return Err(format!("{}", errMsg));
+            }
+        }
+    }
+
+    //Gets the pointer to pass for an out or in out argument, the type checker makes sure it is a variable or array element
+    fn argumentPtr(&mut self, arg: &Expr, builder: &Builder<'ctx>, localTable: &mut HashMap<String, PointerValue<'ctx>>) -> Result<PointerValue<'ctx>, String> {
+        match arg {
+            Expr::VarRef(varName) => {
+                match localTable.get(varName).or(self.globalTable.get(varName)) {
+                    Some(ptr) => {
+                        return Ok(*ptr);
+                    }
+                    None => {
+                        return Err(format!("variable {} not found", varName.clone()));
+                    }
+                }
+            }
+            Expr::ArrayRef(arrName, indexExpr) => {
+                return self.arrayElementPtr(arrName, indexExpr, builder, localTable);
+            }
+            _ => {
+                return Err(format!("{} cannot be passed to an out parameter", arg));
+            }
+        }
+    }
+
+    //Compiles the condition of an if or for into an i1 to branch on
+    //Conditions can be any bool expression, integers are false when 0 and true otherwise
+    fn compileCondition(&mut self, condExpr: &Expr, builder: &Builder<'ctx>, localTable: &mut HashMap<String, PointerValue<'ctx>>) -> Result<IntValue<'ctx>, String> {
+        let condVal = self.compileExpr(condExpr, builder, localTable)?;
+        match self.convertValue(builder, condVal, self.context.bool_type().as_basic_type_enum())? {
+            BasicValueEnum::IntValue(condition) => {
+                return Ok(condition);
+            }
+            _ => {
+                return Err(format!("Condition must evaluate to a bool"));
+            }
+        }
+    }
+
+    //Checks if the block the builder is in already ends in a terminator like a return
+    //Nothing can be added after one, and the flow checker has already warned that the statements after it can never run
+    fn blockClosed(&self, builder: &Builder<'ctx>) -> bool {
+        match builder.get_insert_block() {
+            Some(curBlock) => curBlock.get_terminator().is_some(),
+            None => false,
+        }
+    }
+
+    //Branches to the target unless the current block already ends in a terminator like a return
+    fn branchIfOpen(&self, builder: &Builder<'ctx>, target: BasicBlock<'ctx>) {
+        if !self.blockClosed(builder) {
+            let _ = builder.build_unconditional_branch(target);
+        }
+    }
+
+    //Gets a pointer to an element of an array variable, looking in the local table before the global one
+    fn arrayElementPtr(&mut self, arrName: &String, indexExpr: &Expr, builder: &Builder<'ctx>, localTable: &mut HashMap<String, PointerValue<'ctx>>) -> Result<PointerValue<'ctx>, String> {
+        //Gets the value of the index expression
+        let indexVal = self.compileExpr(indexExpr, builder, localTable)?;
+
+        //Gets the pointer to the array
+        let arrayPtr: PointerValue;
+        match localTable.get(arrName) {
+            Some(ptr) => {
+                arrayPtr = ptr.clone();
+            }
+            None => {
+                match self.globalTable.get(arrName) {
+                    Some(ptr) => {
+                        arrayPtr = ptr.clone();
+                    }
+                    None => {
+                        return Err(format!("variable {} not found", arrName.clone()));
+                    }
+                }
+            }
+        }
+
+        //Indexes are integers, a bool is used as 0 or 1
+        if !indexVal.is_int_value() {
+            return Err(format!("Can only index {} by integer", arrName.clone()));
+        }
+        let indexInt: IntValue;
+        match self.convertValue(builder, indexVal, self.context.i32_type().as_basic_type_enum())? {
+            BasicValueEnum::IntValue(val) => {
+                indexInt = val;
+            }
+            _ => {
+                return Err(format!("Can only index by integer"));
+            }
+        }
+
+        //Makes sure the index is inside the array before using it
+        if self.boundsChecks {
+            self.buildBoundsCheck(builder, arrName, arrayPtr, indexInt)?;
+        }
+
+        //The first index steps through the pointer to the array, the second picks the element
+        let zero = self.context.i32_type().const_int(0, false);
+        let indexList = [zero, indexInt];
+        let checkIndexPtr = unsafe { builder.build_gep(arrayPtr, &indexList, "arrayIndexPtr") };
+        match checkIndexPtr {
+            Ok(ptr) => {
+                return Ok(ptr);
+            }
+            Err(err) => {
+                return Err(format!("Error getting index of array {}: {}", arrName.clone(), err));
+            }
+        }
+    }
+
+    //Gets the language type an llvm type holds, None for arrays and anything else without a scalar type
+    fn varTypeOf(&self, llvmType: BasicTypeEnum<'ctx>) -> Option<VarType> {
+        match llvmType {
+            BasicTypeEnum::IntType(intType) => {
+                if intType.get_bit_width() == 1 {
+                    return Some(VarType::Bool);
+                } else {
+                    return Some(VarType::Int);
+                }
+            }
+            BasicTypeEnum::FloatType(_) => {
+                return Some(VarType::Float);
+            }
+            BasicTypeEnum::PointerType(_) => {
+                return Some(VarType::Str);
+            }
+            _ => {
+                return None;
+            }
+        }
+    }
+
+    //Checks an array index at runtime, calling the boundserror routine in funcLib to print the error and exit if it is outside the array
+    //Constant indexes that are known to be inside the array are not checked
+    fn buildBoundsCheck(&mut self, builder: &Builder<'ctx>, arrName: &String, arrayPtr: PointerValue<'ctx>, indexInt: IntValue<'ctx>) -> Result<(), String> {
+        let i32Type = self.context.i32_type();
+        let arrSize = match arrayPtr.get_type().get_element_type() {
+            AnyTypeEnum::ArrayType(arrType) => arrType.len(),
+            _ => {
+                return Err(format!("{} is not an array", arrName.clone()));
+            }
+        };
+        if let Some(constIndex) = indexInt.get_sign_extended_constant() {
+            if constIndex >= 0 && constIndex < arrSize as i64 {
+                return Ok(());
+            }
+        }
+
+        //Compared unsigned, so a negative index looks too big and fails the same check
+        let sizeVal = i32Type.const_int(arrSize as u64, false);
+        let outOfBounds = match builder.build_int_compare(IntPredicate::UGE, indexInt, sizeVal, "outOfBounds") {
+            Ok(val) => val,
+            Err(err) => {
+                return Err(format!("{}", err));
+            }
+        };
+
+        let function = match builder.get_insert_block().and_then(|block| block.get_parent()) {
+            Some(function) => function,
+            None => {
+                return Err(format!("Array access outside of a function"));
+            }
+        };
+        let failBlock = self.context.append_basic_block(function, "boundsFail");
+        let okBlock = self.context.append_basic_block(function, "boundsOk");
+        let _ = builder.build_conditional_branch(outOfBounds, failBlock, okBlock);
+
+        //Reports the index, the array and the line, boundserror never returns
+        builder.position_at_end(failBlock);
+        let boundsError = match self.module.get_function("boundserror") {
+            Some(fun) => fun,
+            None => {
+                return Err(format!("boundserror is not defined"));
+            }
+        };
+        let lineNum = self.curLine as u64;
+        let args: Vec<BasicMetadataValueEnum> = vec![
+            indexInt.into(),
+            self.arrayNameString(arrName).into(),
+            sizeVal.into(),
+            i32Type.const_int(lineNum, false).into(),
+        ];
+        let _ = builder.build_call(boundsError, &args, "boundsError");
+        let _ = builder.build_unreachable();
+
+        builder.position_at_end(okBlock);
+        return Ok(());
+    }
+
+    //Converts a value to the given type using the conversion the coercion table gives for the two types
+    //This is the same table the checker uses, so anything it allowed can be converted here
+    fn convertValue(&self, builder: &Builder<'ctx>, value: BasicValueEnum<'ctx>, targType: BasicTypeEnum<'ctx>) -> Result<BasicValueEnum<'ctx>, String> {
+        let (fromType, toType) = match (self.varTypeOf(value.get_type()), self.varTypeOf(targType)) {
+            (Some(fromType), Some(toType)) => (fromType, toType),
+            //Arrays are only ever used as the same type
+            _ => {
+                if value.get_type() == targType {
+                    return Ok(value);
+                } else {
+                    return Err(format!("Cannot convert a value of type {} to {}", value.get_type(), targType));
+                }
+            }
+        };
+
+        let converted = match Coercion::find(&fromType, &toType) {
+            Coercion::Same => {
+                return Ok(value);
+            }
+            Coercion::IntToFloat => {
+                builder.build_signed_int_to_float(value.into_int_value(), targType.into_float_type(), "intToFloat").map(|val| val.as_basic_value_enum())
+            }
+            Coercion::FloatToInt => {
+                builder.build_float_to_signed_int(value.into_float_value(), targType.into_int_type(), "floatToInt").map(|val| val.as_basic_value_enum())
+            }
+            //Any nonzero integer is true
+            Coercion::IntToBool => {
+                let intVal = value.into_int_value();
+                builder.build_int_compare(IntPredicate::NE, intVal, intVal.get_type().const_zero(), "intToBool").map(|val| val.as_basic_value_enum())
+            }
+            //Bools are unsigned so true becomes 1
+            Coercion::BoolToInt => {
+                builder.build_int_z_extend(value.into_int_value(), targType.into_int_type(), "boolToInt").map(|val| val.as_basic_value_enum())
+            }
+            Coercion::Incompatible => {
+                return Err(format!("Cannot convert {} to {}", fromType, toType));
+            }
+        };
+        match converted {
+            Ok(val) => {
+                return Ok(val);
+            }
+            Err(err) => {
+                return Err(format!("Error converting value: {}", err));
+            }
+        }
+    }
+
+    //Converts both operands of an arithmetic or relational operation to their common type from the coercion table
+    fn unifyOperands(&self, builder: &Builder<'ctx>, op1Val: BasicValueEnum<'ctx>, op2Val: BasicValueEnum<'ctx>) -> Result<(BasicValueEnum<'ctx>, BasicValueEnum<'ctx>), String> {
+        let commonType = match (self.varTypeOf(op1Val.get_type()), self.varTypeOf(op2Val.get_type())) {
+            (Some(type1), Some(type2)) => Coercion::commonType(&type1, &type2),
+            _ => None,
+        };
+        let targType = match commonType {
+            Some(VarType::Float) => self.context.f32_type().as_basic_type_enum(),
+            Some(VarType::Int) => self.context.i32_type().as_basic_type_enum(),
+            _ => {
+                return Err(format!("Cannot use {} and {} together in an operation", op1Val.get_type(), op2Val.get_type()));
+            }
+        };
+        let op1Conv = self.convertValue(builder, op1Val, targType)?;
+        let op2Conv = self.convertValue(builder, op2Val, targType)?;
+        return Ok((op1Conv, op2Conv));
+    }
+
+
+    //Builds an arithmetic operation on two single values
+    fn buildArithmetic(&self, builder: &Builder<'ctx>, op: &Operator, op1Val: BasicValueEnum<'ctx>, op2Val: BasicValueEnum<'ctx>) -> Result<BasicValueEnum<'ctx>, String> {
+        //Casts both to their common type, so an integer mixed with a float is done in float
+        let (op1Val, op2Val) = self.unifyOperands(builder, op1Val, op2Val)?;
+
+        //a match case to handle the different types of operators
+        let retOp = match (op1Val, op2Val) {
+            (BasicValueEnum::FloatValue(op1Float), BasicValueEnum::FloatValue(op2Float)) => {
+                match op {
+                    Operator::Add => builder.build_float_add(op1Float, op2Float, "addFloat"),
+                    Operator::Sub => builder.build_float_sub(op1Float, op2Float, "subFloat"),
+                    Operator::Mul => builder.build_float_mul(op1Float, op2Float, "multiplyFloat"),
+                    Operator::Div => builder.build_float_div(op1Float, op2Float, "divideFloat"),
+                    _ => {
+                        //This should never happen because of parsing and error checking
+                        return Err(format!("Improper operator for arthimatic operation"));
+                    }
+                }.map(|val| val.as_basic_value_enum())
+            }
+            (BasicValueEnum::IntValue(op1Int), BasicValueEnum::IntValue(op2Int)) => {
+                match op {
+                    Operator::Add => builder.build_int_add(op1Int, op2Int, "addInt"),
+                    Operator::Sub => builder.build_int_sub(op1Int, op2Int, "subInt"),
+                    Operator::Mul => builder.build_int_mul(op1Int, op2Int, "multiplyInt"),
+                    Operator::Div => builder.build_int_signed_div(op1Int, op2Int, "divideInt"),
+                    _ => {
+                        //This should never happen because of parsing and error checking
+                        return Err(format!("Improper operator for arthimatic operation"));
+                    }
+                }.map(|val| val.as_basic_value_enum())
+            }
+            _ => {
+                return Err(format!("Unsupported types for arithmetic operation"));
+            }
+        };
+        match retOp {
+            Ok(result) => {
+                return Ok(result);
+            }
+            Err(errMsg) => {
+                return Err(format!("{}", errMsg));
+            }
+        }
+    }
+
+    //Allocates space at the start of the function the builder is in
+    //Keeping temporaries in the entry block means a loop does not grow the stack every time around
+    fn entryAlloca(&self, builder: &Builder<'ctx>, allocType: BasicTypeEnum<'ctx>, name: &str) -> Result<PointerValue<'ctx>, String> {
+        let function = match builder.get_insert_block().and_then(|block| block.get_parent()) {
+            Some(function) => function,
+            None => {
+                return Err(format!("Cannot allocate {} outside of a function", name));
+            }
+        };
+        let entry = match function.get_first_basic_block() {
+            Some(entry) => entry,
+            None => {
+                return Err(format!("Function has no entry block to allocate {} in", name));
+            }
+        };
+        let entryBuilder = self.context.create_builder();
+        match entry.get_first_instruction() {
+            Some(firstInstr) => entryBuilder.position_before(&firstInstr),
+            None => entryBuilder.position_at_end(entry),
+        }
+        match entryBuilder.build_alloca(allocType, name) {
+            Ok(ptr) => {
+                return Ok(ptr);
+            }
+            Err(err) => {
+                return Err(format!("Error allocating {}: {}", name, err));
+            }
+        }
+    }
+
+    //Builds an arithmetic operation on whole arrays, one element at a time in a loop
+    //Both arrays have to be the same size, and a single value on one side is used with every element of the other
+    fn arrayArithmetic(&mut self, builder: &Builder<'ctx>, op: &Operator, op1Val: BasicValueEnum<'ctx>, op2Val: BasicValueEnum<'ctx>) -> Result<BasicValueEnum<'ctx>, String> {
+        let i32Type = self.context.i32_type();
+
+        //Gets the size of the arrays and the type of each side's elements
+        let mut arrSize: Option<u32> = None;
+        let mut elemTypes: Vec<BasicTypeEnum<'ctx>> = Vec::new();
+        for operand in [op1Val, op2Val] {
+            if let BasicValueEnum::ArrayValue(arrVal) = operand {
+                let size = arrVal.get_type().len();
+                if arrSize.is_some() && arrSize != Some(size) {
+                    return Err(format!("Array sizes do not match: {} and {}", arrSize.unwrap_or(0), size));
+                }
+                arrSize = Some(size);
+                elemTypes.push(arrVal.get_type().get_element_type());
+            } else {
+                elemTypes.push(operand.get_type());
+            }
+        }
+        let arrSize = arrSize.unwrap_or(0);
+
+        //The result holds the common type of the elements
+        let resultElemType = match (self.varTypeOf(elemTypes[0]), self.varTypeOf(elemTypes[1])) {
+            (Some(type1), Some(type2)) => match Coercion::commonType(&type1, &type2) {
+                Some(VarType::Float) => self.context.f32_type().as_basic_type_enum(),
+                Some(VarType::Int) => i32Type.as_basic_type_enum(),
+                _ => {
+                    return Err(format!("Cannot use {} and {} together in an arithmetic operation", type1, type2));
+                }
+            },
+            _ => {
+                return Err(format!("Unsupported array element types for arithmetic operation"));
+            }
+        };
+
+        //The arrays are stored so their elements can be indexed, along with space for the result and the loop index
+        let mut operandPtrs: Vec<Option<PointerValue<'ctx>>> = Vec::new();
+        for operand in [op1Val, op2Val] {
+            if let BasicValueEnum::ArrayValue(arrVal) = operand {
+                let arrPtr = self.entryAlloca(builder, arrVal.get_type().as_basic_type_enum(), "arrOperand")?;
+                let _ = builder.build_store(arrPtr, arrVal);
+                operandPtrs.push(Some(arrPtr));
+            } else {
+                operandPtrs.push(None);
+            }
+        }
+        let resultPtr = self.entryAlloca(builder, resultElemType.array_type(arrSize).as_basic_type_enum(), "arrResult")?;
+        let indexPtr = self.entryAlloca(builder, i32Type.as_basic_type_enum(), "arrIndex")?;
+        let _ = builder.build_store(indexPtr, i32Type.const_zero());
+
+        //set up the blocks of the loop
+        let function = match builder.get_insert_block().and_then(|block| block.get_parent()) {
+            Some(function) => function,
+            None => {
+                return Err(format!("Array operation outside of a function"));
+            }
+        };
+        let loopCond = self.context.append_basic_block(function, "arrCond");
+        let loopBody = self.context.append_basic_block(function, "arrBody");
+        let loopDone = self.context.append_basic_block(function, "arrDone");
+        let _ = builder.build_unconditional_branch(loopCond);
+
+        //Loops while the index is in the array
+        builder.position_at_end(loopCond);
+        let index = match builder.build_load(indexPtr, "index") {
+            Ok(val) => val.into_int_value(),
+            Err(err) => {
+                return Err(format!("Error loading array index: {}", err));
+            }
+        };
+        let inRange = match builder.build_int_compare(IntPredicate::SLT, index, i32Type.const_int(arrSize as u64, false), "inRange") {
+            Ok(val) => val,
+            Err(err) => {
+                return Err(format!("{}", err));
+            }
+        };
+        let _ = builder.build_conditional_branch(inRange, loopBody, loopDone);
+
+        //Works out one element of the result
+        builder.position_at_end(loopBody);
+        let zero = i32Type.const_zero();
+        let mut elemVals: Vec<BasicValueEnum<'ctx>> = Vec::new();
+        for (operand, operandPtr) in [op1Val, op2Val].into_iter().zip(operandPtrs.into_iter()) {
+            match operandPtr {
+                Some(arrPtr) => {
+                    let elemPtr = match unsafe { builder.build_gep(arrPtr, &[zero, index], "elemPtr") } {
+                        Ok(ptr) => ptr,
+                        Err(err) => {
+                            return Err(format!("Error indexing array operand: {}", err));
+                        }
+                    };
+                    match builder.build_load(elemPtr, "elem") {
+                        Ok(val) => elemVals.push(val),
+                        Err(err) => {
+                            return Err(format!("Error loading array element: {}", err));
+                        }
+                    }
+                }
+                None => {
+                    elemVals.push(operand);
+                }
+            }
+        }
+        let elemResult = self.buildArithmetic(builder, op, elemVals[0], elemVals[1])?;
+        let elemResult = self.convertValue(builder, elemResult, resultElemType)?;
+        let resultElemPtr = match unsafe { builder.build_gep(resultPtr, &[zero, index], "resultElemPtr") } {
+            Ok(ptr) => ptr,
+            Err(err) => {
+                return Err(format!("Error indexing array result: {}", err));
+            }
+        };
+        let _ = builder.build_store(resultElemPtr, elemResult);
+
+        //Moves on to the next element
+        let nextIndex = match builder.build_int_add(index, i32Type.const_int(1, false), "nextIndex") {
+            Ok(val) => val,
+            Err(err) => {
+                return Err(format!("{}", err));
+            }
+        };
+        let _ = builder.build_store(indexPtr, nextIndex);
+        let _ = builder.build_unconditional_branch(loopCond);
+
+        //The whole result array is the value of the expression
+        builder.position_at_end(loopDone);
+        match builder.build_load(resultPtr, "arrResult") {
+            Ok(val) => {
+                return Ok(val);
+            }
+            Err(err) => {
+                return Err(format!("Error loading array result: {}", err));
+            }
         }
     }
 
@@ -219,6 +957,20 @@ impl<'ctx> Compiler<'ctx> {
                 self.defineGetFloat();
                 self.definePutBool();
                 self.definePutFloat();
+                self.defineGetBool();
+                self.definePutStr();
+                self.defineGetStr();
+                if self.boundsChecks {
+                    self.defineBoundsError();
+                }
+
+                //The built in functions are in the outermost scope, so a procedure with the same name shadows them
+                for builtinName in ["getinteger", "getfloat", "getbool", "getstring", "putinteger", "putfloat", "putbool", "putstring"] {
+                    if let Some(builtinFunc) = self.module.get_function(builtinName) {
+                        self.procScopes[0].insert(builtinName.to_string(), builtinFunc);
+                    }
+                }
+                self.procPath.push(progName.clone());
                 
                 //Creates the main function in module
                 let i32Type = self.context.i32_type();
@@ -235,10 +987,12 @@ impl<'ctx> Compiler<'ctx> {
                 //Initializes all of the main function variables as globals
                 if let Stmt::Block(ref instrs, lineNum) = progHeader.clone() {
                     for instr in instrs {
-                        self.compileStmt(instr.clone(), &mainBuilder, &mut mainLocalTable, mainFunc);
+                        if let Err(msg) = self.compileStmt(instr.clone(), &mainBuilder, &mut mainLocalTable, mainFunc) {
+                            self.reportIce(msg);
+                        }
                     }
                 } else {
-                    println!("Problem with AST: header must be a Block");
+                    self.reportError("Problem with AST: header must be a Block".to_string());
                 }
 
 
@@ -255,34 +1009,63 @@ impl<'ctx> Compiler<'ctx> {
                 // Check if the variable is a Block and iterate through it
                 if let Stmt::Block(ref instrs, lineNum) = body.clone() {
                     for instr in instrs {
-                        let good = self.compileStmt(instr.clone(), &mainBuilder, &mut mainLocalTable, mainFunc);
+                        if self.blockClosed(&mainBuilder) {
+                            break;
+                        }
+                        //The rest of the body is skipped after an error as the function being built is no longer in a usable state
+                        if let Err(msg) = self.compileStmt(instr.clone(), &mainBuilder, &mut mainLocalTable, mainFunc) {
+                            self.reportIce(msg);
+                            break;
+                        }
                     }
                 } else {
-                    panic!("Problem with AST: header must be a Block");
+                    self.reportIce("Problem with AST: body must be a Block".to_string());
                 }
                 
                 //Creates the main function return, a 0 for success
-                let mainRet = i32Type.const_int(0, false);
-                let _ = mainBuilder.build_return(Some(&mainRet));
+                if !self.blockClosed(&mainBuilder) {
+                    let mainRet = i32Type.const_int(0, false);
+                    let _ = mainBuilder.build_return(Some(&mainRet));
+                }
             }
             _ => {
-                let errMsg = format!("ProgramAst must be a Program Stmt");
-                panic!("{}", errMsg);
+                self.reportIce("ProgramAst must be a Program Stmt".to_string());
             }
         }
         
+        //Any errors found while generating mean the module can not be used
+        if self.reports.status {
+            return Err(format!("{} error(s) found while generating code", self.reports.errors.len()));
+        }
+
+        //LLVM has to accept the module too, if it doesn't we generated something the checker should have caught
+        if let Err(llvmMsg) = self.module.verify() {
+            self.reportIce(format!("generated module is not valid: {}", llvmMsg.to_string().trim()));
+            return Err(format!("{} error(s) found while generating code", self.reports.errors.len()));
+        }
+
         //Returns the completed module back to main
         return Ok(&self.module);
     }
 
-    //The function that handles statements, it returns a bool (will actually only return true as false values will panic())
-    fn compileStmt(&mut self, stmt: Stmt, builder: &Builder<'ctx>, localTable: &mut HashMap<String, PointerValue<'ctx>>, function: FunctionValue) -> bool{
+    //The function that handles statements
+    //Returns an error message if code could not be generated for the statement, the caller reports it as an internal compiler error
+    fn compileStmt(&mut self, stmt: Stmt, builder: &Builder<'ctx>, localTable: &mut HashMap<String, PointerValue<'ctx>>, function: FunctionValue<'ctx>) -> Result<(), String>{
+        //Errors found in this statement are reported on its line
+        let stmtLine = stmt.lineNum();
+        if stmtLine != 0 {
+            self.curLine = stmtLine;
+        }
+
         //A match case to handle every type of Stmt
         match stmt.clone(){
             //For local variable declarations
             Stmt::VarDecl(varName, varType, lineNum) => {
                 //A match case to handle each variable type being defined
-                match varType{
+                match varType.clone(){
+                    VarType::Void => {
+                        return Err(format!("Variable {} cannot be declared void", varName.clone()));
+                    }
                     //For bool variables
                     VarType::Bool => {
                         let localType = self.context.bool_type();
@@ -296,8 +1079,7 @@ impl<'ctx> Compiler<'ctx> {
                                 localPtr = ptr.clone();
                             }
                             Err(err) => {
-                                println!("Error allocating local bool variable {}", localName.clone());
-                                panic!();
+                                return Err(format!("Error allocating local bool variable {}", localName.clone()));
                             }
                         }
                         //Initializes the variable
@@ -307,7 +1089,7 @@ impl<'ctx> Compiler<'ctx> {
                         //Inserts the variable into the local symbol table
                         localTable.insert(varName.clone(), localPtr);
                         
-                        return true;
+                        return Ok(());
                     }
                     //floats
                     VarType::Float => {
@@ -322,8 +1104,7 @@ impl<'ctx> Compiler<'ctx> {
                                 localPtr = ptr.clone();
                             }
                             Err(err) => {
-                                println!("Error allocating local float variable {}", localName.clone());
-                                panic!();
+                                return Err(format!("Error allocating local float variable {}", localName.clone()));
                             }
                         }
 
@@ -332,7 +1113,7 @@ impl<'ctx> Compiler<'ctx> {
 
                         localTable.insert(varName.clone(), localPtr);
                         
-                        return true;
+                        return Ok(());
                     }
                     //Ints
                     VarType::Int => {
@@ -350,8 +1131,7 @@ impl<'ctx> Compiler<'ctx> {
                                 localPtr = ptr.clone();
                             }
                             Err(err) => {
-                                println!("Error allocating local int variable {}: {}", localName.clone(), err);
-                                panic!();
+                                return Err(format!("Error allocating local int variable {}: {}", localName.clone(), err));
                             }
                         }
 
@@ -360,17 +1140,11 @@ impl<'ctx> Compiler<'ctx> {
 
                         localTable.insert(varName.clone(), localPtr);
                         
-                        return true;
+                        return Ok(());
                     }
-                    //Strings, needs work
+                    //Strings, stored as a pointer to the text
                     VarType::Str => {
-                        let maxStringLen = 64 as u32 + 1;
-                        let i8Type = self.context.i8_type();
-                        let arrayType = i8Type.array_type(maxStringLen);
-                        // let stringVal: Vec<IntValue> = 
-
-                        
-                        let localVarCheck = builder.build_alloca(arrayType.clone(), &varName.clone());
+                        let localVarCheck = builder.build_alloca(self.stringType(), &varName.clone());
 
                         let localPtr: PointerValue;
                         match localVarCheck{
@@ -378,34 +1152,22 @@ impl<'ctx> Compiler<'ctx> {
                                 localPtr = ptr.clone();
                             }
                             Err(err) => {
-                                println!("Error allocating local str variable {}", varName.clone());
-                                panic!();
+                                return Err(format!("Error allocating local str variable {}", varName.clone()));
                             }
                         }
 
-                        let string = "EMPTY";
-                        let stringBytes = string.as_bytes();
-                        let arrayVal = self.context.const_string(stringBytes, false).clone();
-            
-            
-                        // Wrap the array constant in a BasicValueEnum
-                        let initVal = BasicValueEnum::ArrayValue(arrayVal);
+                        //Starts out as the empty string
+                        let initVal = self.constString("");
                         let _ = builder.build_store(localPtr, initVal);
 
                         localTable.insert(varName.clone(), localPtr);
                         
-                        return true;
+                        return Ok(());
                     }
-                    //int arrays, needs work
-                    VarType::IntArray(size) => {
-                        let arrSize = size as u32;
-                        let i32Type = self.context.i32_type();
-                        let arrayType = i32Type.array_type(arrSize);
-                        let globName = varName.clone();
-
-
-                        //Adds to the local variables
-                        let localVarCheck = builder.build_alloca(arrayType.clone(), &varName.clone());
+                    //Arrays, allocated whole with their declared size
+                    VarType::Array(elemType, size) => {
+                        let arrayType = self.llvmType(&varType)?;
+                        let localVarCheck = builder.build_alloca(arrayType, &varName.clone());
 
                         let localPtr: PointerValue;
                         match localVarCheck{
@@ -413,21 +1175,36 @@ impl<'ctx> Compiler<'ctx> {
                                 localPtr = ptr.clone();
                             }
                             Err(err) => {
-                                println!("Error allocating local str variable {}", varName.clone());
-                                panic!();
+                                return Err(format!("Error allocating local array variable {}", varName.clone()));
                             }
                         }
-                        localTable.insert(varName.clone(), localPtr);
+
+                        let initVal = self.initialValue(&varType)?;
+                        let _ = builder.build_store(localPtr, initVal);
+
+                        localTable.insert(varName.clone(), localPtr);
                         
-                        return true;
+                        return Ok(());
                         
                     }
                 }
                 
             }
+            //A call on its own, whatever it returns is thrown away
+            Stmt::Call(procName, params, lineNum) => {
+                self.compileCall(&procName, &params, builder, localTable)?;
+                return Ok(());
+            }
+            //Parameters are set up when their procedure is declared
+            Stmt::ParamDecl(varName, varType, paramMode, lineNum) => {
+                return Err(format!("Parameter {} declared outside of a procedure declaration", varName.clone()));
+            }
             //Global variable declarations
             Stmt::GlobVarDecl(varName, varType, lineNum) => {
-                match varType{
+                match varType.clone(){
+                    VarType::Void => {
+                        return Err(format!("Variable {} cannot be declared void", varName.clone()));
+                    }
                     //Bools
                     VarType::Bool => {
                         //Creates the variable
@@ -441,7 +1218,7 @@ impl<'ctx> Compiler<'ctx> {
                         //Adds it to the global symbol table
                         self.globalTable.insert(varName.clone(), globPtr);
                         
-                        return true;
+                        return Ok(());
                     }
                     //Floats
                     VarType::Float => {
@@ -453,7 +1230,7 @@ impl<'ctx> Compiler<'ctx> {
                         let globPtr = globVar.as_pointer_value();
                         self.globalTable.insert(varName.clone(), globPtr);
                         
-                        return true;
+                        return Ok(());
                     }
                     //ints
                     VarType::Int => {
@@ -466,52 +1243,31 @@ impl<'ctx> Compiler<'ctx> {
                         let globPtr = globVar.as_pointer_value();
                         self.globalTable.insert(varName.clone(), globPtr);
                         
-                        return true;
+                        return Ok(());
                     }
-                    //Strings (needs work)
+                    //Strings, a global pointer to the text
                     VarType::Str => {
-
-                        // Define the array type [65 x i8]
-                        let max_string_len = 65;
-                        let i8_type = self.context.i8_type();
-                        let array_type = i8_type.array_type(max_string_len);
-
-                        // Create a string that fits exactly 65 characters with padding and null terminator
-                        let string_value = "A".repeat(64) + "\0";
-
-                        // Convert the string into a byte array
-                        let string_bytes = string_value.into_bytes();
-                        
-                        // // Create the constant array with the string bytes
-                        // let const_array = array_type.const_array(
-                        //     &string_bytes.iter().map(|&byte| i8_type.const_int(byte as u64, false).into()).collect::<Vec<BasicValueEnum>>()
-                        // );
-
-                        // Declare the global variable and initialize it
-                        let glob_name = varName.clone();
-                        let glob_var = self.module.add_global(array_type, Some(AddressSpace::default()), &glob_name);
-                        let globPtr = glob_var.as_pointer_value();
-                        // let test = unsafe { ArrayValue::new(string_value) };
-                        let test = array_type.const_zero();
-                        self.globalTable.insert(glob_name.clone(), globPtr);
-                        glob_var.set_initializer(&test);
+                        let globName = varName.clone();
+                        let globVar = self.module.add_global(self.stringType(), Some(AddressSpace::default()), &globName);
+                        //Starts out as the empty string
+                        globVar.set_initializer(&self.constString(""));
+                        let globPtr = globVar.as_pointer_value();
+                        self.globalTable.insert(globName.clone(), globPtr);
                         
-                        return true;
+                        return Ok(());
                     }
-                    //Integer arrays (needs work)
-                    VarType::IntArray(size) => {
-                        let arrSize = size as u32;
-                        let i32Type = self.context.i32_type();
-                        let arrayType = i32Type.array_type(arrSize);
+                    //Arrays
+                    VarType::Array(elemType, size) => {
+                        let arrayType = self.llvmType(&varType)?;
                         let globName = varName.clone();
 
-
                         //Adds to the global variables
-                        let globVar = self.module.add_global(arrayType.clone(), None, &globName);
+                        let globVar = self.module.add_global(arrayType, None, &globName);
+                        globVar.set_initializer(&self.initialValue(&varType)?);
                         let globPtr = globVar.as_pointer_value();
                         self.globalTable.insert(varName.clone(), globPtr);
                         
-                        return true;
+                        return Ok(());
                         
                     }
                 }
@@ -547,7 +1303,7 @@ impl<'ctx> Compiler<'ctx> {
                                 }
                                 //If its not found at at all, panics
                                 None => {
-                                    panic!("variable {} not found", targName.clone());
+                                    return Err(format!("variable {} not found", targName.clone()));
                                 }
                             }
                         }
@@ -556,192 +1312,29 @@ impl<'ctx> Compiler<'ctx> {
                 
                 //If the variable we are assigning is a value in an array
                 else if let Expr::ArrayRef(ref targName, indexExpr) = variable{
-                    // varName = targName.clone();
-                    let arrSize = 64 as u32;
-                    let i32Type = self.context.i32_type().clone();
-                    let arrayType = i32Type.array_type(arrSize).clone();
-                    
-                    //Gets the value of the index expression
-                    let indexExprCheck = self.compileExpr(&*&indexExpr.clone(), builder, localTable);
-                    let mut indexVal: BasicValueEnum;
-                    match indexExprCheck{
-                        Ok(val) => {
-                            indexVal = val.clone();
-                        }
-                        Err(err) => {
-                            println!("{}", err.clone());
-                            panic!();
-                        }
-                    }
-
-                    //Gets the loccation of the array
-                    let mut arrayPtr: PointerValue;
-                    let checkLocVar = localTable.get(&targName.clone());
-                    match checkLocVar{
-                        Some(ptr) => {
-                            // println!("Assigninig local variable {} at location {}", targName.clone(), ptr.clone());
-                            arrayPtr = ptr.clone();
-                        }
-                        None => {
-                            let checkGlobVar = self.globalTable.get(&targName.clone());
-                            match checkGlobVar{
-                                Some(ptr) => {
-                                    // println!("Assigninig global array variable {} at location {}", targName.clone(), ptr.clone());
-                                    arrayPtr = ptr.clone();
-                                }
-                                None => {
-                                    panic!("variable {} not found", targName.clone());
-                                }
-                            }
-                        }
-                    }
-
-                    //Evaluates the index expression into a value
-                    let mut indexInt: IntValue;
-                    match indexVal{
-                        BasicValueEnum::IntValue(val) => {
-                            indexInt = val.clone();
-                        }
-                        BasicValueEnum::FloatValue(val) => {
-                            let intType = self.context.i32_type().clone();
-                            let intValue = builder.build_float_to_signed_int(val.clone(), intType, "float_to_int");
-                            match intValue{
-                                Ok(iVal) => {
-                                    indexInt = iVal.clone();
-                                }
-                                Err(err) => {
-                                    panic!("Error converting float to int");
-                                }
-                            }
-
-                        }
-                        _ => {
-                            panic!("Can only index by integer");
-                        }
-                    }
-                
-                    // Get the pointer to the desired index
-                    let intType = self.context.i32_type().clone();
-                    let zero = intType.const_int(0, false);
-                    let indexList = [zero, indexInt];
-                    let checkIndexPtr = unsafe { builder.build_gep(arrayPtr, &indexList, "arrayIndexLoad") };
-                    match checkIndexPtr{
-                        Ok(ptr) => {
-                            // println!("GOT ARRAY INDEX PTR");
-                            variablePtr = ptr.clone();
-                        }
-                        Err(err) => {
-                            panic!("Error getting array index ptr");
-                        }
-                    }
+                    variablePtr = self.arrayElementPtr(targName, &indexExpr, builder, localTable)?;
                 }
                 
                 //Fails if not a variable
                 else {
-                    panic!("Cannot assing to a non variable");
+                    return Err(format!("Cannot assing to a non variable"));
                 }
 
-                //If the new value we are storing is an array reference, does the same as above but pulls the value instead of the ptr
-                if let Expr::ArrayRef(ref targName, indexExpr) = newValue.clone() {
-                    let arrSize = 64 as u32;
-                    let i32Type = self.context.i32_type().clone();
-                    let arrayType = i32Type.array_type(arrSize).clone();
-                    
-                    //Gets the value of the index expression
-                    let indexExprCheck = self.compileExpr(&*&indexExpr.clone(), builder, localTable);
-                    let mut indexVal: BasicValueEnum;
-                    match indexExprCheck{
-                        Ok(val) => {
-                            indexVal = val.clone();
-                        }
-                        Err(err) => {
-                            // println!("{}", err.clone());
-                            panic!("Could error with index {}:", err.clone());
-                        }
-                    }
-
-                    //Gets the pointer to the array
-                    let mut arrayPtr: PointerValue;
-                    let checkLocVar = localTable.get(&targName.clone());
-                    match checkLocVar{
-                        Some(ptr) => {
-                            arrayPtr = ptr.clone();
-                        }
-                        None => {
-                            let checkGlobVar = self.globalTable.get(&targName.clone());
-                            match checkGlobVar{
-                                Some(ptr) => {
-                                    arrayPtr = ptr.clone();
-                                }
-                                None => {
-                                    panic!("variable {} not found", targName.clone());
-                                }
-                            }
-                        }
-                    }
-
-                    //Gets the value stored at that index
-                    let mut indexInt: IntValue;
-                    match indexVal{
-                        BasicValueEnum::IntValue(val) => {
-                            indexInt = val.clone();
-                        }
-                        BasicValueEnum::FloatValue(val) => {
-                            let intType = self.context.i32_type().clone();
-                            let intValue = builder.build_float_to_signed_int(val.clone(), intType, "float_to_int");
-                            match intValue{
-                                Ok(iVal) => {
-                                    indexInt = iVal.clone();
-                                }
-                                Err(err) => {
-                                    panic!("Error converting float to int");
-                                }
-                            }
-
-                        }
-                        _ => {
-                            panic!("Can only index by integer");
-                        }
-                    }
-                
-                    // Get the pointer to the desired index
-                    let variablePtr: PointerValue;
-                    let checkIndexPtr = unsafe { builder.build_in_bounds_gep(arrayPtr, &[indexInt], "test") };
-                    match checkIndexPtr{
-                        Ok(ptr) => {
-                            variablePtr = ptr.clone();
-                        }
-                        Err(err) => {
-                            panic!("Error getting array index ptr");
-                        }
+                //Evaluates the expression we are setting as the value
+                let checkNewValue = self.compileExpr(&newValue.clone(), builder, localTable);
+                match checkNewValue.clone(){
+                    Ok(value) => {
+                        
+                        newEnumValue = value.clone();
                     }
-
-                    //Gets the value at that pointer
-                    let retValCheck = builder.build_load(variablePtr, "arrayIndexReference");
-                    match retValCheck{
-                        Ok(val) => {
-                            // println!("ARRAY INDEX VALUE GOT {}", val.clone());
-                            newEnumValue = val.clone();
-                        }
-                        Err(msg) => {
-                            panic!("Error getting array index value");
-                        }
+                    Err(msg) => {
+                        return Err(format!("{}", msg));
                     }
-                
                 }
-                
-                //Evaluates the expression we are setting as the value
-                else {
-                    let checkNewValue = self.compileExpr(&newValue.clone(), builder, localTable);
-                    match checkNewValue.clone(){
-                        Ok(value) => {
-                            
-                            newEnumValue = value.clone();
-                        }
-                        Err(msg) => {
-                            panic!("{}", msg.clone());
-                        }
-                    }
+
+                //Converts the value to the type of the variable, the checker lets an integer be assigned to a float and so on
+                if let Ok(targType) = BasicTypeEnum::try_from(variablePtr.get_type().get_element_type()) {
+                    newEnumValue = self.convertValue(builder, newEnumValue, targType)?;
                 }
 
                 
@@ -751,40 +1344,44 @@ impl<'ctx> Compiler<'ctx> {
                     BasicValueEnum::IntValue(intVal) => {
                         // println!("Stored int value {} in variable {}",intVal.clone(), varName.clone());
                         let _ = builder.build_store(variablePtr, intVal.clone());
-                        return true;
+                        return Ok(());
                     }
                     BasicValueEnum::FloatValue(intVal) => {
                         // println!("Stored int value {} in variable {}",intVal.clone(), varName.clone());
                         let _ = builder.build_store(variablePtr, intVal.clone());
-                        return true;
+                        return Ok(());
                     }
                     BasicValueEnum::ArrayValue(val) => {
                         // println!("ARRAY {}", val.clone());
                         let _ = builder.build_store(variablePtr, val.clone());
-                        return true;
+                        return Ok(());
+                    }
+                    //Strings, the variable now points at the new text
+                    BasicValueEnum::PointerValue(ptrVal) => {
+                        let _ = builder.build_store(variablePtr, ptrVal.clone());
+                        return Ok(());
                     }
                     _ => {
-                        println!("Not implemented for that type yet");
-                        return true;
+                        self.reportError(format!("Assigning this type of value is not implemented yet"));
+                        return Ok(());
                     }
                 }
             }
             //These are for blocks, aka vectors of expressions, just iterates through the vector and evaluates each one
+            //Stops once the block is closed, anything after a return can never run
             Stmt::Block(blockStmt, lineNum) => {
                 for instr in blockStmt.clone() {
-                    let good = self.compileStmt(instr.clone(), builder, localTable, function);
-                    if (!good){
-                        panic!("Error in block on line {}:", lineNum.clone());
-                    } else {
-                        //continue
+                    if self.blockClosed(builder) {
+                        break;
                     }
+                    self.compileStmt(instr.clone(), builder, localTable, function)?;
                 }
-                return true;
+                return Ok(());
             }
             //For error statements, these never make it here as they are caught by the error
             //checker but its defined here for completion
             Stmt::Error(err, lineNum) => {
-                panic!("Somehow an error statemet made it to the compiler. Error from line {}", lineNum.clone());
+                return Err(format!("Somehow an error statemet made it to the compiler. Error from line {}", lineNum.clone()));
             }
             //If the statement is a simple expression
             //This is something like a procedure call without assignment
@@ -795,10 +1392,10 @@ impl<'ctx> Compiler<'ctx> {
                         let checked = self.compileExpr(&exprStmt.clone(), builder, localTable);
                         match checked {
                             Ok(val) => {
-                                return true;
+                                return Ok(());
                             }
                             Err(err) => {
-                                panic!("Error: {}", err.clone());
+                                return Err(format!("Error: {}", err.clone()));
                             }
                         }
                     }
@@ -807,29 +1404,13 @@ impl<'ctx> Compiler<'ctx> {
             //Creates a for loop
             Stmt::For(assignment, condExpr, body, lineNum) => {
                 
-                //Parses the assignment first
-                let mut iInitVal: BasicValueEnum;
-                let mut iName: String;
-                let assignStmt = Rc::clone(&assignment);
-                if let Stmt::Assign(varRef, val, lineNum) = &*assignStmt.clone() {
-                    if let Expr::VarRef(varName) = varRef.clone(){
-                        //Checks the value of the iterator assignment
-                        let iteratorValCheck = self.compileExpr(&val.clone(), &builder, localTable);
-                        match iteratorValCheck{
-                            Ok(val) => {
-                                // println!("Iterator value: {}", val.clone());
-                            }
-                            Err(err) => {
-                                panic!("Error parsing for loop iterator assignment: {}", err.clone());
-                            }
-                        }
-                    }
-                    else {
-                        panic!("Error: For loop iterator must be a variable");
-                    }
+                //Assigns the starting value of the loop variable first
+                let assignStmt = (*assignment).clone();
+                if let Stmt::Assign(Expr::VarRef(_), _, _) = assignStmt {
+                    self.compileStmt(assignStmt, builder, localTable, function)?;
                 }
                 else {
-                    panic!("Error: For loop assignment must be a variable assignment");
+                    return Err(format!("Error: For loop assignment must be a variable assignment"));
                 }
                 
                 
@@ -849,119 +1430,8 @@ impl<'ctx> Compiler<'ctx> {
                 //Loop condition block so we can add instructions here
                 let _ = builder.position_at_end(loopCond); 
 
-                //Parse the condition into llvm values including loading values if they are variables
-                let mut condOp1Val: BasicValueEnum;
-                let mut condOp2Val: BasicValueEnum;
-                let mut condOp: IntPredicate; 
-                if let Expr::RelOp(op1Box, op, op2Box) = condExpr{
-                   let op1 = *op1Box.clone();
-                   let op2 = *op2Box.clone();
-                    //Determins which operator we will use
-                    match op{
-                        Operator::Greater => {
-                            condOp = IntPredicate::SGT;
-                        }
-                        Operator::Greater_Equal => {
-                            condOp = IntPredicate::SGE;
-                        }
-                        Operator::Less => {
-                            condOp = IntPredicate::SLT;
-                        }
-                        Operator::Less_Equal => {
-                            condOp = IntPredicate::SLE;
-                        }
-                        Operator::Check_Equal => {
-                            condOp = IntPredicate::EQ;
-                        }
-                        Operator::Not_Equals => {
-                            condOp = IntPredicate::NE;
-                        }
-                        _ => {
-                            panic!("For condition operator must be logical operator");
-                        }
-                    }
-                    //First gets the values of both operands
-                    let op1Res = self.compileExpr(&op1.clone(), &builder, localTable);
-                    //Makes sure both results of checked operands are good
-                    match op1Res{
-                        Ok(res) => {
-                            condOp1Val = res;
-                        }
-                        Err(msg) => {
-                            panic!("Error in for loop condition");
-                        }
-                    }
-                    let op2Res = self.compileExpr(&op2.clone(), &builder, localTable);
-                    //Makes sure both results of checked operands are good
-                    match op2Res{
-                        Ok(res) => {
-                            condOp2Val = res;
-                        }
-                        Err(msg) => {
-                            panic!("Error in for loop condition");
-                        }
-                    }
-                   
-                } else {
-                
-                    panic!("For loop condition must be a logical operation");
-                }
-
-                //Checks/converts the values of the 2 operands, this is for compatability between int and float
-                let mut op1Int: IntValue;
-                let mut op2Int: IntValue;
-                match condOp1Val{
-                    BasicValueEnum::IntValue(val) => {
-                        op1Int = val.clone();
-                    }
-                    BasicValueEnum::FloatValue(val) => {
-                        let intType = self.context.i32_type().clone();
-                        let intVal = builder.build_float_to_signed_int(val.clone(), intType.clone(), "floatToInt");
-                        match intVal{
-                            Ok(val) => {
-                                op1Int = val.clone()
-                            }
-                            Err(msg) => {
-                                panic!("Error converting float to int");
-                            }
-                        }
-                    }
-                    _ => {
-                        panic!("For loop condition values must be numbers");
-                    }
-                }
-                match condOp2Val{
-                    BasicValueEnum::IntValue(val) => {
-                        op2Int = val.clone();
-                    }
-                    BasicValueEnum::FloatValue(val) => {
-                        let intType = self.context.i32_type().clone();
-                        let intVal = builder.build_float_to_signed_int(val.clone(), intType.clone(), "floatToInt");
-                        match intVal{
-                            Ok(val) => {
-                                op2Int = val.clone()
-                            }
-                            Err(msg) => {
-                                panic!("Error converting float to int");
-                            }
-                        }
-                    }
-                    _ => {
-                        panic!("For loop condition values must be numbers");
-                    }
-                }
-
-                //Creates the condition statement
-                let conditionCheck = builder.build_int_compare(condOp.clone(), op1Int.clone(), op2Int.clone(), "forLoopCondition");
-                let condition: IntValue;
-                match conditionCheck{
-                    Ok(val) => {
-                        condition = val.clone();
-                    }
-                    Err(msg) => {
-                        panic!("Error creating condition");
-                    }
-                }
+                //Evaluates the condition each time around the loop
+                let condition = self.compileCondition(&condExpr, builder, localTable)?;
 
                 //Adds a conditional branch that checks if the condition is met or if the loop should be taken
                 let _ = builder.build_conditional_branch(condition, loopBody, mergeFor);
@@ -969,289 +1439,164 @@ impl<'ctx> Compiler<'ctx> {
                 //Move builder to the loop body to populate it
                 builder.position_at_end(loopBody);
 
-                //Populates the body with statements
+                //Populates the body with statements, continue goes back to the condition and break leaves the loop
                 let bodyStmt = *body.clone();
-                self.compileStmt(bodyStmt.clone(), &builder, localTable, function);
+                self.loopTargets.push((loopCond, mergeFor));
+                let bodyResult = self.compileStmt(bodyStmt.clone(), &builder, localTable, function);
+                self.loopTargets.pop();
+                bodyResult?;
 
                 //Branch the end of the loop to the condition box to reevaluate the condition
-                let _ = builder.build_unconditional_branch(loopCond);
+                self.branchIfOpen(builder, loopCond);
 
                 //Moves builder to the end of the block
                 builder.position_at_end(mergeFor);
 
                 // println!("CREATED FOR LOOP ");
-                return true;
-            }
-            //Creates and if/else check
-            Stmt::If(condExpr, body, elseStmt, lineNum) => {
-                //Parts of the if statement
-                //The body of the if
-                let ifBody = self.context.append_basic_block(function, "ifBody");
-                //The body of the else
-                let elseBody = self.context.append_basic_block(function, "elseBody");
-                //The section that comes after the if statement where it will continue normal code execution
-                let mergeBack = self.context.append_basic_block(function, "ifMerge");
-                
-                //Parse the condition
-                let mut condOp1Val: BasicValueEnum;
-                let mut condOp2Val: BasicValueEnum;
-                let mut condOp: IntPredicate; 
-                //If the condition is an relational operation
-                if let Expr::RelOp(op1Box, op, op2Box) = condExpr.clone(){
-                   let op1 = *op1Box.clone();
-                   let op2 = *op2Box.clone();
-                    match op{
-                        Operator::Greater => {
-                            condOp = IntPredicate::SGT;
-                        }
-                        Operator::Greater_Equal => {
-                            condOp = IntPredicate::SGE;
-                        }
-                        Operator::Less => {
-                            condOp = IntPredicate::SLT;
-                        }
-                        Operator::Less_Equal => {
-                            condOp = IntPredicate::SLE;
-                        }
-                        Operator::Check_Equal => {
-                            condOp = IntPredicate::EQ;
-                        }
-                        Operator::Not_Equals => {
-                            condOp = IntPredicate::NE;
-                        }
-                        _ => {
-                            panic!("For condition operator must be logical operator");
-                        }
-                    }
-                    
-                    //Evaluates the operands and gets their values
-                    let op1Check = self.compileExpr(&op1.clone(), builder, localTable);
-                    match op1Check{
-                        Ok(val) => {
-                            condOp1Val = val.clone();
-                        }
-                        Err(err) => {
-                            panic!("Error getting if condition op 1: {}", err.clone());
-                        }
+                return Ok(());
+            }
+            Stmt::While(condExpr, body, lineNum) => {
+                //The blocks of the while loop, laid out the same as a for loop
+                let whileCond = self.context.append_basic_block(function, "whileCond");
+                let whileBody = self.context.append_basic_block(function, "whileBody");
+                let mergeWhile = self.context.append_basic_block(function, "mergeWhile");
+
+                //Jumps to the condition check
+                let _ = builder.build_unconditional_branch(whileCond);
+                builder.position_at_end(whileCond);
+
+                //Evaluates the condition each time around the loop and either runs the body or leaves
+                let condition = self.compileCondition(&condExpr, builder, localTable)?;
+                let _ = builder.build_conditional_branch(condition, whileBody, mergeWhile);
+
+                //Populates the body, continue goes back to the condition and break leaves the loop
+                builder.position_at_end(whileBody);
+                self.loopTargets.push((whileCond, mergeWhile));
+                let bodyResult = self.compileStmt(*body.clone(), &builder, localTable, function);
+                self.loopTargets.pop();
+                bodyResult?;
+
+                //Goes back around to the condition
+                self.branchIfOpen(builder, whileCond);
+
+                builder.position_at_end(mergeWhile);
+                return Ok(());
+            }
+            //Break and continue jump out of the current block to the innermost loop's targets
+            Stmt::Break(lineNum) | Stmt::Continue(lineNum) => {
+                let (continueTarget, breakTarget) = match self.loopTargets.last() {
+                    Some(targets) => *targets,
+                    None => {
+                        return Err(format!("break or continue outside of a loop on line {}", lineNum));
                     }
-                    let op2Check = self.compileExpr(&op2.clone(), builder, localTable);
-                    match op2Check{
-                        Ok(val) => {
-                            condOp2Val = val.clone();
-                        }
-                        Err(err) => {
-                            panic!("Error getting if condition op 2");
-                        }
-                    } 
-
-                } 
-                //If the condition is just a bool
-                else if let Expr::BoolLiteral(boolVal) = condExpr.clone() {
-                    let intBool = boolVal.clone() as u64;
-                    let intVal = self.context.bool_type();
-                    let boolConst = intVal.const_int(intBool.clone(), false);
-                    let boolVal = BasicValueEnum::IntValue(boolConst.clone());
-
-                    condOp1Val = boolVal.clone();
-                    condOp2Val = boolVal.clone();
-                    condOp = IntPredicate::EQ;
+                };
+                let target = if let Stmt::Break(_) = stmt { breakTarget } else { continueTarget };
+                let _ = builder.build_unconditional_branch(target);
 
-                } else {
-                    panic!("If loop condition must evaluate to a bool");
-                }
-                
-                //Parses operand returns
-                let mut op1Int: IntValue;
-                let mut op2Int: IntValue;
-                match condOp1Val{
-                    BasicValueEnum::IntValue(val) => {
-                        op1Int = val.clone();
-                    }
-                    BasicValueEnum::FloatValue(val) => {
-                        let intType = self.context.i32_type().clone();
-                        let intVal = builder.build_float_to_signed_int(val.clone(), intType.clone(), "floatToInt");
-                        match intVal{
-                            Ok(val) => {
-                                op1Int = val.clone()
-                            }
-                            Err(msg) => {
-                                println!("Error converting float to int");
-                                panic!();
-                            }
-                        }
-                    }
-                    _ => {
-                        panic!("For loop condition values must be numbers");
-                    }
-                }
-                match condOp2Val{
-                    BasicValueEnum::IntValue(val) => {
-                        op2Int = val.clone();
-                    }
-                    BasicValueEnum::FloatValue(val) => {
-                        let intType = self.context.i32_type().clone();
-                        let intVal = builder.build_float_to_signed_int(val.clone(), intType.clone(), "floatToInt");
-                        match intVal{
-                            Ok(val) => {
-                                op2Int = val.clone()
-                            }
-                            Err(msg) => {
-                                println!("Error converting float to int");
-                                panic!();
-                            }
-                        }
-                    }
-                    _ => {
-                        println!("For loop condition values must be numbers");
-                        panic!();
-                    }
-                }
+                //Anything after this in the block can never run, but it still needs a block to go into
+                let afterJump = self.context.append_basic_block(function, "afterJump");
+                builder.position_at_end(afterJump);
+                return Ok(());
+            }
+            //Creates and if/elsif/else check
+            //Each condition is checked in turn, falling through to the next one, so the arms form a flat chain
+            Stmt::If(condExpr, body, elsifArms, elseStmt, lineNum) => {
+                //The section that comes after the if statement where it will continue normal code execution
+                let mergeBack = self.context.append_basic_block(function, "ifMerge");
+                //The body of the else, this is where the chain ends up if no condition was true
+                let elseBody = self.context.append_basic_block(function, "elseBody");
 
-                //Creates the condition expression
-                let conditionCheck = builder.build_int_compare(condOp.clone(), op1Int.clone(), op2Int.clone(), "ifCondition");
-                let condition: IntValue;
-                match conditionCheck{
-                    Ok(val) => {
-                        condition = val.clone();
-                    }
-                    Err(msg) => {
-                        println!("Error creating condition");
-                        panic!("Invalid condition");
-                        
-                    }
-                }
+                //The if arm followed by the elsif arms, in the order they are checked
+                let mut condArms: Vec<(Expr, Stmt)> = vec![(condExpr, *body)];
+                condArms.extend(elsifArms);
+                let armCount = condArms.len();
+                for (armInd, (armCond, armBody)) in condArms.into_iter().enumerate() {
+                    //The body of this arm, and where to go if its condition is false
+                    let ifBody = self.context.append_basic_block(function, "ifBody");
+                    let nextCheck = if armInd + 1 == armCount {
+                        elseBody
+                    } else {
+                        self.context.append_basic_block(function, "elsifCond")
+                    };
 
-                //Creates the conditional check
-                let _ = builder.build_conditional_branch(condition, ifBody, elseBody);
+                    //Evaluates the condition and creates the conditional check
+                    let condition = self.compileCondition(&armCond, builder, localTable)?;
+                    let _ = builder.build_conditional_branch(condition, ifBody, nextCheck);
 
-                //Position at the end of the ifBody
-                builder.position_at_end(ifBody);
-                
-                //Add to the if body
-                let mut ifRet: bool = false;
-                let bodyStmt = *body.clone();
-                match bodyStmt.clone(){
-                    Stmt::Block(stmtVec, lineNum) => {
-                        for stmt in stmtVec.clone(){
-                            match stmt.clone(){
-                                Stmt::Return(val, lineNum) => {
-                                    let checkedIfBody = self.compileStmt(stmt.clone(), builder, localTable, function);
-                                    if checkedIfBody{
-                                        //continue
-                                    } else {
-                                        panic!();
-                                    }
-                                    ifRet = true;
-                                    break;
-                                }
-                                _ => {
-                                    let checkedIfBody = self.compileStmt(stmt.clone(), builder, localTable, function);
-                                    if checkedIfBody{
-                                        //continue
-                                    } else {
-                                        panic!();
-                                    }
-                                    ifRet = false;
-                                }
-                            }
-                        }
-                    }
-                    _ => {
-                        panic!("If body must be a block");
-                    }
-                }
+                    //Adds to the arm body, merging back at the end unless it returned
+                    builder.position_at_end(ifBody);
+                    self.compileStmt(armBody, builder, localTable, function)?;
+                    self.branchIfOpen(builder, mergeBack);
 
-                //Checks if the body body contained a return, if it didnt, tells it to merge back at the end of the condition 
-                if !ifRet{
-                    let _ = builder.build_unconditional_branch(mergeBack);
+                    //The next condition is checked from here
+                    builder.position_at_end(nextCheck);
                 }
 
-                //Move to the end of the else body
-                builder.position_at_end(elseBody);
-
-                let mut elseRet: bool = false;
-                //Checks if there is an else statement
-                match elseStmt.clone(){
-                    //If there is an else, evalueates the stmt and adds to that section
-                    Some(elseVal) => {
-                        let elseStmt = *elseVal.clone();
-                        match elseStmt{
-                            Stmt::Return(val, lineNum) => {
-                                let checkedIfBody = self.compileStmt(bodyStmt.clone(), &builder, localTable, function);
-                                if checkedIfBody{
-                                    //continue
-                                } else {
-                                    panic!("Error building if body");
-                                }
-                                elseRet = true;
-                            }
-                            Stmt::Block(stmtVec, lineNum) => {
-                                for stmt in stmtVec.clone(){
-                                    match stmt.clone(){
-                                        Stmt::Return(val, lineNum) => {
-                                            let checkedIfBody = self.compileStmt(bodyStmt.clone(), &builder, localTable, function);
-                                            if checkedIfBody{
-                                                //continue
-                                            } else {
-                                                panic!("Error building if body");
-                                            }
-                                            elseRet = true;
-                                        }
-                                        _ => {
-                                            let checkedIfBody = self.compileStmt(bodyStmt.clone(), &builder, localTable, function);
-                                            if checkedIfBody{
-                                                //continue
-                                            } else {
-                                                println!("Error building if body");
-                                                panic!();
-                                            }
-                                            elseRet = false;
-                                        }
-                                    }
-                                }
-                            }
-                            _ => {
-                                let checkedIfBody = self.compileStmt(bodyStmt.clone(), &builder, localTable, function);
-                                if checkedIfBody{
-                                    //continue
-                                } else {
-                                    println!("Error building if body");
-                                    panic!();
-                                }
-                                elseRet = false;
-                            }
-                        }
-                    }
-                    None => {
-                        // println!("If statement no else");
-                        elseRet = false;
-                    }
+                //Checks if there is an else statement, and adds its statements to that section
+                if let Some(elseVal) = elseStmt.clone() {
+                    self.compileStmt(*elseVal, builder, localTable, function)?;
                 }
 
                 //If there was not a return in the else, it forces it to branch to the merge
                 //This ensures there is no dangling sections of code without terminators
-                if !elseRet {
-                    let _ = builder.build_unconditional_branch(mergeBack);
-                }
+                self.branchIfOpen(builder, mergeBack);
                     
                 //Moves builder to the end of the block
                 builder.position_at_end(mergeBack);
 
                 // println!("CREATED if LOOP ");
-                return true;
+                return Ok(());
                 
             }
-            //For procedure declarations in the header
-            Stmt::ProcDecl(procRetType, procName, params, headerBox, bodyBox, lineNum) => {
-                
-                
+            //Creates a case statement as a single switch on the value
+            Stmt::Case(value, arms, others, lineNum) => {
+                let intType = self.context.i32_type().as_basic_type_enum();
+                let caseVal = self.compileExpr(&value, builder, localTable)?;
+                let caseVal = self.convertValue(builder, caseVal, intType)?.into_int_value();
+
+                //The block after the case, and the others block that takes every value without an arm
+                let mergeCase = self.context.append_basic_block(function, "caseMerge");
+                let othersBlock = match others {
+                    Some(_) => self.context.append_basic_block(function, "caseOthers"),
+                    None => mergeCase,
+                };
 
-                let newProcName = format!("{}{}", self.scope.to_string(), procName.clone());
-                let procName = newProcName;
-                self.scope += 1; 
+                //Each arm value has to be a constant for the switch, the type checker makes sure of this
+                let mut switchArms: Vec<(IntValue<'ctx>, BasicBlock<'ctx>)> = vec![];
+                for (armValue, armBody) in arms.iter() {
+                    let armVal = self.compileExpr(armValue, builder, localTable)?;
+                    let armVal = self.convertValue(builder, armVal, intType)?.into_int_value();
+                    if !armVal.is_const() {
+                        return Err(format!("Case arm value {} on line {} is not a constant", armValue, armBody.lineNum()));
+                    }
+                    let armBlock = self.context.append_basic_block(function, "caseArm");
+                    switchArms.push((armVal, armBlock));
+                }
+                let _ = builder.build_switch(caseVal, othersBlock, &switchArms);
 
+                //Fills in each arm, every one merges back after the case unless it returned
+                for ((_, armBody), (_, armBlock)) in arms.into_iter().zip(switchArms.into_iter()) {
+                    builder.position_at_end(armBlock);
+                    self.compileStmt(armBody, builder, localTable, function)?;
+                    self.branchIfOpen(builder, mergeCase);
+                }
+                if let Some(othersBody) = others {
+                    builder.position_at_end(othersBlock);
+                    self.compileStmt(*othersBody, builder, localTable, function)?;
+                    self.branchIfOpen(builder, mergeCase);
+                }
 
-                println!("Creating proc {}", procName);
+                builder.position_at_end(mergeCase);
+                return Ok(());
+            }
+            //A forward declaration only declares the function, its body is added when the procedure is defined
+            Stmt::ProcForward(procRetType, procName, params, lineNum) => {
+                self.declareProc(&procRetType, &procName, &params)?;
+                return Ok(());
+            }
+            //For procedure declarations in the header
+            Stmt::ProcDecl(procRetType, procName, params, headerBox, bodyBox, lineNum) => {
+                
                 
 
                 //Creates the local variable hash table
@@ -1260,131 +1605,20 @@ impl<'ctx> Compiler<'ctx> {
                 //Creates the local builder
                 //We will be changing scopes so we make a new builder
                 let procBuilder = self.context.create_builder();
-                
-                //Creates a vec for the param types
-                let mut paramTypes: Vec<BasicTypeEnum> = Vec::new();
 
-                //Parses the params
+                //A procedure with a forward declaration already has its function, so the body is added to that one
                 let paramStmtBlock = *params.clone();
-                match paramStmtBlock.clone(){
-                    Stmt::Block(params, lineNum) => {
-                        for param in params{
-                            match param.clone(){
-                                Stmt::VarDecl(varName, varType, lineNum) => {
-                                    let mut paramType: BasicTypeEnum;
-                                    match varType{
-                                        VarType::Bool => {
-                                            paramType = self.context.bool_type().as_basic_type_enum().clone();
-                                        }
-                                        VarType::Float => {
-                                            paramType = self.context.f32_type().as_basic_type_enum().clone();
-                    
-                                        }
-                                        VarType::Int => {
-                                            paramType = self.context.i32_type().as_basic_type_enum().clone();
-                    
-                                        }
-                                        VarType::IntArray(size) => {
-                                            let arrSize = size as u32;
-                                            let i32Type = self.context.i32_type();
-                                            let arrayType = i32Type.array_type(arrSize);
-                                            
-                                            
-                                            paramType = self.context.i32_type().as_basic_type_enum().clone();
-                    
-                                        }
-                                        VarType::Str => {
-                                            paramType = self.context.i8_type().as_basic_type_enum().clone();
-                                            
-                                        }
-                                    }
-                                    paramTypes.push(paramType.clone());
-                                    
-                                }
-                                _ => {
-                                    panic!("Function delcaration parameters can only be local variable declarations");
-                                }
-                            }
-                        }
-                    }
-                    Stmt::VarDecl(varName, varType, lineNum) => {
-                        let mut paramType: BasicTypeEnum;
-                        match varType{
-                            VarType::Bool => {
-                                paramType = self.context.bool_type().as_basic_type_enum().clone();
-                            }
-                            VarType::Float => {
-                                paramType = self.context.f32_type().as_basic_type_enum().clone();
-        
-                            }
-                            VarType::Int => {
-                                paramType = self.context.i32_type().as_basic_type_enum().clone();
-        
-                            }
-                            VarType::IntArray(size) => {
-                                let arrSize = size as u32;
-                                let i32Type = self.context.i32_type();
-                                let arrayType = i32Type.array_type(arrSize);
-                                
-                                
-                                paramType = self.context.i32_type().as_basic_type_enum().clone();
-        
-                            }
-                            VarType::Str => {
-                                paramType = self.context.i8_type().as_basic_type_enum().clone();
-                                
-                            }
-                        }
-                        paramTypes.push(paramType.clone());
-                        
-                    }
-                    _ => {
-                        panic!("Function delcaration parameters can only be local variable declarations");
-                    }
-                }
-                    
-                // println!("Created param list");
-                
-                //Gets the procedure return type
-                let mut procTypeEnum: BasicTypeEnum;
-                match procRetType{
-                    VarType::Bool => {
-                        procTypeEnum = self.context.bool_type().as_basic_type_enum().clone();
-                    }
-                    VarType::Float => {
-                        procTypeEnum = self.context.f32_type().as_basic_type_enum().clone();
-
-                    }
-                    VarType::Int => {
-                        procTypeEnum = self.context.i32_type().as_basic_type_enum().clone();
-
-                    }
-                    VarType::IntArray(size) => {
-                        let arrSize = size as u32;
-                        let i32Type = self.context.i32_type();
-                        let arrayType = i32Type.array_type(arrSize);
-                        
-                        
-                        procTypeEnum = self.context.i32_type().as_basic_type_enum().clone();
-
-                    }
-                    VarType::Str => {
-                        procTypeEnum = self.context.i8_type().as_basic_type_enum().clone();
-                        
-                    }
-                }
-                
-                //Converts the parameters into a useable form for the inkwell function thing
-                let paramTypesSlice: Vec<BasicMetadataTypeEnum> = paramTypes.iter().map(|&ty| ty.into()).collect();
-                let paramTypesSlice = &paramTypesSlice[..];
-
-                //creates the function type structure
-                let funcType = procTypeEnum.fn_type(paramTypesSlice, false);
-
-                //Adds the function to the module and gets its functionvalue
-                let procFunVal = self.module.add_function(&procName.clone(), funcType, None);
+                let declared = self.procScopes.last().and_then(|procScope| procScope.get(&procName)).cloned();
+                let procFunVal = match declared {
+                    Some(fun) if (fun.count_basic_blocks() == 0) && self.paramModes.contains_key(&fun.get_name().to_string_lossy().to_string()) => fun,
+                    _ => self.declareProc(&procRetType, &procName, &paramStmtBlock)?,
+                };
                 let function = procFunVal;
 
+                //Opens the scope for the procedures nested inside it
+                self.procScopes.push(HashMap::new());
+                self.procPath.push(procName.clone());
+
 
                 //Creates the entrypoint at the procedure
                 let procEntry = self.context.append_basic_block(procFunVal, "procEntry");
@@ -1407,7 +1641,7 @@ impl<'ctx> Compiler<'ctx> {
                                 paramPtr = val;
                             }
                             Err(err) => {
-                                panic!("Error allocating param space {}", err);
+                                return Err(format!("Error allocating param space {}", err));
                             }
                         }
 
@@ -1424,7 +1658,12 @@ impl<'ctx> Compiler<'ctx> {
                         for paramStmt in stmtVec.clone(){
                             let curStmt = paramStmt.clone();
                             match curStmt{
-                                Stmt::VarDecl(varName, varType, lineNum) => {
+                                //An out or in out parameter already points at the caller's variable, so it is used as the variable itself
+                                Stmt::ParamDecl(varName, varType, paramMode, lineNum) if paramMode != ParamMode::In => {
+                                    let params = procFunVal.get_params();
+                                    procLocTable.insert(varName.clone(), params[i].into_pointer_value());
+                                }
+                                Stmt::ParamDecl(varName, varType, paramMode, lineNum) => {
                                     let params = procFunVal.get_params();
                                     let paramValue = params[i];
                                     let paramName = varName.clone();
@@ -1437,7 +1676,7 @@ impl<'ctx> Compiler<'ctx> {
                                             paramPtr = val;
                                         }
                                         Err(err) => {
-                                            panic!("Error allocating param space {}", err);
+                                            return Err(format!("Error allocating param space {}", err));
                                         }
                                     }
 
@@ -1450,14 +1689,14 @@ impl<'ctx> Compiler<'ctx> {
 
                                 }
                                 _ => {
-                                    panic!("Parameters must be variable declaration or block");
+                                    return Err(format!("Parameters must be variable declaration or block"));
                                 }
                             }
                             i += 1;
                         }
                     }
                     _ => {
-                        panic!("Parameters must be variable declaration or block");
+                        return Err(format!("Parameters must be variable declaration or block"));
                     }
                 }
 
@@ -1471,10 +1710,12 @@ impl<'ctx> Compiler<'ctx> {
                 // Check if the variable is a Block and iterate through it
                 if let Stmt::Block(ref instrs, lineNum) = procHeader.clone() {
                     for instr in instrs {
-                        self.compileStmt(instr.clone(), &procBuilder, &mut procLocTable, function);
+                        if let Err(msg) = self.compileStmt(instr.clone(), &procBuilder, &mut procLocTable, function) {
+                            self.reportIce(msg);
+                        }
                     }
                 } else {
-                    panic!("Problem with procedure AST: header must be a Block");
+                    return Err(format!("Problem with procedure AST: header must be a Block"));
                 }
 
                 // println!("procedure Header processed");
@@ -1493,113 +1734,91 @@ impl<'ctx> Compiler<'ctx> {
                 let mut body = *newBodyBox;
 
                 // Check if the variable is a Block and iterate through it
+                //An error is reported here rather than passed up, so the rest of the program is still compiled
                 if let Stmt::Block(ref instrs, lineNum) = body.clone() {
                     for instr in instrs {
-                        let good = self.compileStmt(instr.clone(), &procBuilder, &mut procLocTable, function);
+                        if self.blockClosed(&procBuilder) {
+                            break;
+                        }
+                        if let Err(msg) = self.compileStmt(instr.clone(), &procBuilder, &mut procLocTable, function) {
+                            self.reportIce(msg);
+                            break;
+                        }
                     }
                 } else {
-                    println!("Problem with proc AST: body must be a Block");
+                    self.reportError("Problem with proc AST: body must be a Block".to_string());
+                }
+
+                //A procedure that returns nothing returns when it runs off the end of its body
+                //Otherwise the flow checker made sure every path returns, so the last block is only left open when nothing
+                //can reach it, like the merge after an if where both sides return. It is marked unreachable to keep the function valid
+                if let Some(lastBlock) = procBuilder.get_insert_block() {
+                    if lastBlock.get_terminator().is_none() {
+                        if procRetType == VarType::Void {
+                            let _ = procBuilder.build_return(None);
+                        } else {
+                            let _ = procBuilder.build_unreachable();
+                        }
+                    }
                 }
                 
-                self.scope -= 1;
+                self.procScopes.pop();
+                self.procPath.pop();
 
                 // println!("Procedure created");
-                return true;             
+                return Ok(());             
             }
             //The StringLiteral expression type was used for development and debugging
             //It should never make it here but is covered just in case
             Stmt::StringLiteral(str, lineNum) => {
-                panic!("StringLiteral Stmt, this should never happen, line {}", lineNum.clone());
+                return Err(format!("StringLiteral Stmt, this should never happen, line {}", lineNum.clone()));
             }
             //Return expressions
             Stmt::Return(valueExpr, lineNum) => {
                 let retValExpr = valueExpr.clone();
-                //IF returning a variable value
+                //This happens if there is no return variable, aka this is a void
+                //A bare return in the program body leaves main, which still gives back 0 for success
+                //Any other function with a return type needs a value, which the checker should have made sure of
                 if let Expr::VarRef(varName) = retValExpr.clone(){
-                    //This happens if there is no return variable, aka this is a void
                     if varName.clone() == ""{
-                        let _ = builder.build_return(None);
-                        return true;
-                    } 
-                    //If returning a variable
-                    else {
-                        //Evaluates the expression, getting the variables value
-                        let exprCheck = self.compileExpr(&retValExpr.clone(), builder, localTable);
-                        //Returns the correct type depending on what it evaluates to
-                        //Creates the relevant return instruction in the module
-                        match exprCheck {
-                            Ok(val) => {
-                                match val {
-                                    BasicValueEnum::IntValue(int_val) => {
-                                        let _ = builder.build_return(Some(&int_val));
-                                    }
-                                    BasicValueEnum::FloatValue(float_val) => {
-                                        let _ = builder.build_return(Some(&float_val));
-                                    }
-                                    BasicValueEnum::PointerValue(ptr_val) => {
-                                        let _ = builder.build_return(Some(&ptr_val));
-                                    }
-                                    BasicValueEnum::ArrayValue(array_val) => {
-                                        let _ = builder.build_return(Some(&array_val));
-                                    }
-                                    BasicValueEnum::StructValue(struct_val) => {
-                                        let _ = builder.build_return(Some(&struct_val));
-                                    }
-                                    BasicValueEnum::VectorValue(vector_val) => {
-                                        let _ = builder.build_return(Some(&vector_val));
-                                    }
+                        match function.get_type().get_return_type() {
+                            Some(retType) => {
+                                if function.get_name().to_bytes() != b"main" {
+                                    return Err(format!("Return without a value from {}, which returns {}", function.get_name().to_string_lossy(), retType));
                                 }
-                                return true;
+                                let _ = builder.build_return(Some(&retType.const_zero()));
                             }
-                            Err(e) => {
-                                // Handle the error case
-                                panic!("Failed get return value: {}", e);
+                            None => {
+                                let _ = builder.build_return(None);
                             }
                         }
-                        
+                        return Ok(());
                     }
                 }
-                //If we are not returning a variable and are instead returning a value
-                else {
-                    //Evaluates the value and returns the relevant type
-                    let exprCheck = self.compileExpr(&retValExpr.clone(), builder, localTable);
-                        match exprCheck {
-                            Ok(val) => {
-                                match val {
-                                    BasicValueEnum::IntValue(int_val) => {
-                                        let _ = builder.build_return(Some(&int_val));
-                                    }
-                                    BasicValueEnum::FloatValue(float_val) => {
-                                        let _ = builder.build_return(Some(&float_val));
-                                    }
-                                    BasicValueEnum::PointerValue(ptr_val) => {
-                                        let _ = builder.build_return(Some(&ptr_val));
-                                    }
-                                    BasicValueEnum::ArrayValue(array_val) => {
-                                        let _ = builder.build_return(Some(&array_val));
-                                    }
-                                    BasicValueEnum::StructValue(struct_val) => {
-                                        let _ = builder.build_return(Some(&struct_val));
-                                    }
-                                    BasicValueEnum::VectorValue(vector_val) => {
-                                        let _ = builder.build_return(Some(&vector_val));
-                                    }
-                                }
-                                return true;
-                            }
-                            Err(e) => {
-                                // Handle the error case
-                                println!("Failed get return value: {}", e);
-                                panic!();
-                            }
-                        }
+
+                //Evaluates the value being returned
+                let exprCheck = self.compileExpr(&retValExpr.clone(), builder, localTable);
+                let mut retVal: BasicValueEnum;
+                match exprCheck {
+                    Ok(val) => {
+                        retVal = val;
+                    }
+                    Err(e) => {
+                        return Err(format!("Failed get return value: {}", e));
+                    }
                 }
-                
+
+                //Converts it to the return type of the procedure, the checker lets an integer procedure return a bool and so on
+                if let Some(retType) = function.get_type().get_return_type() {
+                    retVal = self.convertValue(builder, retVal, retType)?;
+                }
+                let _ = builder.build_return(Some(&retVal));
+                return Ok(());
             }
             //For the entire program statement, this shouldnt happen
             //because the only program statement should be handled by programCompiler
             Stmt::Program(name, headerBox, bodyBox, lineNum) => {
-                panic!("Program Stmt, this should never happen. Statement on line {}", lineNum.clone());
+                return Err(format!("Program Stmt, this should never happen. Statement on line {}", lineNum.clone()));
             }
             
         }
@@ -1625,1095 +1844,192 @@ impl<'ctx> Compiler<'ctx> {
                 let floatVal = floatType.const_float(value.clone().into());
                 return Ok(BasicValueEnum::FloatValue(floatVal.clone()));
             }
-            //This needs ironed outs
+            //String literals become constants in the module, the value is a pointer to the text
             Expr::StringLiteral(string) => {
-                let stringBytes = string.as_bytes();
-    
-    
-                let arrayVal = self.context.const_string(stringBytes, false).clone();
-    
-    
-                // Wrap the array constant in a BasicValueEnum
-                let basicArrayVal = BasicValueEnum::ArrayValue(arrayVal);
-                return Ok(basicArrayVal.clone());
-    
+                let strPtr = self.constString(string);
+                return Ok(BasicValueEnum::PointerValue(strPtr));
             }
-            //This needs ironed out
+            //Integer array literals become a constant array of the values
             Expr::IntArrayLiteral(size, values) => {
-                // println!("intarray NEEDS WRITTEN");
-                let i32_type = self.context.i32_type();
-                let intValue = i32_type.const_int(0, false);                
-                return Ok(BasicValueEnum::IntValue(intValue));
+                let i32Type = self.context.i32_type();
+                let intVals: Vec<IntValue> = values.iter().map(|val| i32Type.const_int(*val as u64, true)).collect();
+                return Ok(BasicValueEnum::ArrayValue(i32Type.const_array(&intVals)));
             }
             Expr::BoolLiteral(boolVal) => {
                 let boolType = self.context.custom_width_int_type(1).clone();
                 let trueVal = BasicValueEnum::IntValue(boolType.const_int(1, false));
                 let falseVal = BasicValueEnum::IntValue(boolType.const_int(0, false));
-                match boolVal{
-                    true => {
-                        return Ok(trueVal);
-                    }
-                    false => {
-                        return Ok(falseVal);
-                    }
-                }
-            }
-            
-            
-            //REFERENCES
-            //For a variable reference, returns the stored value of the variable
-            Expr::VarRef(varName) => {
-                //Gets the value if defined in local scope
-                let checkLocVar = localTable.get(&varName.clone());
-                match checkLocVar{
-                    Some(varPtr) => {
-                        // println!("Loading local value {} at location {}", varName.clone(), varPtr.clone());
-                        let loadedVal = builder.build_load(varPtr.clone(), &varName.clone());
-                        match loadedVal{
-                            Ok(val) => {
-                                return Ok(val.clone());
-                            }
-                            Err(err) => {
-                                panic!("{}", format!("Error with pointer to value {}", varName.clone()));
-                            }
-                        }
-                    }
-                    None => {
-                        //Gets the value if in global scope
-                        let checkGlobVar = self.globalTable.get(&varName.clone());
-                            match checkGlobVar{
-                                Some(varPtr) => {
-                                    // println!("Loading local value {} at location {}", varName.clone(), varPtr.clone());
-                                    
-                                    let loadedVal = builder.build_load(varPtr.clone(), &varName.clone());
-                                    match loadedVal{
-                                        Ok(val) => {
-                                            return Ok(val.clone());
-                                        }
-                                        Err(err) => {
-                                            panic!("{}", format!("FFFError with pointer to value {}", varName.clone()));
-                                        }
-                                    }
-                                }
-                                None => {
-                                    let errMsg = format!("Variable {} is not defined", varName.clone());
-                                    panic!("{}", errMsg);
-                                }
-                            }
-                    }
-                }
-                
-            }
-            //Array references
-            Expr::ArrayRef(name, indexExpr) => {
-                let targName = name.clone();
-                let arrSize = 64 as u32;
-                let i32Type = self.context.i32_type().clone();
-                let arrayType = i32Type.array_type(arrSize).clone();
-                
-                //Gets the value of the index expression
-                let indexExprCheck = self.compileExpr(&*&indexExpr.clone(), builder, localTable);
-                let mut indexVal: BasicValueEnum;
-                match indexExprCheck{
-                    Ok(val) => {
-                        indexVal = val.clone();
-                    }
-                    Err(err) => {
-                        // println!("{}", err.clone());
-                        let errMsg = format!("Could error with index {}", err.clone());
-                        panic!("{}", errMsg.clone());
-                    }
-                }
-    
-                //Gets the pointer to the array 
-                let mut arrayPtr: PointerValue;
-                let checkLocVar = localTable.get(&targName.clone());
-                match checkLocVar{
-                    Some(ptr) => {
-                        // println!("getting local array {} at location {}", targName.clone(), ptr.clone());
-                        arrayPtr = ptr.clone();
-                    }
-                    None => {
-                        let checkGlobVar = self.globalTable.get(&targName.clone());
-                        match checkGlobVar{
-                            Some(ptr) => {
-                                // println!("Gettting global array index  {} at location {}", targName.clone(), ptr.clone());
-                                arrayPtr = ptr.clone();
-                            }
-                            None => {
-                                let errMsg = format!("variable {} not found", targName.clone());
-                                panic!("{}", errMsg.clone());
-                            }
-                        }
-                    }
-                }
-    
-                //Does the necessary conversions to allow compatability
-                let mut indexInt: IntValue;
-                match indexVal{
-                    BasicValueEnum::IntValue(val) => {
-                        indexInt = val.clone();
-                    }
-                    BasicValueEnum::FloatValue(val) => {
-                        let intType = self.context.i32_type().clone();
-                        let intValue = builder.build_float_to_signed_int(val.clone(), intType, "float_to_int");
-                        match intValue{
-                            Ok(iVal) => {
-                                indexInt = iVal.clone();
-                            }
-                            Err(err) => {
-                                let errMsg = format!("Error converting float to int");
-                                panic!("{}", errMsg.clone());
-                            }
-                        }
-    
-                    }
-                    _ => {
-                        let errMsg = format!("Can only index by integer");
-                        panic!("{}", errMsg.clone());
-                    }
-                }
-            
-                // Get the pointer to the desired index
-                let variablePtr: PointerValue;
-                let intType = self.context.i32_type().clone();
-                let zero = intType.const_int(0, false);
-                let indexList = [zero, indexInt];
-                let checkIndexPtr = unsafe { builder.build_gep(arrayPtr, &indexList, "arrayIndexLoad") };
-                match checkIndexPtr{
-                    Ok(ptr) => {
-                        variablePtr = ptr.clone();
-                    }
-                    Err(err) => {
-                        let errMsg = format!("Error getting array index ptr");
-                        panic!("{}", errMsg);
-                    }
-                }
-    
-                //Gets the value at that pointer and returns it
-                let retValCheck = builder.build_load(variablePtr, "arrayIndexReference");
-                match retValCheck{
-                    Ok(val) => {
-                        return Ok(val.clone());
-                    }
-                    Err(msg) => {
-                        let errMsg = format!("Error getting array index value");
-                        panic!("{}", errMsg.clone());
-                    }
-                }
-                
-            }
-            //Procedure call/reference
-            Expr::ProcRef(procName, params) => {
-                let realProcName = procName.clone();
-                let origProcName = format!("{}{}", self.scope - 1, procName.clone());
-                let newProcName: String;
-                if (procName.contains("get") | procName.contains("put")){
-                    newProcName = procName.clone();
-                } else {
-                    newProcName = format!("{}{}", self.scope.to_string(), procName.clone());
-                }
-                let procName = newProcName;
-                self.scope += 1;    
-
-                
-                
-                
-                
-                
-                //Get the function value from the module
-                let mut function: FunctionValue;
-                let functionCheck = self.module.get_function(&procName.clone());
-                match functionCheck{
-                    Some(fun) => {
-                        function = fun.clone();
-                    }
-                    None => {
-                        let functionCheck = self.module.get_function(&&origProcName.clone());
-                        match functionCheck{
-                            Some(fun) => {
-                                function = fun.clone();
-                            }
-                            None => {
-                                let errMsg = format!("Function: {} not found in this scope", realProcName.clone());
-                                panic!("{}", errMsg);
-                            }
-                        }
-                    }
-                }
-
-                    
-
-
-                //Compile params and add their values to a vector
-                let mut compiledParams: Vec<BasicValueEnum> = Vec::new();
-                if let Some(paramExprs) = params.clone(){
-                    for param in paramExprs{
-                        let paramCheck = self.compileExpr(&param.clone(), builder, localTable);
-                        match paramCheck{
-                            Ok(val) => {
-                                compiledParams.push(val.clone());
-                            }
-                            Err(err) => {
-                                let errMsg = format!("Error parsing function call param: {}", err.clone());
-                                panic!("{}", errMsg.clone());
-                            }
-                        }
-                    }
-                }
-
-                //COnvert teh vector of params to correct type for calling function
-                let params: Vec<BasicMetadataValueEnum> = compiledParams.into_iter().map(|val| val.into()).collect();
-                let parmVals = params.as_slice();
-
-
-
-
-                //Create the function call
-                let procCallRes = builder.build_call(function, parmVals, "callProc");
-                self.scope -= 1;
-                
-                match procCallRes{
-                    Ok(val) => {
-                        let retVal = val.try_as_basic_value().left().unwrap();
-                        return Ok(retVal.clone());
-                    }
-                    Err(err) => {
-                        let errMsg = format!("Error calling procedure");
-                        panic!("{}", errMsg);
-                    }
-                }
-            }
-            
-
-            //EXPRESSIONS
-            //Arithmetic operations
-            Expr::ArthOp(op1, op, op2) => {
-                //Sets up types to be used
-                let intType = self.context.i32_type().clone();
-                let floatType = self.context.f32_type().clone();
-    
-                //First evaluates the values of both operands
-                let op1Res = self.compileExpr(&*op1.clone(), builder, localTable).clone();
-                let op2Res = self.compileExpr(&*op2.clone(), builder, localTable).clone();
-                let mut op1Val: BasicValueEnum;
-                let mut op2Val: BasicValueEnum;
-                //Makes sure both results of checked operands are good
-                match op1Res.clone(){
-                    Ok(res) => {
-                        op1Val = res.clone();
-                    }
-                    Err(msg) => {
-                        panic!("{}", msg.clone());
-                    }
-                }
-                match op2Res.clone(){
-                    Ok(res) => {
-                        op2Val = res.clone();
-                    }
-                    Err(msg) => {
-                        panic!("{}", msg.clone());
-                    }
-                }
-    
-                //Checks if either value is a float
-                let op1IsFloat: bool;
-                match op1Val.clone(){
-                    BasicValueEnum::FloatValue(_) => {
-                        op1IsFloat = true;
-                    }
-                    _ => {
-                        op1IsFloat = false;
-                    }
-                };
-                let op2IsFloat: bool;
-                match op2Val.clone(){
-                    BasicValueEnum::FloatValue(_) => {
-                        op2IsFloat = true;
-                    }
-                    _ => {
-                        op2IsFloat = false;
-                    }
-                };
-    
-                //a match case to handle the different types of operators
-                match op.clone(){
-                    //Addition
-                    Operator::Add => {
-                        //If either result is a float, casts the int to float and does the float addition
-                        if op1IsFloat.clone() || op2IsFloat.clone() {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val.clone() {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, floatType, "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val.clone();
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val.clone() {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val.clone();
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, floatType, "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op2Float = val.clone();
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Creates the float addition and adds it to the statement
-                            let retOp = builder.build_float_add(op1Float, op2Float, "addFloat");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::FloatValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                        } 
-                        
-                        // Both operands are integers
-                        else {
-                            let op1Int = op1Val.into_int_value();
-                            let op2Int = op2Val.into_int_value();
-                            let retOp = builder.build_int_add(op1Int.clone(), op2Int.clone(), "addInt");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                            
-                        }
-                    }
-                    //Subtraction
-                    Operator::Sub => {
-                        //If either result is a float
-                        if op1IsFloat || op2IsFloat {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, floatType, "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val.clone();
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val;
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, floatType, "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op2Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Does the float add
-                            let retOp = builder.build_float_sub(op1Float, op2Float, "subFloat");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::FloatValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                        } 
-                        // Both operands are integers
-                        else {
-                            let op1Int = op1Val.into_int_value();
-                            let op2Int = op2Val.into_int_value();
-                            let retOp = builder.build_int_sub(op1Int.clone(), op2Int.clone(), "subInt");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                            
-                        }
-                    } 
-                    //Multiplication
-                    Operator::Mul => {
-                        //If either result is a float
-                        if op1IsFloat || op2IsFloat {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, floatType, "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val;
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, floatType, "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op2Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Does the float add
-                            let retOp = builder.build_float_mul(op1Float, op2Float, "multiplyFloat");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::FloatValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                        } 
-                        //Both operands are integers
-                        else {
-                            let op1Int = op1Val.clone().into_int_value();
-                            let op2Int = op2Val.clone().into_int_value();
-                            let retOp = builder.build_int_mul(op1Int.clone(), op2Int.clone(), "multiplyInt");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                            
-                        }
-                    }
-                    //division
-                    Operator::Div => {
-                        //If either result is a float
-                        if op1IsFloat || op2IsFloat {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, floatType, "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val;
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, floatType, "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op2Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Does the float add
-                            let retOp = builder.build_float_div(op1Float, op2Float, "divideFloat");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::FloatValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                        } 
-                        // Both operands are integers
-                        else {
-                            let op1Int = op1Val.into_int_value();
-                            let op2Int = op2Val.into_int_value();
-                            let retOp = builder.build_int_signed_div(op1Int.clone(), op2Int.clone(), "divideInt");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                            
-                        }
-                    }
-                    _ => {
-                        //This should never happen because of parsing and error checking
-                        panic!("Improper operator for arthimatic operation");
-                    }
-                }
-            
-            }
-            //Relational operation
-            Expr::RelOp(op1, op, op2) => {   
-                
-                //First gets the values of both operands
-                let op1Res = self.compileExpr(&*op1.clone(), builder, localTable);
-                let op2Res = self.compileExpr(&*op2.clone(), builder, localTable);
-                let mut op1Val: BasicValueEnum;
-                let mut op2Val: BasicValueEnum;
-                //Makes sure both results of checked operands are good
-                match op1Res{
-                    Ok(res) => {
-                        op1Val = res.clone();
-                    }
-                    Err(msg) => {
-                        panic!("{}", msg.clone());
-                    }
-                }
-                match op2Res{
-                    Ok(res) => {
-                        op2Val = res.clone();
-                    }
-                    Err(msg) => {
-                        panic!("{}", msg.clone());
-                    }
-                }
-    
-                //Checks if either value is a float
-                let op1IsFloat: bool;
-                match op1Val.clone(){
-                    BasicValueEnum::FloatValue(_) => {
-                        op1IsFloat = true;
-                    }
-                    _ => {
-                        op1IsFloat = false;
-                    }
-                };
-                let op2IsFloat: bool;
-                match op2Val.clone(){
-                    BasicValueEnum::FloatValue(_) => {
-                        op2IsFloat = true;
-                    }
-                    _ => {
-                        op2IsFloat = false;
-                    }
-                };
-    
-                //a match case to handle the different types of operators
-                match op{
-                    Operator::Check_Equal => {
-                        //If either result is a float
-                        if op1IsFloat || op2IsFloat {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val;
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op2Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Does the float equality check
-                            let retOp = builder.build_float_compare(FloatPredicate::OEQ,op1Float, op2Float, "equalFloat");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                        } 
-                        // Both operands are integers
-                        else {
-                            let op1Int = op1Val.into_int_value();
-                            let op2Int = op2Val.into_int_value();
-                            let retOp = builder.build_int_compare(IntPredicate::EQ,op1Int, op2Int, "equalInt");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                            
-                        }
-                    }
-                    Operator::Greater => {
-                        //If either result is a float
-                        if op1IsFloat || op2IsFloat {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for greater"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val;
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op2Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Does the float equality check
-                            let retOp = builder.build_float_compare(FloatPredicate::OGT,op1Float, op2Float, "floatGreater");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                        } 
-                        // Both operands are integers
-                        else {
-                            let op1Int = op1Val.into_int_value();
-                            let op2Int = op2Val.into_int_value();
-                            let retOp = builder.build_int_compare(IntPredicate::SGT,op1Int, op2Int, "intGreater");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                            
-                        }
-                    }
-                    Operator::Greater_Equal => {
-                        //If either result is a float
-                        if op1IsFloat || op2IsFloat {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val;
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op2Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Does the float equality check
-                            let retOp = builder.build_float_compare(FloatPredicate::OGE,op1Float, op2Float, "floatGreaterEqual");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
+                match boolVal{
+                    true => {
+                        return Ok(trueVal);
+                    }
+                    false => {
+                        return Ok(falseVal);
+                    }
+                }
+            }
+            
+            
+            //REFERENCES
+            //For a variable reference, returns the stored value of the variable
+            Expr::VarRef(varName) => {
+                //Gets the value if defined in local scope
+                let checkLocVar = localTable.get(&varName.clone());
+                match checkLocVar{
+                    Some(varPtr) => {
+                        // println!("Loading local value {} at location {}", varName.clone(), varPtr.clone());
+                        let loadedVal = builder.build_load(varPtr.clone(), &varName.clone());
+                        match loadedVal{
+                            Ok(val) => {
+                                return Ok(val.clone());
                             }
-                        } 
-                        // Both operands are integers
-                        else {
-                            let op1Int = op1Val.into_int_value();
-                            let op2Int = op2Val.into_int_value();
-                            let retOp = builder.build_int_compare(IntPredicate::SGE,op1Int, op2Int, "intGreaterEqual");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
+                            Err(err) => {
+                                return Err(format!("Error with pointer to value {}", varName.clone()));
                             }
-                            
                         }
                     }
-                    Operator::Less => {
-                        //If either result is a float
-                        if op1IsFloat || op2IsFloat {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val;
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
+                    None => {
+                        //Gets the value if in global scope
+                        let checkGlobVar = self.globalTable.get(&varName.clone());
+                            match checkGlobVar{
+                                Some(varPtr) => {
+                                    // println!("Loading local value {} at location {}", varName.clone(), varPtr.clone());
+                                    
+                                    let loadedVal = builder.build_load(varPtr.clone(), &varName.clone());
+                                    match loadedVal{
                                         Ok(val) => {
-                                            op2Float = val;
+                                            return Ok(val.clone());
                                         }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
+                                        Err(err) => {
+                                            return Err(format!("FFFError with pointer to value {}", varName.clone()));
                                         }
                                     }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Does the float equality check
-                            let retOp = builder.build_float_compare(FloatPredicate::OLT,op1Float, op2Float, "floatLess");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                        } 
-                        // Both operands are integers
-                        else {
-                            let op1Int = op1Val.into_int_value();
-                            let op2Int = op2Val.into_int_value();
-                            let retOp = builder.build_int_compare(IntPredicate::SLT,op1Int, op2Int, "intLess");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
                                 }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
+                                None => {
+                                    let errMsg = format!("Variable {} is not defined", varName.clone());
+                                    return Err(format!("{}", errMsg));
                                 }
                             }
-                            
-                        }
                     }
-                    Operator::Less_Equal => {
-                        //If either result is a float
-                        if op1IsFloat || op2IsFloat {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val;
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op2Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
+                }
+                
+            }
+            //Array references
+            Expr::ArrayRef(name, indexExpr) => {
+                let elementPtr = self.arrayElementPtr(name, indexExpr, builder, localTable)?;
     
-                            //Does the float equality check
-                            let retOp = builder.build_float_compare(FloatPredicate::OLE,op1Float, op2Float, "floatLessEqual");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
-                            }
-                        } 
-                        // Both operands are integers
-                        else {
-                            let op1Int = op1Val.into_int_value();
-                            let op2Int = op2Val.into_int_value();
-                            let retOp = builder.build_int_compare(IntPredicate::SLE,op1Int, op2Int, "intLessEqual");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
+                //Gets the value at that pointer and returns it
+                let retValCheck = builder.build_load(elementPtr, "arrayIndexReference");
+                match retValCheck{
+                    Ok(val) => {
+                        return Ok(val.clone());
+                    }
+                    Err(msg) => {
+                        let errMsg = format!("Error getting array index value");
+                        return Err(format!("{}", errMsg));
+                    }
+                }
+                
+            }
+            //Procedure call/reference
+            Expr::ProcRef(procName, params) => {
+                let procCall = self.compileCall(procName, params, builder, localTable)?;
+                match procCall.try_as_basic_value().left() {
+                    Some(retVal) => {
+                        return Ok(retVal.clone());
+                    }
+                    None => {
+                        return Err(format!("Procedure {} does not return a value", procName.clone()));
+                    }
+                }
+            }
+            
+
+            //EXPRESSIONS
+            //Arithmetic operations
+            Expr::ArthOp(op1, op, op2) => {
+                //First evaluates the values of both operands
+                let op1Val = self.compileExpr(&*op1.clone(), builder, localTable)?;
+                let op2Val = self.compileExpr(&*op2.clone(), builder, localTable)?;
+
+                //Whole arrays are worked on element by element in a loop
+                if op1Val.is_array_value() || op2Val.is_array_value() {
+                    return self.arrayArithmetic(builder, op, op1Val, op2Val);
+                }
+                return self.buildArithmetic(builder, op, op1Val, op2Val);
+            }
+            //Relational operation
+            Expr::RelOp(op1, op, op2) => {
+                //First gets the values of both operands
+                let op1Val = self.compileExpr(&*op1.clone(), builder, localTable)?;
+                let op2Val = self.compileExpr(&*op2.clone(), builder, localTable)?;
+
+                //Casts both to their common type, floats are compared as floats and everything else as signed integers
+                let (op1Val, op2Val) = self.unifyOperands(builder, op1Val, op2Val)?;
+
+                //a match case to handle the different types of operators
+                let retOp = match (op1Val, op2Val) {
+                    (BasicValueEnum::FloatValue(op1Float), BasicValueEnum::FloatValue(op2Float)) => {
+                        let (predicate, name) = match op.clone() {
+                            Operator::Check_Equal => (FloatPredicate::OEQ, "equalFloat"),
+                            Operator::Greater => (FloatPredicate::OGT, "floatGreater"),
+                            Operator::Greater_Equal => (FloatPredicate::OGE, "floatGreaterEqual"),
+                            Operator::Less => (FloatPredicate::OLT, "floatLess"),
+                            Operator::Less_Equal => (FloatPredicate::OLE, "floatLessEqual"),
+                            Operator::Not_Equals => (FloatPredicate::ONE, "floatNotEqual"),
+                            _ => {
+                                //This should never happen because of parsing and error checking
+                                return Err(format!("Improper operator for relational operation"));
+                            }
+                        };
+                        builder.build_float_compare(predicate, op1Float, op2Float, name)
+                    }
+                    (BasicValueEnum::IntValue(op1Int), BasicValueEnum::IntValue(op2Int)) => {
+                        let (predicate, name) = match op.clone() {
+                            Operator::Check_Equal => (IntPredicate::EQ, "equalInt"),
+                            Operator::Greater => (IntPredicate::SGT, "intGreater"),
+                            Operator::Greater_Equal => (IntPredicate::SGE, "intGreaterEqual"),
+                            Operator::Less => (IntPredicate::SLT, "intLess"),
+                            Operator::Less_Equal => (IntPredicate::SLE, "intLessEqual"),
+                            Operator::Not_Equals => (IntPredicate::NE, "intNotEqual"),
+                            _ => {
+                                //This should never happen because of parsing and error checking
+                                return Err(format!("Improper operator for relational operation"));
                             }
-                            
-                        }
+                        };
+                        builder.build_int_compare(predicate, op1Int, op2Int, name)
                     }
-                    Operator::Not_Equals => {
-                        //If either result is a float
-                        if op1IsFloat || op2IsFloat {
-                            
-                            //Checks if op1 is float, casts it to float if not
-                            let op1Float: FloatValue;
-                            match op1Val {
-                                BasicValueEnum::FloatValue(val) => op1Float = val,
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op1Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for not equal"),
-                            };
-    
-                            //Checks if op2 is float, casts it to float if not
-                            let op2Float: FloatValue;
-                            match op2Val {
-                                BasicValueEnum::FloatValue(val) => {
-                                    op2Float = val;
-                                }
-                                BasicValueEnum::IntValue(val) => {
-                                    // Convert integer to float if necessary
-                                    let resConv = builder.build_signed_int_to_float(val, self.context.f32_type(), "intToFloat");
-                                    match resConv{
-                                        Ok(val) => {
-                                            op2Float = val;
-                                        }
-                                        Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
-                                        }
-                                    }
-                                },
-                                _ => panic!("Unsupported type for addition"),
-                            };
-    
-                            //Does the float equality check
-                            let retOp = builder.build_float_compare(FloatPredicate::ONE,op1Float, op2Float, "floatNotEqual");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
+                    _ => {
+                        return Err(format!("Unsupported types for relational operation"));
+                    }
+                };
+                match retOp {
+                    Ok(result) => {
+                        return Ok(BasicValueEnum::IntValue(result));
+                    }
+                    Err(errMsg) => {
+                        return Err(format!("{}", errMsg));
+                    }
+                }
+            }
+            //Not, flips every bit so it is a logical not on a bool and a bitwise not on an integer
+            Expr::NotOp(operand) => {
+                let operandVal = self.compileExpr(operand, builder, localTable)?;
+                match operandVal {
+                    BasicValueEnum::IntValue(intVal) => {
+                        match builder.build_not(intVal, "notOp") {
+                            Ok(result) => {
+                                return Ok(BasicValueEnum::IntValue(result));
                             }
-                        } 
-                        // Both operands are integers
-                        else {
-                            let op1Int = op1Val.into_int_value();
-                            let op2Int = op2Val.into_int_value();
-                            let retOp = builder.build_int_compare(IntPredicate::NE,op1Int, op2Int, "intNotEqual");
-                            match retOp{
-                                Ok(result) => {
-                                    return Ok(BasicValueEnum::IntValue(result.clone()));
-                                }
-                                Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
-                                }
+                            Err(errMsg) => {
+                                return Err(format!("{}", errMsg));
                             }
-                            
                         }
                     }
                     _ => {
-                        //This should never happen because of parsing and error checking
-                        panic!("Improper operator for logical operation");
+                        return Err(format!("Not can only be used on a bool or an integer"));
                     }
                 }
-            
             }
             //Logical expressions
             Expr::LogOp(op1, op, op2) => {
@@ -2730,7 +2046,7 @@ impl<'ctx> Compiler<'ctx> {
                         op1Val = res;
                     }
                     Err(msg) => {
-                        panic!("{}", msg.clone());
+                        return Err(format!("{}", msg));
                     }
                 }
                 match op2Res{
@@ -2738,7 +2054,7 @@ impl<'ctx> Compiler<'ctx> {
                         op2Val = res;
                     }
                     Err(msg) => {
-                        panic!("{}", msg.clone());
+                        return Err(format!("{}", msg));
                     }
                 }
     
@@ -2781,11 +2097,11 @@ impl<'ctx> Compiler<'ctx> {
                                             op1Int = val;
                                         }
                                         Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
+                                            return Err(format!("{}", errMsg));
                                         }
                                     }
                                 },
-                                _ => panic!("Unsupported type for addition"),
+                                _ => return Err(format!("Unsupported type for addition")),
                             };
     
                             //Checks if op2 is float, casts it to float if not
@@ -2800,11 +2116,11 @@ impl<'ctx> Compiler<'ctx> {
                                             op2Int = val;
                                         }
                                         Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
+                                            return Err(format!("{}", errMsg));
                                         }
                                     }
                                 },
-                                _ => panic!("Unsupported type for addition"),
+                                _ => return Err(format!("Unsupported type for addition")),
                             };
     
                             let retOp = builder.build_and(op1Int, op2Int, "intAnd");
@@ -2813,7 +2129,7 @@ impl<'ctx> Compiler<'ctx> {
                                     return Ok(BasicValueEnum::IntValue(result.clone()));
                                 }
                                 Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
+                                    return Err(format!("{}", errMsg));
                                 }
                             }
     
@@ -2828,7 +2144,7 @@ impl<'ctx> Compiler<'ctx> {
                                     return Ok(BasicValueEnum::IntValue(result.clone()));
                                 }
                                 Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
+                                    return Err(format!("{}", errMsg));
                                 }
                             }
                             
@@ -2851,11 +2167,11 @@ impl<'ctx> Compiler<'ctx> {
                                             op1Int = val;
                                         }
                                         Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
+                                            return Err(format!("{}", errMsg));
                                         }
                                     }
                                 },
-                                _ => panic!("Unsupported type for addition"),
+                                _ => return Err(format!("Unsupported type for addition")),
                             };
     
                             //Checks if op2 is float, casts it to float if not
@@ -2870,11 +2186,11 @@ impl<'ctx> Compiler<'ctx> {
                                             op2Int = val;
                                         }
                                         Err(errMsg) => {
-                                            panic!("{}", format!("{}", errMsg));
+                                            return Err(format!("{}", errMsg));
                                         }
                                     }
                                 },
-                                _ => panic!("Unsupported type for addition"),
+                                _ => return Err(format!("Unsupported type for addition")),
                             };
     
                             let retOp = builder.build_or(op1Int, op2Int, "intOr");
@@ -2883,7 +2199,7 @@ impl<'ctx> Compiler<'ctx> {
                                     return Ok(BasicValueEnum::IntValue(result.clone()));
                                 }
                                 Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
+                                    return Err(format!("{}", errMsg));
                                 }
                             }
     
@@ -2898,7 +2214,7 @@ impl<'ctx> Compiler<'ctx> {
                                     return Ok(BasicValueEnum::IntValue(result.clone()));
                                 }
                                 Err(errMsg) => {
-                                    panic!("{}", format!("{}", errMsg));
+                                    return Err(format!("{}", errMsg));
                                 }
                             }
                             
@@ -2908,7 +2224,7 @@ impl<'ctx> Compiler<'ctx> {
                     
                     _ => {
                         //This should never happen because of parsing and error checking
-                        panic!("Improper operator for logical operation");
+                        return Err(format!("Improper operator for logical operation"));
                     }
                 }
                 
@@ -2950,16 +2266,23 @@ impl<'ctx> Compiler<'ctx> {
         let printFnType = retType.fn_type(parmVals, false);
         let putInt = self.module.add_function("putfloat", printFnType, None);        
     }
-    //putstring (NEEDS ADJUSTED)
+    //putstring
     fn definePutStr(&mut self) {
-        let i8_type = self.context.i8_type();
-        let array_type = i8_type.array_type(65);
-        let string_type = array_type.ptr_type(AddressSpace::default());
+        let stringType = self.stringType();
         let retType = self.context.bool_type();
-        let paramTypes = vec![BasicMetadataTypeEnum::from(string_type)];
+        let paramTypes = vec![BasicMetadataTypeEnum::from(stringType)];
         let parmVals = paramTypes.as_slice();
         let printFnType = retType.fn_type(parmVals, false);
-        let putInt = self.module.add_function("putstring", printFnType, None);
+        let putStr = self.module.add_function("putstring", printFnType, None);
+    }
+
+    //boundserror, called when an array index is out of bounds, not callable from programs
+    fn defineBoundsError(&mut self) {
+        let i32Type = self.context.i32_type();
+        let paramTypes = vec![BasicMetadataTypeEnum::from(i32Type), BasicMetadataTypeEnum::from(self.stringType()), BasicMetadataTypeEnum::from(i32Type), BasicMetadataTypeEnum::from(i32Type)];
+        let parmVals = paramTypes.as_slice();
+        let boundsErrorType = self.context.void_type().fn_type(parmVals, false);
+        let boundsError = self.module.add_function("boundserror", boundsErrorType, None);
     }
 
     //getinteger
@@ -2986,6 +2309,77 @@ impl<'ctx> Compiler<'ctx> {
         let getIntType = intType.fn_type(parmVals, false);
         let putInt = self.module.add_function("getbool", getIntType, None);
     }
+    //getstring, the text it returns is on the heap
+    fn defineGetStr(&mut self) {
+        let stringType = self.stringType();
+        let paramTypes = vec![];
+        let parmVals = paramTypes.as_slice();
+        let getStrType = stringType.fn_type(parmVals, false);
+        let getStr = self.module.add_function("getstring", getStrType, None);
+    }
 
 }
 
+
+
+
+
+
+///////////////////////// TESTS /////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Compiles a program made straight from the header declarations, skipping the checker
+    fn compileDecls(decls: Vec<Stmt>) -> (Result<(), String>, Reporting) {
+        let programAst = Stmt::Program("test".to_string(), Box::new(Stmt::Block(decls, 1)), Box::new(Stmt::Block(Vec::new(), 2)), 1);
+        let context = Context::create();
+        let mut globalTable: HashMap<String, PointerValue> = HashMap::new();
+        let mut compiler = Compiler::new(programAst, &context, &mut globalTable, "Program".to_string());
+        let result = compiler.compileProgram().map(|_| ());
+        return (result, compiler.reports.clone());
+    }
+
+    #[test]
+    fn voidStorageIsIce() {
+        let voidArray = VarType::Array(Box::new(VarType::Void), 3);
+        let (result, reports) = compileDecls(vec![Stmt::GlobVarDecl("a".to_string(), voidArray, 1)]);
+        assert!(result.is_err());
+        assert_eq!(reports.diagnostics.len(), 1, "{:?}", reports.errors);
+        assert_eq!(reports.diagnostics[0].code, "C000");
+        assert!(reports.errors[0].contains("void has no stored LLVM type"), "{}", reports.errors[0]);
+    }
+
+    #[test]
+    fn bareReturnNeedsMain() {
+        //The program body can return without a value, main still gives back 0
+        let programAst = Stmt::Program("test".to_string(), Box::new(Stmt::Block(Vec::new(), 1)), Box::new(Stmt::Block(vec![Stmt::Return(Expr::VarRef(String::new()), 2)], 2)), 1);
+        let context = Context::create();
+        let mut globalTable: HashMap<String, PointerValue> = HashMap::new();
+        let mut compiler = Compiler::new(programAst, &context, &mut globalTable, "Program".to_string());
+        let module = compiler.compileProgram().unwrap();
+        assert!(module.print_to_string().to_string().contains("ret i32 0"));
+
+        //A procedure that returns a value can not
+        let procAst = Stmt::ProcDecl(VarType::Int, "f".to_string(), Box::new(Stmt::Block(Vec::new(), 2)), Box::new(Stmt::Block(Vec::new(), 2)), Box::new(Stmt::Block(vec![Stmt::Return(Expr::VarRef(String::new()), 3)], 3)), 2);
+        let (result, reports) = compileDecls(vec![procAst]);
+        assert!(result.is_err());
+        assert_eq!(reports.diagnostics[0].code, "C000");
+        assert!(reports.errors[0].contains("Return without a value from"), "{:?}", reports.errors);
+    }
+
+    #[test]
+    fn storedTypes() {
+        let decls = vec![
+            Stmt::GlobVarDecl("i".to_string(), VarType::Int, 1),
+            Stmt::GlobVarDecl("s".to_string(), VarType::Str, 1),
+            Stmt::GlobVarDecl("f".to_string(), VarType::Array(Box::new(VarType::Float), 2), 1),
+        ];
+        let (result, reports) = compileDecls(decls);
+        assert!(result.is_ok(), "{:?}", reports.errors);
+        assert!(reports.diagnostics.is_empty());
+    }
+}
+
+///////////////////////// /TESTS /////////////////////////
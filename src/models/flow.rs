@@ -0,0 +1,1165 @@
+///////////////////////// Setup /////////////////////////
+
+//Rules
+#![allow(non_snake_case)]
+#![allow(non_camel_case_types)]
+#![allow(unused_imports)]
+#![allow(dead_code)]
+#![allow(unused_parens)]
+#![allow(unused_mut)]
+#![allow(unused_variables)]
+
+//package imports
+use {
+    crate::models::parser::{Stmt, Expr, VarType, ParamMode},
+    crate::models::reporting::{Reporting, Span},
+    std::collections::{HashMap, HashSet},
+};
+
+///////////////////////// /Setup /////////////////////////
+
+
+
+///////////////////////// FLOW CHECKING SECTION /////////////////////////
+//Runs over the checked program AST and follows where control and values can go
+//A procedure that returns a value has to return on every path through it, and statements nothing can reach are warned about
+//Variables read before they are assigned, and variables, parameters and procedures that are never used, are warned about as well
+
+//The kinds of problem the flow checker reports, each has its own diagnostic code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlowCode {
+    MissingReturn,      //A procedure that returns a value can reach the end of its body without returning
+    Internal,           //A tree the flow checker cannot make sense of, which means a bug in an earlier stage
+    Unreachable,        //Warning: a statement control can never get to
+    ReadBeforeAssign,   //Warning: a variable that may be read before anything is assigned to it
+    UnusedVariable,     //Warning: a variable that is never read
+    UnusedParameter,    //Warning: a parameter that is never read or assigned
+    UnusedProcedure,    //Warning: a procedure that is never called
+}
+
+impl FlowCode {
+    //The code shown in the diagnostic, errors are numbered from F001 and warnings from F101
+    pub fn code(&self) -> &'static str {
+        match self {
+            FlowCode::MissingReturn => "F001",
+            FlowCode::Internal => "F002",
+            FlowCode::Unreachable => "F101",
+            FlowCode::ReadBeforeAssign => "F102",
+            FlowCode::UnusedVariable => "F103",
+            FlowCode::UnusedParameter => "F104",
+            FlowCode::UnusedProcedure => "F105",
+        }
+    }
+}
+
+pub struct FlowChecker {
+    pub reports: Reporting,     //The errors and warnings found while following the program
+    pub fileName: String,       //The name of the source file, used for diagnostics
+    breakSeen: Vec<bool>,       //For each loop enclosing the statement being followed, whether a break out of it was found, innermost last
+
+    //Used by the dataflow pass
+    usage: VarUsage,                                        //The variables of the procedure (or program body) being followed
+    globals: HashMap<String, usize>,                        //Every global variable, with the line it is declared on
+    globalReads: HashSet<String>,                           //The global variables read anywhere in the program
+    globalWrites: HashSet<String>,                          //The global variables assigned anywhere in the program
+    procScopes: Vec<HashMap<String, (String, Vec<ParamMode>)>>, //The procedures visible in each scope, innermost last: name to (key, how each parameter is passed)
+    procStack: Vec<String>,                                 //The keys of the procedures being followed, a call from inside a procedure to itself does not count as a use
+    procCalls: HashSet<String>,                             //The keys of every procedure called from outside itself
+    declaredProcs: Vec<(String, usize, String)>,            //Every procedure declared: (name, line, key)
+    breakStates: Vec<Option<HashSet<String>>>,              //For each loop enclosing the statement being followed, the variables assigned at every break out of it
+    curLine: usize,                                        //The line of the statement currently being followed
+}
+
+//What the dataflow pass knows about the variables of one procedure, or of the program body
+#[derive(Debug, Clone, Default)]
+struct VarUsage {
+    locals: HashMap<String, usize>,     //The local variables and parameters, with the line they are declared on
+    params: Vec<String>,                //The parameters, in order
+    tracked: HashSet<String>,           //The variables that have to be assigned before they are read
+    reads: HashSet<String>,             //The locals read
+    writes: HashSet<String>,            //The locals assigned
+    warned: HashSet<String>,            //The variables already warned about being read before they are assigned, each is only reported once
+}
+
+impl FlowChecker {
+    //The constructor
+    pub fn new() -> FlowChecker {
+        FlowChecker {
+            reports: Reporting::new(),
+            fileName: String::new(),
+            breakSeen: Vec::new(),
+            usage: VarUsage::default(),
+            globals: HashMap::new(),
+            globalReads: HashSet::new(),
+            globalWrites: HashSet::new(),
+            procScopes: Vec::new(),
+            procStack: Vec::new(),
+            procCalls: HashSet::new(),
+            declaredProcs: Vec::new(),
+            breakStates: Vec::new(),
+            curLine: 0,
+        }
+    }
+
+    //Reports a flow error on the given line
+    fn reportError(&mut self, code: FlowCode, message: String, lineNum: usize) {
+        let span = Span::line(self.fileName.clone(), lineNum);
+        self.reports.reportError(code.code(), message, Some(span));
+    }
+
+    //Reports a flow warning on the given line
+    fn reportWarning(&mut self, code: FlowCode, message: String, lineNum: usize) {
+        let span = Span::line(self.fileName.clone(), lineNum);
+        self.reports.reportWarning(code.code(), message, Some(span));
+    }
+
+    //The main outward facing function, follows the header and body of the program
+    //Returns false if any errors were found, the dataflow pass only gives warnings
+    pub fn checkProgram(&mut self, programAst: &Stmt) -> bool {
+        match programAst {
+            Stmt::Program(name, header, body, lineNum) => {
+                self.checkHeader(header);
+                self.flowsThrough(body);
+                self.checkData(header, body);
+                return !self.reports.status;
+            }
+            _ => {
+                self.reportError(FlowCode::Internal, "FlowChecker must be passed a Program AST".to_string(), 0);
+                return false;
+            }
+        }
+    }
+
+    //Follows every procedure declared in a header, including the ones nested inside them
+    fn checkHeader(&mut self, header: &Stmt) {
+        if let Stmt::Block(instrs, lineNum) = header {
+            for instr in instrs {
+                if let Stmt::ProcDecl(retType, procName, params, procHeader, body, procLine) = instr {
+                    self.checkHeader(procHeader);
+                    //Loops never cross into a procedure, a break inside one belongs to its own loops
+                    let outerLoops = std::mem::take(&mut self.breakSeen);
+                    let fallsOff = self.flowsThrough(body);
+                    self.breakSeen = outerLoops;
+                    if fallsOff && (*retType != VarType::Void) {
+                        self.reportError(FlowCode::MissingReturn, format!("Procedure {} can reach the end of its body without returning a value of type {}", procName.clone(), retType), *procLine);
+                    }
+                }
+            }
+        }
+    }
+
+    //Checks if a loop condition is a constant that is always true, so the loop can only be left with a break or return
+    fn alwaysTrue(condition: &Expr) -> bool {
+        match condition {
+            Expr::BoolLiteral(val) => *val,
+            Expr::IntLiteral(val) => *val != 0,
+            _ => false,
+        }
+    }
+
+    //Follows a statement and returns whether control can reach the end of it and carry on to the next statement
+    //Return, break and continue never do, an if or case only does if one of its branches can
+    fn flowsThrough(&mut self, stmt: &Stmt) -> bool {
+        match stmt {
+            //Once a statement in a block stops control, everything after it in the block is unreachable
+            //Only the first of them is warned about, the rest of the block goes with it
+            Stmt::Block(stmts, lineNum) => {
+                let mut reachable = true;
+                let mut stopLine = 0;
+                for instr in stmts {
+                    if !reachable {
+                        self.reportWarning(FlowCode::Unreachable, format!("Unreachable statement, control never gets past line {}", stopLine), instr.lineNum());
+                        //Still follows it for the procedures and loops inside
+                        self.flowsThrough(instr);
+                        break;
+                    }
+                    if !self.flowsThrough(instr) {
+                        reachable = false;
+                        stopLine = instr.lineNum();
+                    }
+                }
+                return reachable;
+            }
+            Stmt::Return(retVal, lineNum) => {
+                return false;
+            }
+            Stmt::Break(lineNum) => {
+                if let Some(seen) = self.breakSeen.last_mut() {
+                    *seen = true;
+                }
+                return false;
+            }
+            Stmt::Continue(lineNum) => {
+                return false;
+            }
+            Stmt::If(cond, body, elsifArms, elseBody, lineNum) => {
+                let mut flows = self.flowsThrough(body);
+                for (elsifCond, elsifBody) in elsifArms {
+                    if self.flowsThrough(elsifBody) {
+                        flows = true;
+                    }
+                }
+                //Without an else, the if can be skipped entirely
+                match elseBody {
+                    Some(elseStmt) => {
+                        if self.flowsThrough(elseStmt) {
+                            flows = true;
+                        }
+                    }
+                    None => {
+                        flows = true;
+                    }
+                }
+                return flows;
+            }
+            Stmt::Case(value, arms, others, lineNum) => {
+                let mut flows = false;
+                for (armValue, armBody) in arms {
+                    if self.flowsThrough(armBody) {
+                        flows = true;
+                    }
+                }
+                //Without an others arm, a value no arm handles goes straight past the case
+                match others {
+                    Some(othersBody) => {
+                        if self.flowsThrough(othersBody) {
+                            flows = true;
+                        }
+                    }
+                    None => {
+                        flows = true;
+                    }
+                }
+                return flows;
+            }
+            //A loop is left when its condition is false, or by a break when the condition is always true
+            Stmt::For(assignment, cond, body, lineNum) => {
+                self.breakSeen.push(false);
+                self.flowsThrough(body);
+                let hadBreak = self.breakSeen.pop().unwrap_or(false);
+                return hadBreak || !Self::alwaysTrue(cond);
+            }
+            Stmt::While(cond, body, lineNum) => {
+                self.breakSeen.push(false);
+                self.flowsThrough(body);
+                let hadBreak = self.breakSeen.pop().unwrap_or(false);
+                return hadBreak || !Self::alwaysTrue(cond);
+            }
+            //Everything else just runs and carries on
+            Stmt::StringLiteral(..) | Stmt::Expr(..) | Stmt::Assign(..) | Stmt::VarDecl(..) | Stmt::GlobVarDecl(..) | Stmt::ParamDecl(..) |
+            Stmt::Error(..) | Stmt::Call(..) | Stmt::Program(..) | Stmt::ProcDecl(..) | Stmt::ProcForward(..) => {
+                return true;
+            }
+        }
+    }
+
+    ///////////////////////// DATAFLOW /////////////////////////
+
+    //Joins the variables assigned along two paths that meet, a variable is only assigned after the join if it is on both
+    //None is a path nothing can reach, so it has no say in what is assigned
+    fn meet(first: Option<HashSet<String>>, second: Option<HashSet<String>>) -> Option<HashSet<String>> {
+        match (first, second) {
+            (None, other) => other,
+            (other, None) => other,
+            (Some(firstSet), Some(secondSet)) => Some(firstSet.intersection(&secondSet).cloned().collect()),
+        }
+    }
+
+    //Follows the values through the whole program
+    //Reading a variable before it is assigned, and declaring variables, parameters and procedures that are never used, give warnings
+    fn checkData(&mut self, header: &Stmt, body: &Stmt) {
+        //Variables declared in the program header and global variables declared anywhere can be used by every procedure
+        let mut progVars: Vec<(String, VarType)> = Vec::new();
+        if let Stmt::Block(instrs, lineNum) = header {
+            for instr in instrs {
+                if let Stmt::VarDecl(varName, varType, varLine) | Stmt::GlobVarDecl(varName, varType, varLine) = instr {
+                    progVars.push((varName.clone(), varType.clone()));
+                }
+            }
+        }
+        self.collectGlobals(header);
+
+        //The program body can read a variable before it assigns it only if no procedure assigns it either,
+        //any call could be the one that does. Arrays are filled one element at a time so they are not followed
+        let mut assignedInProcs: HashSet<String> = HashSet::new();
+        Self::collectProcAssigned(header, &mut assignedInProcs);
+        let mut mainUsage = VarUsage::default();
+        for (varName, varType) in progVars {
+            if !matches!(varType, VarType::Array(..)) && !assignedInProcs.contains(&varName) {
+                mainUsage.tracked.insert(varName);
+            }
+        }
+
+        self.procScopes.push(Self::headerProcs(header));
+        self.followHeaderData(header);
+        self.usage = mainUsage;
+        self.followData(body, Some(HashSet::new()));
+        self.procScopes.pop();
+
+        //Everything is known once the whole program has been followed
+        let mut globalList: Vec<(String, usize)> = self.globals.iter().map(|(varName, varLine)| (varName.clone(), *varLine)).collect();
+        globalList.sort_by_key(|(varName, varLine)| (*varLine, varName.clone()));
+        for (varName, varLine) in globalList {
+            if !self.globalReads.contains(&varName) {
+                self.reportUnread(&varName, varLine, self.globalWrites.contains(&varName));
+            }
+        }
+        for (procName, procLine, procKey) in self.declaredProcs.clone() {
+            if !self.procCalls.contains(&procKey) {
+                self.reportWarning(FlowCode::UnusedProcedure, format!("Procedure {} is never called", procName), procLine);
+            }
+        }
+    }
+
+    //Warns about a variable that is never read, saying if it is at least assigned
+    fn reportUnread(&mut self, varName: &String, varLine: usize, written: bool) {
+        if written {
+            self.reportWarning(FlowCode::UnusedVariable, format!("Variable {} is assigned but never read", varName.clone()), varLine);
+        } else {
+            self.reportWarning(FlowCode::UnusedVariable, format!("Variable {} is never used", varName.clone()), varLine);
+        }
+    }
+
+    //Finds every global variable in the program, including the ones declared inside procedures
+    fn collectGlobals(&mut self, header: &Stmt) {
+        if let Stmt::Block(instrs, lineNum) = header {
+            for instr in instrs {
+                match instr {
+                    //Variables in the program header are globals too
+                    Stmt::VarDecl(varName, varType, varLine) | Stmt::GlobVarDecl(varName, varType, varLine) => {
+                        self.globals.insert(varName.clone(), varLine.clone());
+                    }
+                    _ => {}
+                }
+            }
+            for instr in instrs {
+                if let Stmt::ProcDecl(retType, procName, params, procHeader, procBody, procLine) = instr {
+                    self.collectProcGlobals(procHeader);
+                }
+            }
+        }
+    }
+
+    //Finds the global variables declared in a procedure header and the ones nested inside it
+    fn collectProcGlobals(&mut self, header: &Stmt) {
+        if let Stmt::Block(instrs, lineNum) = header {
+            for instr in instrs {
+                match instr {
+                    Stmt::GlobVarDecl(varName, varType, varLine) => {
+                        self.globals.insert(varName.clone(), varLine.clone());
+                    }
+                    Stmt::ProcDecl(retType, procName, params, procHeader, procBody, procLine) => {
+                        self.collectProcGlobals(procHeader);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    //Finds every name a procedure in the header might assign, as a plain assignment or as an argument to any call
+    //This only has to be generous, a name in here is just never warned about in the program body
+    fn collectProcAssigned(header: &Stmt, assigned: &mut HashSet<String>) {
+        if let Stmt::Block(instrs, lineNum) = header {
+            for instr in instrs {
+                if let Stmt::ProcDecl(retType, procName, params, procHeader, procBody, procLine) = instr {
+                    Self::collectProcAssigned(procHeader, assigned);
+                    Self::collectAssigned(procBody, assigned);
+                }
+            }
+        }
+    }
+
+    //Finds every name assigned in a statement, or given to a call where it could be an out argument
+    fn collectAssigned(stmt: &Stmt, assigned: &mut HashSet<String>) {
+        match stmt {
+            Stmt::Assign(Expr::VarRef(varName), value, lineNum) | Stmt::Assign(Expr::ArrayRef(varName, _), value, lineNum) => {
+                assigned.insert(varName.clone());
+                Self::collectArgNames(value, assigned);
+            }
+            Stmt::Assign(_, value, lineNum) | Stmt::Return(value, lineNum) | Stmt::Expr(value, lineNum) => {
+                Self::collectArgNames(value, assigned);
+            }
+            Stmt::Call(procName, args, lineNum) => {
+                Self::collectArgNames(&Expr::ProcRef(procName.clone(), args.clone()), assigned);
+            }
+            Stmt::Block(stmts, lineNum) => {
+                for instr in stmts {
+                    Self::collectAssigned(instr, assigned);
+                }
+            }
+            Stmt::If(cond, body, elsifArms, elseBody, lineNum) => {
+                Self::collectArgNames(cond, assigned);
+                Self::collectAssigned(body, assigned);
+                for (elsifCond, elsifBody) in elsifArms {
+                    Self::collectArgNames(elsifCond, assigned);
+                    Self::collectAssigned(elsifBody, assigned);
+                }
+                if let Some(elseStmt) = elseBody {
+                    Self::collectAssigned(elseStmt, assigned);
+                }
+            }
+            Stmt::Case(value, arms, others, lineNum) => {
+                Self::collectArgNames(value, assigned);
+                for (armValue, armBody) in arms {
+                    Self::collectAssigned(armBody, assigned);
+                }
+                if let Some(othersBody) = others {
+                    Self::collectAssigned(othersBody, assigned);
+                }
+            }
+            Stmt::For(assignment, cond, body, lineNum) => {
+                Self::collectAssigned(assignment, assigned);
+                Self::collectArgNames(cond, assigned);
+                Self::collectAssigned(body, assigned);
+            }
+            Stmt::While(cond, body, lineNum) => {
+                Self::collectArgNames(cond, assigned);
+                Self::collectAssigned(body, assigned);
+            }
+            _ => {}
+        }
+    }
+
+    //Finds the variables given directly as arguments to the calls in an expression
+    fn collectArgNames(expr: &Expr, assigned: &mut HashSet<String>) {
+        match expr {
+            Expr::ProcRef(procName, Some(args)) => {
+                for arg in args {
+                    match arg {
+                        Expr::VarRef(varName) | Expr::ArrayRef(varName, _) => {
+                            assigned.insert(varName.clone());
+                        }
+                        _ => {}
+                    }
+                    Self::collectArgNames(arg, assigned);
+                }
+            }
+            Expr::ArrayRef(arrName, index) => Self::collectArgNames(index, assigned),
+            Expr::ArthOp(op1, op, op2) | Expr::RelOp(op1, op, op2) | Expr::LogOp(op1, op, op2) => {
+                Self::collectArgNames(op1, assigned);
+                Self::collectArgNames(op2, assigned);
+            }
+            Expr::NotOp(operand) => Self::collectArgNames(operand, assigned),
+            _ => {}
+        }
+    }
+
+    //Gets the procedures declared in a header, by name, with the key used to tell them apart and how each of their parameters is passed
+    fn headerProcs(header: &Stmt) -> HashMap<String, (String, Vec<ParamMode>)> {
+        let mut procs: HashMap<String, (String, Vec<ParamMode>)> = HashMap::new();
+        if let Stmt::Block(instrs, lineNum) = header {
+            for instr in instrs {
+                if let Stmt::ProcDecl(retType, procName, params, procHeader, procBody, procLine) = instr {
+                    let mut modes: Vec<ParamMode> = Vec::new();
+                    if let Stmt::Block(paramStmts, paramLine) = &**params {
+                        for paramStmt in paramStmts {
+                            if let Stmt::ParamDecl(paramName, paramType, paramMode, paramLine) = paramStmt {
+                                modes.push(paramMode.clone());
+                            }
+                        }
+                    }
+                    procs.insert(procName.clone(), (format!("{}:{}", procName, procLine), modes));
+                }
+            }
+        }
+        return procs;
+    }
+
+    //Finds the procedure a call refers to, checking the innermost scope first
+    fn lookupProc(&self, procName: &String) -> Option<(String, Vec<ParamMode>)> {
+        for procScope in self.procScopes.iter().rev() {
+            if let Some(procInfo) = procScope.get(procName) {
+                return Some(procInfo.clone());
+            }
+        }
+        return None;
+    }
+
+    //Follows each procedure declared in a header
+    fn followHeaderData(&mut self, header: &Stmt) {
+        if let Stmt::Block(instrs, lineNum) = header {
+            for instr in instrs {
+                if let Stmt::ProcDecl(retType, procName, params, procHeader, procBody, procLine) = instr {
+                    self.followProcData(procName, *procLine, params, procHeader, procBody);
+                }
+            }
+        }
+    }
+
+    //Follows the values through one procedure, then warns about its locals and parameters that are never used
+    fn followProcData(&mut self, procName: &String, procLine: usize, params: &Stmt, header: &Stmt, body: &Stmt) {
+        let procKey = format!("{}:{}", procName, procLine);
+        self.declaredProcs.push((procName.clone(), procLine, procKey.clone()));
+
+        //The procedures nested inside are followed first, each with its own variables
+        self.procStack.push(procKey);
+        self.procScopes.push(Self::headerProcs(header));
+        self.followHeaderData(header);
+
+        //In and in out parameters come in with a value, out parameters have to be assigned before they are read
+        let mut usage = VarUsage::default();
+        let mut assigned: HashSet<String> = HashSet::new();
+        if let Stmt::Block(paramStmts, paramLine) = params {
+            for paramStmt in paramStmts {
+                if let Stmt::ParamDecl(paramName, paramType, paramMode, paramLine) = paramStmt {
+                    usage.locals.insert(paramName.clone(), paramLine.clone());
+                    usage.params.push(paramName.clone());
+                    if *paramMode == ParamMode::Out {
+                        if !matches!(paramType, VarType::Array(..)) {
+                            usage.tracked.insert(paramName.clone());
+                        }
+                    } else {
+                        assigned.insert(paramName.clone());
+                    }
+                }
+            }
+        }
+        //Local variables start out unassigned, the zero they are given is never meant to be read
+        if let Stmt::Block(instrs, lineNum) = header {
+            for instr in instrs {
+                if let Stmt::VarDecl(varName, varType, varLine) = instr {
+                    usage.locals.insert(varName.clone(), varLine.clone());
+                    usage.params.retain(|paramName| paramName != varName);
+                    if !matches!(varType, VarType::Array(..)) {
+                        usage.tracked.insert(varName.clone());
+                    }
+                }
+            }
+        }
+
+        self.usage = usage;
+        let outerBreaks = std::mem::take(&mut self.breakStates);
+        self.followData(body, Some(assigned));
+        self.breakStates = outerBreaks;
+        let usage = std::mem::take(&mut self.usage);
+
+        //Parameters are warned about in order, then locals in the order they are declared
+        for paramName in usage.params.iter() {
+            if !usage.reads.contains(paramName) && !usage.writes.contains(paramName) {
+                let paramLine = usage.locals.get(paramName).cloned().unwrap_or(procLine);
+                self.reportWarning(FlowCode::UnusedParameter, format!("Parameter {} of procedure {} is never used", paramName.clone(), procName.clone()), paramLine);
+            }
+        }
+        let mut localList: Vec<(String, usize)> = usage.locals.iter().filter(|(varName, varLine)| !usage.params.contains(varName)).map(|(varName, varLine)| (varName.clone(), *varLine)).collect();
+        localList.sort_by_key(|(varName, varLine)| (*varLine, varName.clone()));
+        for (varName, varLine) in localList {
+            if !usage.reads.contains(&varName) {
+                self.reportUnread(&varName, varLine, usage.writes.contains(&varName));
+            }
+        }
+
+        self.procScopes.pop();
+        self.procStack.pop();
+    }
+
+    //Records a read of a variable, warning if it can be reached before anything is assigned to it
+    fn readVar(&mut self, varName: &String, assigned: &Option<HashSet<String>>) {
+        if self.usage.locals.contains_key(varName) {
+            self.usage.reads.insert(varName.clone());
+        } else if self.globals.contains_key(varName) {
+            self.globalReads.insert(varName.clone());
+        }
+        if let Some(assignedSet) = assigned {
+            if self.usage.tracked.contains(varName) && !assignedSet.contains(varName) && !self.usage.warned.contains(varName) {
+                self.usage.warned.insert(varName.clone());
+                self.reportWarning(FlowCode::ReadBeforeAssign, format!("Variable {} may be read before it is assigned", varName.clone()), self.curLine);
+            }
+        }
+    }
+
+    //Records an assignment to a variable, from here on it has a value
+    fn writeVar(&mut self, varName: &String, assigned: &mut Option<HashSet<String>>) {
+        if self.usage.locals.contains_key(varName) {
+            self.usage.writes.insert(varName.clone());
+        } else if self.globals.contains_key(varName) {
+            self.globalWrites.insert(varName.clone());
+        }
+        if let Some(assignedSet) = assigned {
+            assignedSet.insert(varName.clone());
+        }
+    }
+
+    //Follows the reads in an expression
+    //Out and in out arguments of the calls in it are assigned by the call
+    fn readExpr(&mut self, expr: &Expr, assigned: &mut Option<HashSet<String>>) {
+        match expr {
+            Expr::VarRef(varName) => {
+                self.readVar(varName, assigned);
+            }
+            Expr::ArrayRef(arrName, index) => {
+                self.readExpr(index, assigned);
+                self.readVar(arrName, assigned);
+            }
+            Expr::ProcRef(procName, args) => {
+                self.followCall(procName, args, assigned);
+            }
+            Expr::ArthOp(op1, op, op2) | Expr::RelOp(op1, op, op2) | Expr::LogOp(op1, op, op2) => {
+                self.readExpr(op1, assigned);
+                self.readExpr(op2, assigned);
+            }
+            Expr::NotOp(operand) => {
+                self.readExpr(operand, assigned);
+            }
+            Expr::IntLiteral(..) | Expr::FloatLiteral(..) | Expr::StringLiteral(..) | Expr::BoolLiteral(..) | Expr::IntArrayLiteral(..) => {}
+        }
+    }
+
+    //Follows a call, in arguments are read, out arguments are assigned and in out arguments are both
+    fn followCall(&mut self, procName: &String, args: &Option<Vec<Expr>>, assigned: &mut Option<HashSet<String>>) {
+        //The built in functions are not declared anywhere and take everything in
+        let (procKey, modes) = match self.lookupProc(procName) {
+            Some((procKey, modes)) => (Some(procKey), modes),
+            None => (None, Vec::new()),
+        };
+        if let Some(procKey) = procKey {
+            if !self.procStack.contains(&procKey) {
+                self.procCalls.insert(procKey);
+            }
+        }
+
+        let mut outArgs: Vec<String> = Vec::new();
+        for (argInd, arg) in args.clone().unwrap_or_default().iter().enumerate() {
+            let mode = modes.get(argInd).cloned().unwrap_or(ParamMode::In);
+            match (&mode, arg) {
+                (ParamMode::Out, Expr::VarRef(varName)) => {
+                    outArgs.push(varName.clone());
+                }
+                (ParamMode::Out, Expr::ArrayRef(arrName, index)) => {
+                    self.readExpr(index, assigned);
+                    outArgs.push(arrName.clone());
+                }
+                (ParamMode::InOut, Expr::VarRef(varName)) | (ParamMode::InOut, Expr::ArrayRef(varName, _)) => {
+                    self.readExpr(arg, assigned);
+                    outArgs.push(varName.clone());
+                }
+                _ => {
+                    self.readExpr(arg, assigned);
+                }
+            }
+        }
+        for varName in outArgs {
+            self.writeVar(&varName, assigned);
+        }
+    }
+
+    //Follows the values through a statement, given the variables assigned on every path to it
+    //Returns the variables assigned on every path out of it, or None if it never finishes, like a return
+    fn followData(&mut self, stmt: &Stmt, mut assigned: Option<HashSet<String>>) -> Option<HashSet<String>> {
+        match stmt {
+            Stmt::Block(stmts, lineNum) => {
+                for instr in stmts {
+                    assigned = self.followData(instr, assigned);
+                }
+                return assigned;
+            }
+            Stmt::Assign(target, value, lineNum) => {
+                self.curLine = lineNum.clone();
+                self.readExpr(value, &mut assigned);
+                match target {
+                    Expr::VarRef(varName) => {
+                        self.writeVar(varName, &mut assigned);
+                    }
+                    //Assigning one element still reads the index
+                    Expr::ArrayRef(arrName, index) => {
+                        self.readExpr(index, &mut assigned);
+                        self.writeVar(arrName, &mut assigned);
+                    }
+                    _ => {}
+                }
+                return assigned;
+            }
+            Stmt::Expr(expr, lineNum) => {
+                self.curLine = lineNum.clone();
+                self.readExpr(expr, &mut assigned);
+                return assigned;
+            }
+            Stmt::Call(procName, args, lineNum) => {
+                self.curLine = lineNum.clone();
+                self.followCall(procName, args, &mut assigned);
+                return assigned;
+            }
+            Stmt::Return(retVal, lineNum) => {
+                self.curLine = lineNum.clone();
+                if *retVal != Expr::VarRef(String::new()) {
+                    self.readExpr(retVal, &mut assigned);
+                }
+                return None;
+            }
+            Stmt::Break(lineNum) => {
+                if let Some(breakState) = self.breakStates.pop() {
+                    self.breakStates.push(Self::meet(breakState, assigned));
+                }
+                return None;
+            }
+            Stmt::Continue(lineNum) => {
+                return None;
+            }
+            //Each branch starts with what was assigned before the if, only what every branch assigns is kept after it
+            Stmt::If(cond, body, elsifArms, elseBody, lineNum) => {
+                self.curLine = lineNum.clone();
+                self.readExpr(cond, &mut assigned);
+                let mut after = self.followData(body, assigned.clone());
+                for (elsifCond, elsifBody) in elsifArms {
+                    self.curLine = elsifBody.lineNum();
+                    self.readExpr(elsifCond, &mut assigned);
+                    let elsifAfter = self.followData(elsifBody, assigned.clone());
+                    after = Self::meet(after, elsifAfter);
+                }
+                match elseBody {
+                    Some(elseStmt) => {
+                        let elseAfter = self.followData(elseStmt, assigned.clone());
+                        after = Self::meet(after, elseAfter);
+                    }
+                    None => {
+                        after = Self::meet(after, assigned);
+                    }
+                }
+                return after;
+            }
+            Stmt::Case(value, arms, others, lineNum) => {
+                self.curLine = lineNum.clone();
+                self.readExpr(value, &mut assigned);
+                let mut after: Option<HashSet<String>> = None;
+                for (armValue, armBody) in arms {
+                    let armAfter = self.followData(armBody, assigned.clone());
+                    after = Self::meet(after, armAfter);
+                }
+                match others {
+                    Some(othersBody) => {
+                        let othersAfter = self.followData(othersBody, assigned.clone());
+                        after = Self::meet(after, othersAfter);
+                    }
+                    None => {
+                        after = Self::meet(after, assigned);
+                    }
+                }
+                return after;
+            }
+            //The body of a loop might not run at all, so nothing it assigns counts after the loop
+            //unless the condition is always true, then the loop is only left by a break
+            Stmt::For(assignment, cond, body, lineNum) => {
+                assigned = self.followData(assignment, assigned);
+                self.curLine = lineNum.clone();
+                self.readExpr(cond, &mut assigned);
+                return self.followLoop(cond, body, assigned);
+            }
+            Stmt::While(cond, body, lineNum) => {
+                self.curLine = lineNum.clone();
+                self.readExpr(cond, &mut assigned);
+                return self.followLoop(cond, body, assigned);
+            }
+            //Declarations are handled when the procedure is set up, and nothing else changes what is assigned
+            Stmt::StringLiteral(..) | Stmt::VarDecl(..) | Stmt::GlobVarDecl(..) | Stmt::ParamDecl(..) | Stmt::Error(..) |
+            Stmt::Program(..) | Stmt::ProcDecl(..) | Stmt::ProcForward(..) => {
+                return assigned;
+            }
+        }
+    }
+
+    //Follows the body of a loop and works out what is assigned once it is left
+    fn followLoop(&mut self, cond: &Expr, body: &Stmt, assigned: Option<HashSet<String>>) -> Option<HashSet<String>> {
+        self.breakStates.push(None);
+        self.followData(body, assigned.clone());
+        let breakState = self.breakStates.pop().unwrap_or(None);
+        if Self::alwaysTrue(cond) {
+            return breakState;
+        }
+        return Self::meet(assigned, breakState);
+    }
+}
+
+
+
+
+
+///////////////////////// TESTS /////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::lexer::Lexer;
+    use crate::models::parser::Parser;
+
+    //Parses the source and follows it, giving back whether it passed and the diagnostics
+    fn flow(source: &str) -> (bool, Reporting) {
+        let mut lexer = Lexer::fromSource("test.src", source);
+        lexer.scanThrough();
+        let mut parser = Parser::new(&mut lexer);
+        let ast = match parser.startParse() {
+            Ok((parseReports, Some(ast))) if !parseReports.status => ast,
+            _ => panic!("test program failed to parse"),
+        };
+        let mut checker = FlowChecker::new();
+        let valid = checker.checkProgram(&ast);
+        return (valid, checker.reports.clone());
+    }
+
+    //Gets the line and message of every diagnostic with the given code
+    fn found(reports: &Reporting, code: &str) -> Vec<(usize, String)> {
+        return reports.diagnostics.iter()
+            .filter(|diag| diag.code == code)
+            .map(|diag| (diag.span.as_ref().map(|span| span.line).unwrap_or(0), diag.message.clone()))
+            .collect();
+    }
+
+    //Gets the lines of the diagnostics with the given code
+    fn lines(reports: &Reporting, code: FlowCode) -> Vec<usize> {
+        return found(reports, code.code()).into_iter().map(|(line, message)| line).collect();
+    }
+
+    #[test]
+    fn returnsOnEveryPath() {
+        let source = "program good is
+    procedure sign : integer (variable n : integer)
+    begin
+        if (n < 0) then
+            return -1;
+        elsif (n == 0) then
+            return 0;
+        else
+            return 1;
+        end if;
+    end procedure;
+
+    procedure pick : integer (variable n : integer)
+    begin
+        case (n) is
+            when 1 then
+                return 10;
+            when others then
+                return 20;
+        end case;
+    end procedure;
+
+    procedure spin : integer (variable n : integer)
+    begin
+        while (true)
+            n := n + 1;
+            if (n > 5) then
+                return n;
+            end if;
+        end while;
+    end procedure;
+
+    procedure nothing (variable n : integer)
+    begin
+        if (n > 0) then
+            return;
+        end if;
+    end procedure;
+begin
+    putinteger(sign(1) + pick(1) + spin(1));
+    nothing(1);
+end program.
+";
+        let (valid, reports) = flow(source);
+        assert!(valid, "{:?}", reports.errors);
+        assert!(found(&reports, FlowCode::MissingReturn.code()).is_empty());
+    }
+
+    #[test]
+    fn missingReturn() {
+        let source = "program bad is
+    procedure noElse : integer (variable n : integer)
+    begin
+        if (n < 0) then
+            return -1;
+        elsif (n == 0) then
+            return 0;
+        end if;
+    end procedure;
+
+    procedure noOthers : integer (variable n : integer)
+    begin
+        case (n) is
+            when 1 then
+                return 10;
+        end case;
+    end procedure;
+
+    procedure loopBreak : integer (variable n : integer)
+    begin
+        while (true)
+            if (n > 5) then
+                break;
+            end if;
+            return n;
+        end while;
+    end procedure;
+
+    procedure loopCond : integer (variable n : integer)
+    begin
+        while (n < 5)
+            return n;
+        end while;
+    end procedure;
+
+    procedure empty : bool (variable n : integer)
+    begin
+    end procedure;
+begin
+    putinteger(noElse(1) + noOthers(1) + loopBreak(1) + loopCond(1));
+    putbool(empty(1));
+end program.
+";
+        let (valid, reports) = flow(source);
+        assert!(!valid);
+        let errors = found(&reports, FlowCode::MissingReturn.code());
+        let lines: Vec<usize> = errors.iter().map(|(line, message)| *line).collect();
+        assert_eq!(lines, vec![2, 11, 19, 29, 36]);
+        assert_eq!(errors[0].1, "Procedure noelse can reach the end of its body without returning a value of type Int");
+        assert!(errors[4].1.starts_with("Procedure empty"));
+    }
+
+    #[test]
+    fn unreachableStatements() {
+        let source = "program dead is
+    variable r : integer;
+
+    procedure early : integer (variable n : integer)
+    begin
+        return n * 2;
+        putinteger(n);
+        putinteger(n);
+    end procedure;
+begin
+    r := 0;
+    while (r < 3)
+        r := r + 1;
+        if (r == 2) then
+            continue;
+            r := 5;
+        end if;
+        break;
+        r := 7;
+    end while;
+    putinteger(early(r));
+    return;
+    putinteger(99);
+end program.
+";
+        let (valid, reports) = flow(source);
+        assert!(valid, "{:?}", reports.errors);
+
+        //Only the first statement after the one that stops control is warned about
+        assert_eq!(lines(&reports, FlowCode::Unreachable), vec![7, 16, 19, 23]);
+        assert!(reports.warnings.iter().any(|warning| warning.contains("control never gets past line 6")), "{:?}", reports.warnings);
+    }
+
+    #[test]
+    fn infiniteLoopMakesRestUnreachable() {
+        let source = "program spin is
+    variable r : integer;
+begin
+    r := 0;
+    while (true)
+        r := r + 1;
+    end while;
+    putinteger(r);
+end program.
+";
+        let (valid, reports) = flow(source);
+        assert!(valid);
+        assert_eq!(lines(&reports, FlowCode::Unreachable), vec![8]);
+    }
+
+    #[test]
+    fn codesAreDistinct() {
+        let kinds = [FlowCode::MissingReturn, FlowCode::Internal, FlowCode::Unreachable, FlowCode::ReadBeforeAssign, FlowCode::UnusedVariable, FlowCode::UnusedParameter, FlowCode::UnusedProcedure];
+        let codes: HashSet<&str> = kinds.iter().map(|kind| kind.code()).collect();
+        assert_eq!(codes.len(), kinds.len());
+    }
+
+    #[test]
+    fn readBeforeAssign() {
+        let source = "program reads is
+    variable a : integer;
+    variable b : integer;
+
+    procedure fill (variable x : out integer, variable y : integer)
+        variable t : integer;
+    begin
+        putinteger(t);
+        putinteger(t);
+        putinteger(x);
+        x := y;
+    end procedure;
+begin
+    b := a + 1;
+    fill(b, 2);
+    putinteger(b);
+end program.
+";
+        let (valid, reports) = flow(source);
+        assert!(valid);
+        //Each variable is only warned about once, in parameters come in with a value
+        let warnings = found(&reports, FlowCode::ReadBeforeAssign.code());
+        assert_eq!(warnings, vec![
+            (8, "Variable t may be read before it is assigned".to_string()),
+            (10, "Variable x may be read before it is assigned".to_string()),
+            (14, "Variable a may be read before it is assigned".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn unusedDeclarations() {
+        let source = "program unused is
+    variable a : integer;
+    variable never : integer;
+    variable onlySet : integer;
+
+    procedure used (variable x : integer, variable y : integer)
+        variable local : integer;
+        variable localSet : bool;
+    begin
+        localSet := true;
+        putinteger(x);
+    end procedure;
+
+    procedure forgotten : integer (variable n : integer)
+    begin
+        return forgotten(n);
+    end procedure;
+begin
+    onlySet := 1;
+    a := 2;
+    used(a, a);
+end program.
+";
+        let (valid, reports) = flow(source);
+        assert!(valid);
+        assert_eq!(found(&reports, FlowCode::UnusedParameter.code()), vec![(6, "Parameter y of procedure used is never used".to_string())]);
+        assert_eq!(found(&reports, FlowCode::UnusedVariable.code()), vec![
+            (7, "Variable local is never used".to_string()),
+            (8, "Variable localset is assigned but never read".to_string()),
+            (3, "Variable never is never used".to_string()),
+            (4, "Variable onlyset is assigned but never read".to_string()),
+        ]);
+        //Calling itself does not count as a use
+        assert_eq!(found(&reports, FlowCode::UnusedProcedure.code()), vec![(14, "Procedure forgotten is never called".to_string())]);
+        assert!(lines(&reports, FlowCode::ReadBeforeAssign).is_empty());
+    }
+
+    #[test]
+    fn assignedOnEveryPath() {
+        let source = "program joins is
+    variable n : integer;
+
+    procedure branches : integer (variable k : integer)
+        variable a : integer;
+        variable b : integer;
+        variable c : integer;
+        variable d : integer;
+    begin
+        if (k > 0) then
+            a := 1;
+        elsif (k < 0) then
+            a := 2;
+        else
+            a := 3;
+        end if;
+
+        case (k) is
+            when 1 then
+                b := 1;
+            when others then
+                b := 2;
+        end case;
+
+        if (k > 5) then
+            return 0;
+        else
+            c := 1;
+        end if;
+
+        while (true)
+            d := k;
+            break;
+        end while;
+        return a + b + c + d;
+    end procedure;
+begin
+    n := branches(1);
+    putinteger(n);
+end program.
+";
+        let (valid, reports) = flow(source);
+        assert!(valid);
+        assert!(reports.warnings.is_empty(), "{:?}", reports.warnings);
+    }
+
+    #[test]
+    fn assignedOnSomePaths() {
+        let source = "program joins is
+    variable n : integer;
+
+    procedure branches : integer (variable k : integer)
+        variable a : integer;
+        variable b : integer;
+        variable c : integer;
+    begin
+        if (k > 0) then
+            a := 1;
+        end if;
+
+        case (k) is
+            when 1 then
+                b := 1;
+        end case;
+
+        while (k < 3)
+            c := k;
+            k := k + 1;
+        end while;
+        return a + b + c;
+    end procedure;
+begin
+    n := branches(1);
+    putinteger(n);
+end program.
+";
+        let (valid, reports) = flow(source);
+        assert!(valid);
+        let warned: Vec<String> = found(&reports, FlowCode::ReadBeforeAssign.code()).into_iter().map(|(line, message)| message).collect();
+        assert_eq!(warned, vec![
+            "Variable a may be read before it is assigned",
+            "Variable b may be read before it is assigned",
+            "Variable c may be read before it is assigned",
+        ]);
+    }
+
+    #[test]
+    fn globalsAndBareReturns() {
+        let source = "program globs is
+    variable shared : integer;
+    variable counter : integer;
+
+    procedure setShared()
+    begin
+        shared := 5;
+    end procedure;
+
+    procedure bump()
+        global variable total : integer;
+    begin
+        total := counter;
+        putinteger(total);
+        return;
+    end procedure;
+begin
+    setShared();
+    putinteger(shared);
+    counter := 1;
+    bump();
+    return;
+end program.
+";
+        let (valid, reports) = flow(source);
+        assert!(valid);
+        //A global assigned by a procedure could have been assigned by any call, and a bare return reads nothing
+        assert!(reports.warnings.is_empty(), "{:?}", reports.warnings);
+    }
+}
+
+///////////////////////// /TESTS /////////////////////////
@@ -30,7 +30,7 @@ use {
     crate::tokenTypeEnum,
     crate::models::reporting::*,
     std::io::prelude::*,
-
+    std::fmt::Write as fmtWrite,
 };
 
 ///////////////////////// /Setup /////////////////////////
@@ -52,11 +52,28 @@ pub struct Lexer {
 
 //This is where all of the methods of the lexer struct are defined
 impl Lexer{
-    //The default constructor for the lexer
-    pub fn new(fileName: &str) -> Lexer {
+    //The default constructor for the lexer, reads the source from the file at the given path
+    //A file that can not be read is reported as a diagnostic instead of a lexer
+    pub fn new(fileName: &str) -> Result<Lexer, Reporting> {
+        match std::fs::read_to_string(fileName) {
+            Ok(fileContents) => {
+                return Ok(Lexer::fromSource(fileName, &fileContents));
+            }
+            Err(err) => {
+                let mut reports = Reporting::new();
+                let errMsg = format!("Could not read {}: {}", fileName, err);
+                reports.report(Diagnostic::error("L005", errMsg).withSpan(Span::new(fileName.to_string(), 0, 0, 0)));
+                return Err(reports);
+            }
+        }
+    }
+
+    //Creates a lexer over source that is already in memory
+    //The file name is only used to label diagnostics
+    pub fn fromSource(fileName: &str, source: &str) -> Lexer {
         // println!("Beginning creation of Lexer");
         //Creates the inFile structure
-        let newFile = inFile::new(fileName);
+        let newFile = inFile::new(fileName, source);
         // println!("Lexer created successfully");
         //Creates the token table
         let mut symTable = tokenTable::new();
@@ -73,90 +90,81 @@ impl Lexer{
     }
     
     //The main function of the lexer
-    //Returns one Token
+    //Returns one Token, marked with the columns it starts and ends at
     fn scan(&mut self) -> Token{
+        let mut newToken = self.scanToken();
+        newToken.colNum = self.inputFile.tokenStartCol;
+        newToken.colEnd = self.inputFile.getColumn();
+        return newToken;
+    }
+
+    //Finds the next token in the file
+    fn scanToken(&mut self) -> Token{
         //Gets the next character
         let mut currChar = self.inputFile.getChar();
 
-        //Looks for the filler characters and removes them
-        while let Some(c) = currChar {            
-            //Defines the filler characters, finds them, passes them or marks whatever is necessary
-            if c == '\n' || c == '\t' || c == '\r' || c == ' ' || c == '\u{0009}' {
-                // println!("Filler character found: '{}'", c);
-                
-                if c == '\n' {
-                    self.inputFile.incLineCnt();
+        //Skips the filler characters and comments until the start of the next token
+        loop {
+            //Defines the filler characters and passes them, the source keeps track of the lines
+            while let Some(c) = currChar {
+                if c == '\n' || c == '\t' || c == '\r' || c == ' ' || c == '\u{0009}' {
+                    currChar = self.inputFile.getChar();
+                } else {
+                    break;
                 }
-                currChar = self.inputFile.getChar();
-            } else {
-                break;
             }
-        }
 
-        //This section parses and ignores comments by looking for the comment identifiers and then skipping until finding the end
-        if let Some('/') = currChar {
-            currChar = self.inputFile.getChar();
-            let Some(c) = currChar else { todo!() };
-            //Two /s in a row, single line comment
-            if c == '/' {
-                // println!("Comment line found");
-                while let Some(c) = currChar {
-                    if c == '\n' {
-                        self.inputFile.incLineCnt();
-                        break;
-                    } else {
-                        currChar = self.inputFile.getChar();
-                    }
-                }
-            } 
-            //This identifies a multiline comment
-            else if c == '*' {
-                // println!("multiline comment");
-                let mut nested: usize = 1;
-                // println!("Comment line found");
-                //Finds the end of the multiline comment
-                while let Some(c) = currChar {
-                    //If a nested multiline comment is found, increases scope
-                    if c == '/' {
-                        // println!("scope +1 nested");
-                        currChar = self.inputFile.getChar();
-                        let Some(ch) = currChar else { todo!() };
-                        if ch == '*' {
-                            nested += 1;
-                            currChar = self.inputFile.getChar();
-                        }
-                    } else if c == '*' {
-                        currChar = self.inputFile.getChar();
-                        let Some(ch) = currChar else { todo!() };
-                        if ch == '/' {
-                            nested -= 1;
-                            if nested == 0 {
-                                currChar = self.inputFile.getChar();
-                                // println!("End of nested comment");
+            //Looks ahead after a / to see if it starts a comment, a lone / is left for the divide below
+            if currChar == Some('/') {
+                match self.inputFile.peekChar() {
+                    //Two /s in a row, single line comment
+                    Some('/') => {
+                        while let Some(c) = self.inputFile.getChar() {
+                            if c == '\n' {
                                 break;
-                            } else {
-                                currChar = self.inputFile.getChar();
-                                // println!("Not end of internal nested comment");
                             }
                         }
-                    } else if c == '\n' {
-                        self.inputFile.incLineCnt();
                         currChar = self.inputFile.getChar();
-
-                          
-                    } else {
+                        continue;
+                    }
+                    //This identifies a multiline comment, these can be nested
+                    Some('*') => {
+                        self.inputFile.markTokenStart();
+                        let startLine = self.inputFile.lineCnt;
+                        let startCol = self.inputFile.tokenStartCol;
+                        self.inputFile.getChar();
+                        let mut nested: usize = 1;
+                        while nested > 0 {
+                            match self.inputFile.getChar() {
+                                Some('/') if self.inputFile.peekChar() == Some('*') => {
+                                    self.inputFile.getChar();
+                                    nested += 1;
+                                }
+                                Some('*') if self.inputFile.peekChar() == Some('/') => {
+                                    self.inputFile.getChar();
+                                    nested -= 1;
+                                }
+                                Some(_) => {}
+                                None => {
+                                    let errMsg = format!("Comment starting on line {} is never closed", startLine);
+                                    let errSpan = Span::new(self.inputFile.fileName.clone(), startLine, startCol, startCol + 2);
+                                    self.reports.report(Diagnostic::error("L003", errMsg).withSpan(errSpan));
+                                    break;
+                                }
+                            }
+                        }
                         currChar = self.inputFile.getChar();
+                        continue;
                     }
+                    _ => {}
                 }
-            } 
-            //This is for if the / has a space after it meaning its a divide not a comment
-            else if c == ' ' {
-                let tokenString = '/';
-                let newToken = Token::new(crate::tokenTypeEnum::DIVIDE,tokenString.to_string(), self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
-                return newToken;
             }
+            break;
         }
 
+        //Remembers where the token starts for error reporting
+        self.inputFile.markTokenStart();
+
         //A switch case to handle all of the different characters the lexer could find
         let mut tokenString: String = "".to_string();
         match currChar {
@@ -173,46 +181,55 @@ impl Lexer{
                     }
                 }
                 self.inputFile.unGetChar();
-                tokenString = tokenString.to_ascii_lowercase();
-                let mut newToken = self.symTab.hashLook(tokenString, self.inputFile.lineCnt.to_string());
-                newToken.lineNum = self.inputFile.lineCnt.to_string();
+                tokenString.make_ascii_lowercase();
+                let newToken = self.symTab.hashLook(tokenString, self.inputFile.lineCnt);
                 return newToken;
             }
 
-            //If the character is a number, iterates through until end of number, if a . is found, it creates a float token
-            Some(ch) if ch.is_ascii_digit() => {
-                let mut tokType: tokenTypeEnum = tokenTypeEnum::INT;
-                //Iterates through until it stops finding numbers
-                while let Some(numC) = currChar {
-                    if numC.is_ascii_digit() {
+            //If the character is a number (or a . right before a digit), reads the whole literal then checks its form
+            //This covers ints like 42, 0xFF, 0b1010 and 1_000, and floats like 3.14, .5, 1e-3 and 2.5E10
+            Some(ch) if ch.is_ascii_digit() || (ch == '.' && self.inputFile.peekChar().map_or(false, |c| c.is_ascii_digit())) => {
+                tokenString.push(ch);
+                let isPrefixed = ch == '0' && matches!(self.inputFile.peekChar(), Some('x' | 'X' | 'b' | 'B'));
+
+                //Takes every character that could be part of the literal, a sign is only taken straight after an exponent
+                while let Some(numC) = self.inputFile.peekChar() {
+                    let afterExponent = !isPrefixed && (tokenString.ends_with('e') || tokenString.ends_with('E'));
+                    if numC.is_ascii_alphanumeric() || numC == '_' || numC == '.' || ((numC == '+' || numC == '-') && afterExponent) {
                         tokenString.push(numC);
-                        currChar = self.inputFile.getChar();
-                    //If the number has a decimal, meaning its a float
-                    } else if numC == '.' {
-                        tokenString.push('.');
-                        tokType = tokenTypeEnum::FLOAT;
-                        currChar = self.inputFile.getChar();
+                        self.inputFile.getChar();
                     } else {
                         break;
                     }
                 }
-                self.inputFile.unGetChar();
-                let newToken: Token = Token::new(tokType,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::CONSTANT);
+
+                //Reports a malformed literal here, the token is still made so the rest of the file can be scanned
+                let tokType: tokenTypeEnum;
+                match Lexer::checkNumber(&tokenString) {
+                    Ok(numType) => {
+                        tokType = numType;
+                    }
+                    Err(reason) => {
+                        let errMsg = format!("Malformed number literal '{}' on line {}: {}", tokenString, self.inputFile.lineCnt, reason);
+                        let errSpan = Span::new(self.inputFile.fileName.clone(), self.inputFile.lineCnt, self.inputFile.tokenStartCol, self.inputFile.getColumn());
+                        self.reports.report(Diagnostic::error("L004", errMsg).withSpan(errSpan));
+                        tokType = if tokenString.contains('.') { tokenTypeEnum::FLOAT } else { tokenTypeEnum::INT };
+                    }
+                }
+                let newToken: Token = Token::new(tokType,tokenString, self.inputFile.lineCnt, tokenGroup::CONSTANT);
                 return newToken;
             }
 
             //If the character is a <, looks if it is a less or less equals
             Some('<') => {
-                let mut nextNextChar = self.inputFile.getChar();
                 tokenString.push('<');
-                let Some(nextC) = nextNextChar else { todo!() };
-                if nextC == '=' {
+                if self.inputFile.peekChar() == Some('=') {
+                    self.inputFile.getChar();
                     tokenString.push('=');
-                    let newToken = Token::new(crate::tokenTypeEnum::LESS_EQUALS,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                    let newToken = Token::new(crate::tokenTypeEnum::LESS_EQUALS,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                     return newToken;
                 } else {
-                    self.inputFile.unGetChar();
-                    let newToken = Token::new(crate::tokenTypeEnum::LESS,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                    let newToken = Token::new(crate::tokenTypeEnum::LESS,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                     return newToken;
                 }
             }
@@ -220,15 +237,13 @@ impl Lexer{
             //If the character is a >, checks if > or >=
             Some('>') => {
                 tokenString.push('>');
-                let mut nextNextChar = self.inputFile.getChar();
-                let Some(nextC) = nextNextChar else { todo!() };
-                if nextC == '=' {
+                if self.inputFile.peekChar() == Some('=') {
+                    self.inputFile.getChar();
                     tokenString.push('=');
-                    let newToken = Token::new(crate::tokenTypeEnum::GREATER_EQUALS,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                    let newToken = Token::new(crate::tokenTypeEnum::GREATER_EQUALS,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                     return newToken;
                 } else {
-                    self.inputFile.unGetChar();
-                    let newToken = Token::new(crate::tokenTypeEnum::GREATER,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                    let newToken = Token::new(crate::tokenTypeEnum::GREATER,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                     return newToken;
                 }
             }
@@ -236,21 +251,18 @@ impl Lexer{
             //If the character is a =, checks if a = or a ==
             Some('=') => {
                 tokenString.push('=');
-                let mut nextNextChar = self.inputFile.getChar();
-                let Some(nextC) = nextNextChar else { todo!() };
-                if nextC == '=' {
+                let nextChar = self.inputFile.peekChar();
+                if nextChar == Some('=') {
+                    self.inputFile.getChar();
                     tokenString.push('=');
-                    let newToken = Token::new(crate::tokenTypeEnum::CHECK_EQUALS,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                    let newToken = Token::new(crate::tokenTypeEnum::CHECK_EQUALS,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                     return newToken;
-                } else if nextC == ' ' {
-                    self.inputFile.unGetChar();
-                    let newToken = Token::new(crate::tokenTypeEnum::SET_EQUALS,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                } else if nextChar == Some(' ') {
+                    let newToken = Token::new(crate::tokenTypeEnum::SET_EQUALS,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                     return newToken;
                 } else {
                     //If there is an unknown next character, creates an error token, this will be turned into an error given to the user in the second pass
-                    println!("ERROR");
-                    self.inputFile.unGetChar();
-                    let newToken = Token::new(crate::tokenTypeEnum::ERROR,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OTHER);
+                    let newToken = Token::new(crate::tokenTypeEnum::ERROR,tokenString, self.inputFile.lineCnt, tokenGroup::OTHER);
                     return newToken;
                 }
             }
@@ -258,15 +270,13 @@ impl Lexer{
             //If the character is a !, checks if != or just !, throws error if a !
             Some('!') => {
                 tokenString.push('!');
-                let mut nextNextChar = self.inputFile.getChar();
-                let Some(nextC) = nextNextChar else { todo!() };
-                if nextC == '=' {
+                if self.inputFile.peekChar() == Some('=') {
+                    self.inputFile.getChar();
                     tokenString.push('=');
-                    let newToken = Token::new(crate::tokenTypeEnum::NOT_EQUALS,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                    let newToken = Token::new(crate::tokenTypeEnum::NOT_EQUALS,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                     return newToken;
                 } else {
-                    self.inputFile.unGetChar();
-                    let newToken = Token::new(crate::tokenTypeEnum::ERROR,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OTHER);
+                    let newToken = Token::new(crate::tokenTypeEnum::ERROR,tokenString, self.inputFile.lineCnt, tokenGroup::OTHER);
                     return newToken;
                 }
             }
@@ -274,22 +284,20 @@ impl Lexer{
             //If the character is a ;
             Some(';') => {
                 tokenString.push(';');
-                let newToken = Token::new(crate::tokenTypeEnum::SEMICOLON,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::SYMBOL);
+                let newToken = Token::new(crate::tokenTypeEnum::SEMICOLON,tokenString, self.inputFile.lineCnt, tokenGroup::SYMBOL);
                 return newToken;
             }
 
             //If the character is a :, checks if a := or just a :
             Some(':') => {
                 tokenString.push(':');
-                let mut nextNextChar = self.inputFile.getChar();
-                let Some(nextC) = nextNextChar else { todo!() };
-                if nextC == '=' {
+                if self.inputFile.peekChar() == Some('=') {
+                    self.inputFile.getChar();
                     tokenString.push('=');
-                    let newToken = Token::new(crate::tokenTypeEnum::SET_EQUALS,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                    let newToken = Token::new(crate::tokenTypeEnum::SET_EQUALS,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                     return newToken;
                 } else {
-                    self.inputFile.unGetChar();
-                    let newToken = Token::new(crate::tokenTypeEnum::COLON,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                    let newToken = Token::new(crate::tokenTypeEnum::COLON,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                     return newToken;
                 }
             }
@@ -297,85 +305,82 @@ impl Lexer{
             //If the character is a [
             Some('[') => {
                 tokenString.push('[');
-                let newToken = Token::new(crate::tokenTypeEnum::L_BRACKET,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::SYMBOL);
+                let newToken = Token::new(crate::tokenTypeEnum::L_BRACKET,tokenString, self.inputFile.lineCnt, tokenGroup::SYMBOL);
                 return newToken;
             }
 
             //If the character is a ]
             Some(']') => {
                 tokenString.push(']');
-                let newToken = Token::new(crate::tokenTypeEnum::R_BRACKET,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::SYMBOL);
+                let newToken = Token::new(crate::tokenTypeEnum::R_BRACKET,tokenString, self.inputFile.lineCnt, tokenGroup::SYMBOL);
                 return newToken;
             }
 
             //If the character is a (
             Some('(') => {
                 tokenString.push('(');
-                let newToken = Token::new(crate::tokenTypeEnum::L_PAREN,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::SYMBOL);
+                let newToken = Token::new(crate::tokenTypeEnum::L_PAREN,tokenString, self.inputFile.lineCnt, tokenGroup::SYMBOL);
                 return newToken;
             }
 
             //If the character is a )
             Some(')') => {
                 tokenString.push(')');
-                let newToken = Token::new(crate::tokenTypeEnum::R_PAREN,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::SYMBOL);
+                let newToken = Token::new(crate::tokenTypeEnum::R_PAREN,tokenString, self.inputFile.lineCnt, tokenGroup::SYMBOL);
                 return newToken;
             }
 
             //If the character is a +
             Some('+') => {
                 tokenString.push('+');
-                let newToken = Token::new(crate::tokenTypeEnum::PLUS,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                let newToken = Token::new(crate::tokenTypeEnum::PLUS,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                 return newToken;
             }
 
             //If the character is a -
             Some('-') => {
                 tokenString.push('-');
-                let mut nextNextChar = self.inputFile.getChar();
-                let Some(nextC) = nextNextChar else { todo!() };
-                self.inputFile.unGetChar();
-                let newToken = Token::new(crate::tokenTypeEnum::MINUS,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                let newToken = Token::new(crate::tokenTypeEnum::MINUS,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                 return newToken;
             }
 
 
             Some('*') => {
                 tokenString.push('*');
-                let newToken = Token::new(crate::tokenTypeEnum::MULTIPLY,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                let newToken = Token::new(crate::tokenTypeEnum::MULTIPLY,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                 return newToken;
             }
 
             Some(',') => {
                 tokenString.push(',');
-                let newToken = Token::new(crate::tokenTypeEnum::COMMA,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::SYMBOL);
+                let newToken = Token::new(crate::tokenTypeEnum::COMMA,tokenString, self.inputFile.lineCnt, tokenGroup::SYMBOL);
                 return newToken;
             }
 
             Some('/') => {
                 tokenString.push('/');
-                let newToken = Token::new(crate::tokenTypeEnum::DIVIDE,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                let newToken = Token::new(crate::tokenTypeEnum::DIVIDE,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                 return newToken;
             }
 
             //If the character is a .
             Some('.') => {
                 tokenString.push('.');
-                let newToken = Token::new(crate::tokenTypeEnum::PERIOD,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::SYMBOL);
+                let newToken = Token::new(crate::tokenTypeEnum::PERIOD,tokenString, self.inputFile.lineCnt, tokenGroup::SYMBOL);
                 return newToken;
             }
 
             //If the character is a &
             Some('&') => {
                 tokenString.push('&');
-                let newToken = Token::new(crate::tokenTypeEnum::AND,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                let newToken = Token::new(crate::tokenTypeEnum::AND,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                 return newToken;
             }
 
             //If the character is a |
             Some('|') => {
                 tokenString.push('|');
-                let newToken = Token::new(crate::tokenTypeEnum::OR,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OPERATOR);
+                let newToken = Token::new(crate::tokenTypeEnum::OR,tokenString, self.inputFile.lineCnt, tokenGroup::OPERATOR);
                 return newToken;
             }
 
@@ -392,21 +397,8 @@ impl Lexer{
                     currChar = self.inputFile.getChar();
 
                 }
-                while tokenString.len() < 64 {
-                    tokenString.push(' ');
-                }
-                tokenString.push('\0');
-                let mut newToken = self.symTab.hashLook(tokenString, self.inputFile.lineCnt.to_string());
-                newToken.lineNum = self.inputFile.lineCnt.to_string();
-                if newToken.tt != tokenTypeEnum::STRING {
-                    newToken.tt = tokenTypeEnum::STRING;
-                }
-                return newToken;
-            }
-            
-            //Somehow a \n makes it here, just runs it through another scan to get the next thing
-            Some('\n') => {
-                let newToken = self.scan();
+                //Not looked up in the symbol table, the text of a string can match a keyword
+                let newToken = Token::new(tokenTypeEnum::STRING, tokenString, self.inputFile.lineCnt, tokenGroup::CONSTANT);
                 return newToken;
             }
             
@@ -414,19 +406,87 @@ impl Lexer{
             Some(c) => {
                 // println!("This character is unaccounted for '{}'", c);
                 tokenString.push(c);
-                let newToken = Token::new(crate::tokenTypeEnum::UNACCOUNTED,tokenString, self.inputFile.lineCnt.to_string(), tokenGroup::OTHER);
+                let newToken = Token::new(crate::tokenTypeEnum::UNACCOUNTED,tokenString, self.inputFile.lineCnt, tokenGroup::OTHER);
                 return newToken;
             }
             
             //This is if there is no character, meaning we have found the end of the file
             None => {
                 // println!("This character is a None aka EOF");
-                let newToken = Token::new(crate::tokenTypeEnum::EOF, "EOF".to_string(), self.inputFile.lineCnt.to_string(), tokenGroup::SYMBOL);
+                let newToken = Token::new(crate::tokenTypeEnum::EOF, "EOF".to_string(), self.inputFile.lineCnt, tokenGroup::SYMBOL);
                 return newToken;
             }
         }
     }
     
+    //Checks the form of a number literal, returning if it is an int or a float, or what is wrong with it
+    //Ints can be decimal, hex (0x) or binary (0b), floats can start with a . and have an exponent, and _ can separate digits
+    //The value itself is checked when the parser turns the token into a constant
+    fn checkNumber(text: &str) -> Result<tokenTypeEnum, String> {
+        let lowerText = text.to_ascii_lowercase();
+        if let Some(digits) = lowerText.strip_prefix("0x") {
+            Lexer::checkDigits(digits, 16, "hexadecimal")?;
+            return Ok(tokenTypeEnum::INT);
+        }
+        if let Some(digits) = lowerText.strip_prefix("0b") {
+            Lexer::checkDigits(digits, 2, "binary")?;
+            return Ok(tokenTypeEnum::INT);
+        }
+
+        //Splits a decimal literal into the whole part, the fraction and the exponent
+        let (mantissa, exponent) = match lowerText.find('e') {
+            Some(expInd) => (&lowerText[..expInd], Some(&lowerText[expInd + 1..])),
+            None => (lowerText.as_str(), None),
+        };
+        let (whole, fraction) = match mantissa.find('.') {
+            Some(dotInd) => (&mantissa[..dotInd], Some(&mantissa[dotInd + 1..])),
+            None => (mantissa, None),
+        };
+
+        if fraction.map_or(false, |fraction| fraction.contains('.')) {
+            return Err("a number can only have one decimal point".to_string());
+        }
+        if whole.is_empty() && fraction.map_or(true, |fraction| fraction.is_empty()) {
+            return Err("the number has no digits".to_string());
+        }
+        if !whole.is_empty() {
+            Lexer::checkDigits(whole, 10, "decimal")?;
+        }
+        if let Some(fraction) = fraction {
+            if !fraction.is_empty() {
+                Lexer::checkDigits(fraction, 10, "decimal")?;
+            }
+        }
+        if let Some(exponent) = exponent {
+            let expDigits = exponent.strip_prefix(|c| c == '+' || c == '-').unwrap_or(exponent);
+            Lexer::checkDigits(expDigits, 10, "exponent")?;
+        }
+
+        if fraction.is_some() || exponent.is_some() {
+            return Ok(tokenTypeEnum::FLOAT);
+        } else {
+            return Ok(tokenTypeEnum::INT);
+        }
+    }
+
+    //Checks that a run of digits only has digits of the given base, with any _ between two digits
+    fn checkDigits(digits: &str, radix: u32, kind: &str) -> Result<(), String> {
+        if digits.is_empty() {
+            return Err(format!("the {} part has no digits", kind));
+        }
+        let digitChars: Vec<char> = digits.chars().collect();
+        for (i, c) in digitChars.iter().enumerate() {
+            if *c == '_' {
+                if i == 0 || i + 1 == digitChars.len() || digitChars[i - 1] == '_' || digitChars[i + 1] == '_' {
+                    return Err("'_' can only be used between digits".to_string());
+                }
+            } else if !c.is_digit(radix) {
+                return Err(format!("'{}' is not a valid {} digit", c, kind));
+            }
+        }
+        return Ok(());
+    }
+
     //Prints all of the tokens, used for debugging
     fn printTokenList(&mut self){
         for token in &self.tokenList {
@@ -434,102 +494,129 @@ impl Lexer{
         }
     }
 
+    //Returns the token list in the same format as printTokenList, used for emitting the tokens to a file
+    pub fn tokenListString(&self) -> String {
+        let mut tokString = String::new();
+        for token in &self.tokenList {
+            let _ = writeln!(tokString, "< \"{}\" , {}, {} >", token.tokenString, token.tt, token.lineNum);
+        }
+        return tokString;
+    }
+
     //A second pass through the tokenString created by the lexer, used to find errors and combine certain groups of tokens into one token
+    //The tokens are moved out of the first pass list rather than copied, since every token in the program goes through here
     fn secondPass(&mut self) -> Vec<Token>{
-        let mut newTokList = Vec::new(); 
-        let mut i: usize = 0;
-        while i < self.tokenList.len() {
-            let token = &self.tokenList[i];
+        let mut newTokList = Vec::with_capacity(self.tokenList.len());
+        let mut tokens = std::mem::take(&mut self.tokenList).into_iter().peekable();
+
+        //The type and group of the last token taken from the first pass, used to tell negative numbers from minus operators
+        let mut prevType = tokenTypeEnum::EOF;
+        let mut prevGroup = tokenGroup::SYMBOL;
+
+        while let Some(token) = tokens.next() {
+            let mut lastType = token.tt.clone();
+            let mut lastGroup = token.tg.clone();
             match token.tt {
                 //Turns an end token into an end program, procedure, or other structure
                 tokenTypeEnum::END => {
-                    // println!("End found");
-                    let nextToken = &self.tokenList[i+1];
-                    if nextToken.tt == tokenTypeEnum::PROGRAM {
-                        // println!("Combining end and program");
-                        let newToken = Token::new(crate::tokenTypeEnum::END_PROGRAM,"END_PROGRAM".to_string(), nextToken.lineNum.to_string(), tokenGroup::OTHER);
-                        newTokList.push(newToken.clone());
-                        i = i + 1;
-                    } else if nextToken.tt == tokenTypeEnum::PROCEDURE {
-                        // println!("Combining end and procedure");
-                        let newToken = Token::new(crate::tokenTypeEnum::END_PROCEDURE,"END_PROCEDURE".to_string(), nextToken.lineNum.to_string(), tokenGroup::OTHER);
-                        newTokList.push(newToken.clone());
-                        i = i + 1;
-                    } else if nextToken.tt == tokenTypeEnum::IF {
-                        // println!("Combining end and if");
-                        let newToken = Token::new(crate::tokenTypeEnum::END_IF,"END_IF".to_string(), nextToken.lineNum.to_string(), tokenGroup::OTHER);
-                        newTokList.push(newToken.clone());
-                        i = i + 1;
-                    } else if nextToken.tt == tokenTypeEnum::FOR {
-                        // println!("Combining end and if");
-                        let newToken = Token::new(crate::tokenTypeEnum::END_FOR,"END_FOR".to_string(), nextToken.lineNum.to_string(), tokenGroup::OTHER);
-                        newTokList.push(newToken.clone());
-                        i = i + 1;
-                    } else {
-                        // println!("other end with type: {}", nextToken.tt);
-                        newTokList.push(token.clone());
-
+                    let endType = match tokens.peek().map(|nextToken| nextToken.tt.clone()) {
+                        Some(tokenTypeEnum::PROGRAM) => Some((tokenTypeEnum::END_PROGRAM, "END_PROGRAM")),
+                        Some(tokenTypeEnum::PROCEDURE) => Some((tokenTypeEnum::END_PROCEDURE, "END_PROCEDURE")),
+                        Some(tokenTypeEnum::IF) => Some((tokenTypeEnum::END_IF, "END_IF")),
+                        Some(tokenTypeEnum::FOR) => Some((tokenTypeEnum::END_FOR, "END_FOR")),
+                        Some(tokenTypeEnum::WHILE) => Some((tokenTypeEnum::END_WHILE, "END_WHILE")),
+                        Some(tokenTypeEnum::CASE) => Some((tokenTypeEnum::END_CASE, "END_CASE")),
+                        _ => None,
+                    };
+                    //Only takes the next token if it is one that ends a structure
+                    match endType.and_then(|endInfo| tokens.next().map(|nextToken| (endInfo, nextToken))) {
+                        Some(((endType, endString), nextToken)) => {
+                            let mut newToken = Token::new(endType, endString.to_string(), nextToken.lineNum, tokenGroup::OTHER);
+                            newToken.colNum = token.colNum;
+                            newToken.colEnd = nextToken.colEnd;
+                            lastType = nextToken.tt;
+                            lastGroup = nextToken.tg;
+                            newTokList.push(newToken);
+                        }
+                        _ => {
+                            newTokList.push(token);
+                        }
+                    }
+                }
+                //Turns else if into an elsif, so the arm shares the end if of the if it belongs to
+                //Only when both are on the same line, an if on the next line starts a nested if with its own end if
+                tokenTypeEnum::ELSE => {
+                    match tokens.next_if(|nextToken| (nextToken.tt == tokenTypeEnum::IF) && (nextToken.lineNum == token.lineNum)) {
+                        Some(nextToken) => {
+                            let mut newToken = Token::new(tokenTypeEnum::ELSIF, "ELSIF".to_string(), nextToken.lineNum, tokenGroup::KEYWORD);
+                            newToken.colNum = token.colNum;
+                            newToken.colEnd = nextToken.colEnd;
+                            lastType = tokenTypeEnum::ELSIF;
+                            lastGroup = tokenGroup::KEYWORD;
+                            newTokList.push(newToken);
+                        }
+                        None => {
+                            newTokList.push(token);
+                        }
                     }
                 }
-                //Turns identifiers into procedure calls if that's what it is
+                //Turns identifiers into procedure calls if that's what it is, the ( is dropped
                 tokenTypeEnum::IDENTIFIER => {
-                    let nextToken = &self.tokenList[i+1];
-                    if nextToken.tt == tokenTypeEnum::L_PAREN {
-                        // println!("Combining end and if");
-                        let newToken = Token::new(crate::tokenTypeEnum::PROCEDURE_CALL, token.tokenString.clone(), nextToken.lineNum.to_string(), tokenGroup::SYMBOL);
-                        newTokList.push(newToken.clone());
-                        i = i + 1;
-                    } else {
-                        // println!("other end with type: {}", nextToken.tt);
-                        newTokList.push(token.clone());
-
+                    match tokens.next_if(|nextToken| nextToken.tt == tokenTypeEnum::L_PAREN) {
+                        Some(nextToken) => {
+                            let mut newToken = Token::new(tokenTypeEnum::PROCEDURE_CALL, token.tokenString, nextToken.lineNum, tokenGroup::SYMBOL);
+                            newToken.colNum = token.colNum;
+                            newToken.colEnd = token.colEnd;
+                            lastType = nextToken.tt;
+                            lastGroup = nextToken.tg;
+                            newTokList.push(newToken);
+                        }
+                        None => {
+                            newTokList.push(token);
+                        }
                     }
                 }
-                //The unaccounted token, throws an error
+                //The unaccounted token, throws an error and leaves it out
                 tokenTypeEnum::UNACCOUNTED => {
-                    if(token.tokenString == "\r"){
-                        // println!("Skipping unaccounted");
-                        // println!("Unaccounted: {}", token.tokenString);
-                        let nextToken = &self.tokenList[i+1];
-                        // println!("Next token: {}", nextToken.tokenString);
-                        newTokList.push(nextToken.clone());
-                        i = i + 1;
-                    } else {
-                        let errMsg = format!("Unaccounted token '{}' found on line {}", token.tokenString.clone(), token.lineNum.clone());
-                        self.reports.reportError(errMsg.clone());
-                        // println!("Skipping unaccounted");
-                        // println!("Unaccounted: {}", token.tokenString);
-                        let nextToken = &self.tokenList[i+1];
-                        // println!("Next token: {}", nextToken.tokenString);
-                        newTokList.push(nextToken.clone());
-                        i = i + 1;
-                    }
-                    
+                    let errMsg = format!("Unaccounted token '{}' found on line {}", token.tokenString.clone(), token.lineNum.clone());
+                    let errSpan = token.span(&self.inputFile.fileName);
+                    self.reports.report(Diagnostic::error("L001", errMsg).withSpan(errSpan));
+                }
+                //Operators that were started but not finished, like a lone = or !
+                tokenTypeEnum::ERROR => {
+                    let errMsg = format!("Invalid operator '{}' found on line {}", token.tokenString.clone(), token.lineNum.clone());
+                    let errSpan = token.span(&self.inputFile.fileName);
+                    self.reports.report(Diagnostic::error("L002", errMsg).withSpan(errSpan).withNote("did you mean '==', '!=' or ':='?".to_string()));
                 }
                 //Checks - tokens for if they are neg numbers or minus operators
                 tokenTypeEnum::MINUS => {
-                    let nextToken = &self.tokenList[i+1];
-                    let prevToken = &self.tokenList[i-1];
-                    //Defines a negative number
-                    if ((nextToken.tg == tokenGroup::VARIABLE) || (nextToken.tg == tokenGroup::CONSTANT)) && ((prevToken.tg == tokenGroup::OPERATOR) || (prevToken.tt == tokenTypeEnum::SET_EQUALS)) {
-                        // println!("Found a neg number");
-                        let newString = format!("-{}", nextToken.tokenString.clone());
-                        let newToken = Token::new(nextToken.tt.clone(), newString, nextToken.lineNum.to_string(), tokenGroup::CONSTANT);
-                        newTokList.push(newToken.clone());
-                        i = i + 1;
-                    //This is just a minus operator
-                    } else {
-                        newTokList.push(token.clone());
+                    //Defines a negative number, a - right after an operator or := and before a value
+                    //or a - before a number at the start of an index, argument, bracketed expression, return value or case arm, like a[-1]
+                    let afterOperator = (prevGroup == tokenGroup::OPERATOR) || (prevType == tokenTypeEnum::SET_EQUALS);
+                    let startOfExpr = matches!(prevType, tokenTypeEnum::L_BRACKET | tokenTypeEnum::L_PAREN | tokenTypeEnum::COMMA | tokenTypeEnum::RETURN | tokenTypeEnum::WHEN);
+                    match tokens.next_if(|nextToken| (afterOperator && ((nextToken.tg == tokenGroup::VARIABLE) || (nextToken.tg == tokenGroup::CONSTANT))) || (startOfExpr && (nextToken.tg == tokenGroup::CONSTANT))) {
+                        Some(nextToken) => {
+                            let newString = format!("-{}", nextToken.tokenString);
+                            let mut newToken = Token::new(nextToken.tt.clone(), newString, nextToken.lineNum, tokenGroup::CONSTANT);
+                            newToken.colNum = token.colNum;
+                            newToken.colEnd = nextToken.colEnd;
+                            lastType = nextToken.tt;
+                            lastGroup = nextToken.tg;
+                            newTokList.push(newToken);
+                        }
+                        //This is just a minus operator
+                        None => {
+                            newTokList.push(token);
+                        }
                     }
                 }
                 //All the other tokens, nothing done just passed through
                 _ => {
-                    // Handle other token types
-                    newTokList.push(token.clone());
+                    newTokList.push(token);
                 }
             }
-            //Increments the i
-            i = i + 1;
+            prevType = lastType;
+            prevGroup = lastGroup;
         }
         //Returns the second pass of the token string
         return newTokList;
@@ -539,15 +626,15 @@ impl Lexer{
     pub fn scanThrough(&mut self){
         // println!("\nBeginning scan:");
 
-        //Scans the first token and initializes the newToken variable
-        let mut newToken: Token = self.scan();
-        self.tokenList.push(newToken.clone());
-
         //Goes through the inputfile and calls scan() which returns each token until the EOF is reached
-        while newToken.tokenString != "EOF".to_string(){
-            newToken = self.scan();
-            self.tokenList.push(newToken.clone());
-        };
+        loop {
+            let newToken: Token = self.scan();
+            let foundEnd = newToken.tt == tokenTypeEnum::EOF;
+            self.tokenList.push(newToken);
+            if foundEnd {
+                break;
+            }
+        }
 
         //Runs the tokenString through a second pass, this will conclude the lexer
         let newTokList = self.secondPass();
@@ -557,62 +644,92 @@ impl Lexer{
 
 }
 
-//inFile Class, this is where the file to be compiled is loaded
+//inFile Class, the source being lexed
+//Holds the characters of the source with a cursor into them, and keeps track of the line and column the cursor is on
 pub struct inFile{
-    attatchFile: bool,
     pub fileName: String,
-    fileContents: String,
+    pub fileContents: String,
+    chars: Vec<char>,           //The characters of the source, indexed by the cursor
     lineCnt: usize,
+    lineStart: usize,           //The character index the current line starts at, used for columns
+    tokenStartCol: usize,       //The column the token currently being scanned started at
     pub numChars: usize,
     pub totalLines: usize,
-    pub file : BufReader <File>,
     pub currentCharIndex: usize,
 }
 impl inFile {
-    //Constructor, imports and opens the file
-    fn new(fileName: &str) -> inFile {
-        let mut newFile = BufReader::new(File::open(fileName).unwrap());
-        let fileContentsString = std::fs::read_to_string(fileName).expect("Unable to read file");
-        let numChars = fileContentsString.len();
-        // println!("Creating the inFile structure");
-        
+    //Constructor, takes the name of the file and its contents
+    fn new(fileName: &str, contents: &str) -> inFile {
+        let chars: Vec<char> = contents.chars().collect();
+        let numChars = chars.len();
+        let totalLines = contents.lines().count();
+
         inFile {
             fileName: fileName.to_string(),
-            attatchFile: false,
+            fileContents: contents.to_string(),
+            chars,
             lineCnt: 1,
+            lineStart: 0,
+            tokenStartCol: 1,
             currentCharIndex: 0,
-            totalLines: 0,
-            file: newFile,
-            fileContents: fileContentsString,
-            numChars: numChars,
+            totalLines,
+            numChars,
         }
-
     }
 
     //Prints the stats of the file (for debugging)
     fn printInfo(&self){
         println!("File Name: {}", self.fileName);
-        println!("Lines: {}", self.lineCnt);
+        println!("Lines: {}", self.totalLines);
     }
 
-    //Gets the next character in the file string
+    //Gets the next character and moves the cursor past it, moving to the next line after a newline
+    //The cursor still moves at the end of the source so that every getChar can be undone with unGetChar
     fn getChar(&mut self) -> Option<char> {
-        if let Some(current_char) = self.fileContents.chars().nth(self.currentCharIndex) {
-            self.currentCharIndex += 1;
-            Some(current_char)
-        } else {
-            None
+        let currentChar = self.chars.get(self.currentCharIndex).copied();
+        self.currentCharIndex += 1;
+        if currentChar == Some('\n') {
+            self.lineCnt += 1;
+            self.lineStart = self.currentCharIndex;
         }
+        return currentChar;
     }
-    
-    //"ungets" the next character by decrementing the current index. Used for looking ahead then going back
+
+    //Looks at the next character without moving the cursor
+    fn peekChar(&self) -> Option<char> {
+        return self.chars.get(self.currentCharIndex).copied();
+    }
+
+    //"ungets" the last character by moving the cursor back one. Used for looking ahead then going back
     fn unGetChar(&mut self) {
+        if self.currentCharIndex == 0 {
+            return;
+        }
         self.currentCharIndex -= 1;
+
+        //Going back over a newline puts the cursor back on the previous line
+        if self.chars.get(self.currentCharIndex) == Some(&'\n') {
+            self.lineCnt -= 1;
+            self.lineStart = match self.chars[..self.currentCharIndex].iter().rposition(|c| *c == '\n') {
+                Some(newline) => newline + 1,
+                None => 0,
+            };
+        }
     }
 
-    //A function to increment the current line
-    fn incLineCnt(&mut self){
-        self.lineCnt += 1;
+    //Gets the line the cursor is on, lines start at 1
+    pub fn getLine(&self) -> usize {
+        return self.lineCnt;
+    }
+
+    //Gets the column of the next character to be read, columns start at 1
+    pub fn getColumn(&self) -> usize {
+        return self.currentCharIndex.saturating_sub(self.lineStart) + 1;
+    }
+
+    //Marks the last character read as the start of a token
+    fn markTokenStart(&mut self){
+        self.tokenStartCol = self.currentCharIndex.saturating_sub(self.lineStart).max(1);
     }
 
 }
@@ -623,21 +740,30 @@ pub struct Token{
     pub tt: tokenTypeEnum,
     pub tokenString: String,
     pub tg: tokenGroup,
-    pub lineNum: String,
+    pub lineNum: usize,         //The line the token is on, 0 if it is not in the source
+    pub colNum: usize,          //The column the token starts at, 0 if the token was made up by the lexer
+    pub colEnd: usize,          //The column just past the end of the token
     //To be completed later when I understand
     //tm: tokenMark,
 }
 impl Token{
     //Init for the Token
-    pub fn new(iden: tokenTypeEnum, tokenString: String, line: String, group: tokenGroup) -> Token{
+    pub fn new(iden: tokenTypeEnum, tokenString: String, line: usize, group: tokenGroup) -> Token{
         Token {
             tt: iden,
             tokenString: tokenString,
             lineNum: line,
             tg: group,
+            colNum: 0,
+            colEnd: 0,
         }
     }
     
+    //Gets the place in the source file the token came from, used for diagnostics
+    pub fn span(&self, fileName: &str) -> Span {
+        Span::new(fileName.to_string(), self.lineNum, self.colNum, self.colEnd)
+    }
+
     //Used for setting the Token type
     fn setTokenType(&mut self, newType: tokenTypeEnum){
         self.tt = newType;
@@ -664,21 +790,31 @@ impl tokenTable{
         //List of all of the tokens that should be in the symbol table when initializes
         //This defines all of the keywords in the program and creates the tokens and organizes them for their intended purpose
         let keywords = vec![
-            ("if", Token::new(tokenTypeEnum::IF, "if".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("else", Token::new(tokenTypeEnum::ELSE, "else".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("procedure", Token::new(tokenTypeEnum::PROCEDURE, "procedure".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("is", Token::new(tokenTypeEnum::IS, "is".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("global", Token::new(tokenTypeEnum::GLOBAL, "global".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("variable", Token::new(tokenTypeEnum::VARIABLE, "variable".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("begin", Token::new(tokenTypeEnum::BEGIN, "begin".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("then", Token::new(tokenTypeEnum::THEN, "then".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("end", Token::new(tokenTypeEnum::END, "end".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("program", Token::new(tokenTypeEnum::PROGRAM, "program".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("return", Token::new(tokenTypeEnum::RETURN, "return".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("for", Token::new(tokenTypeEnum::FOR, "for".to_string(), "0".to_string(), tokenGroup::KEYWORD)),
-            ("not", Token::new(tokenTypeEnum::NOT, "not".to_string(), "0".to_string(), tokenGroup::OPERATOR)),
-            ("true", Token::new(tokenTypeEnum::TRUE, "true".to_string(), "0".to_string(), tokenGroup::CONSTANT)),
-            ("false", Token::new(tokenTypeEnum::FALSE, "false".to_string(), "0".to_string(), tokenGroup::CONSTANT)),
+            ("if", Token::new(tokenTypeEnum::IF, "if".to_string(), 0, tokenGroup::KEYWORD)),
+            ("else", Token::new(tokenTypeEnum::ELSE, "else".to_string(), 0, tokenGroup::KEYWORD)),
+            ("elsif", Token::new(tokenTypeEnum::ELSIF, "elsif".to_string(), 0, tokenGroup::KEYWORD)),
+            ("procedure", Token::new(tokenTypeEnum::PROCEDURE, "procedure".to_string(), 0, tokenGroup::KEYWORD)),
+            ("is", Token::new(tokenTypeEnum::IS, "is".to_string(), 0, tokenGroup::KEYWORD)),
+            ("global", Token::new(tokenTypeEnum::GLOBAL, "global".to_string(), 0, tokenGroup::KEYWORD)),
+            ("variable", Token::new(tokenTypeEnum::VARIABLE, "variable".to_string(), 0, tokenGroup::KEYWORD)),
+            ("begin", Token::new(tokenTypeEnum::BEGIN, "begin".to_string(), 0, tokenGroup::KEYWORD)),
+            ("then", Token::new(tokenTypeEnum::THEN, "then".to_string(), 0, tokenGroup::KEYWORD)),
+            ("end", Token::new(tokenTypeEnum::END, "end".to_string(), 0, tokenGroup::KEYWORD)),
+            ("program", Token::new(tokenTypeEnum::PROGRAM, "program".to_string(), 0, tokenGroup::KEYWORD)),
+            ("return", Token::new(tokenTypeEnum::RETURN, "return".to_string(), 0, tokenGroup::KEYWORD)),
+            ("for", Token::new(tokenTypeEnum::FOR, "for".to_string(), 0, tokenGroup::KEYWORD)),
+            ("while", Token::new(tokenTypeEnum::WHILE, "while".to_string(), 0, tokenGroup::KEYWORD)),
+            //exit is another name for break
+            ("break", Token::new(tokenTypeEnum::BREAK, "break".to_string(), 0, tokenGroup::KEYWORD)),
+            ("exit", Token::new(tokenTypeEnum::BREAK, "exit".to_string(), 0, tokenGroup::KEYWORD)),
+            ("continue", Token::new(tokenTypeEnum::CONTINUE, "continue".to_string(), 0, tokenGroup::KEYWORD)),
+            ("case", Token::new(tokenTypeEnum::CASE, "case".to_string(), 0, tokenGroup::KEYWORD)),
+            ("when", Token::new(tokenTypeEnum::WHEN, "when".to_string(), 0, tokenGroup::KEYWORD)),
+            ("others", Token::new(tokenTypeEnum::OTHERS, "others".to_string(), 0, tokenGroup::KEYWORD)),
+            ("forward", Token::new(tokenTypeEnum::FORWARD, "forward".to_string(), 0, tokenGroup::KEYWORD)),
+            ("not", Token::new(tokenTypeEnum::NOT, "not".to_string(), 0, tokenGroup::OPERATOR)),
+            ("true", Token::new(tokenTypeEnum::TRUE, "true".to_string(), 0, tokenGroup::CONSTANT)),
+            ("false", Token::new(tokenTypeEnum::FALSE, "false".to_string(), 0, tokenGroup::CONSTANT)),
         ];
 
         //Inserts all of the keywords into the table
@@ -699,14 +835,14 @@ impl tokenTable{
     }
     
     //Checks if a given word is in the hashtable. Used to check if a word is a keyword
-    //If not found, returns a new token with that string
-    fn hashLook(&mut self, mut lookupString: String, line: String) -> Token{
+    //Returns the keyword token on the given line, or a new identifier token with that string if not found
+    fn hashLook(&mut self, mut lookupString: String, line: usize) -> Token{
         if let Some(tokenResp) = self.tokTab.get(&lookupString){
-            return tokenResp.clone();
+            let mut keywordToken = tokenResp.clone();
+            keywordToken.lineNum = line;
+            return keywordToken;
         } else {
-            let newToken = Token::new(tokenTypeEnum::IDENTIFIER, lookupString, line.to_string(), tokenGroup::VARIABLE);
-            self.tokTab.insert(newToken.tokenString.clone(), newToken.clone());
-            return newToken;
+            return Token::new(tokenTypeEnum::IDENTIFIER, lookupString, line, tokenGroup::VARIABLE);
         }
     }
 
@@ -750,3 +886,270 @@ pub fn printTokList(tokList: &Vec<Token>){
 ///////////////////////// /LEXER SECTION /////////////////////////
 
 
+
+
+
+///////////////////////// TESTS /////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::parser::Expr;
+
+    //Scans the source and gets the tokens after the second pass
+    fn lex(source: &str) -> Lexer {
+        let mut lexer = Lexer::fromSource("test.src", source);
+        lexer.scanThrough();
+        return lexer;
+    }
+
+    #[test]
+    fn cursorGetAndPeek() {
+        let mut source = inFile::new("test.src", "ab");
+        assert_eq!(source.peekChar(), Some('a'));
+        assert_eq!(source.peekChar(), Some('a'));
+        assert_eq!(source.getColumn(), 1);
+        assert_eq!(source.getChar(), Some('a'));
+        assert_eq!(source.getColumn(), 2);
+        assert_eq!(source.peekChar(), Some('b'));
+        assert_eq!(source.getChar(), Some('b'));
+        assert_eq!(source.peekChar(), None);
+        assert_eq!(source.getChar(), None);
+    }
+
+    #[test]
+    fn cursorUnGet() {
+        let mut source = inFile::new("test.src", "xyz");
+        source.getChar();
+        source.getChar();
+        source.unGetChar();
+        assert_eq!(source.getColumn(), 2);
+        assert_eq!(source.getChar(), Some('y'));
+
+        //Ungetting at the start does nothing
+        let mut source = inFile::new("test.src", "xyz");
+        source.unGetChar();
+        assert_eq!(source.getColumn(), 1);
+        assert_eq!(source.getChar(), Some('x'));
+    }
+
+    #[test]
+    fn cursorUnGetAtEnd() {
+        //Reading past the end still moves the cursor, so the unget lands back on the last character
+        let mut source = inFile::new("test.src", "q");
+        assert_eq!(source.getChar(), Some('q'));
+        assert_eq!(source.getChar(), None);
+        source.unGetChar();
+        assert_eq!(source.peekChar(), None);
+        source.unGetChar();
+        assert_eq!(source.getChar(), Some('q'));
+    }
+
+    #[test]
+    fn cursorLinesAndColumns() {
+        let mut source = inFile::new("test.src", "ab\ncd\n\nef");
+        for _ in 0..3 {
+            source.getChar();
+        }
+        assert_eq!(source.getLine(), 2);
+        assert_eq!(source.getColumn(), 1);
+        source.getChar();
+        assert_eq!(source.getColumn(), 2);
+
+        //Blank lines still count
+        for _ in 0..3 {
+            source.getChar();
+        }
+        assert_eq!(source.getLine(), 4);
+        assert_eq!(source.getColumn(), 1);
+    }
+
+    #[test]
+    fn cursorUnGetAcrossNewline() {
+        let mut source = inFile::new("test.src", "abc\nd\nef");
+        for _ in 0..4 {
+            source.getChar();
+        }
+        assert_eq!(source.getLine(), 2);
+        assert_eq!(source.getColumn(), 1);
+
+        //Going back over the newline returns to the end of the line before
+        source.unGetChar();
+        assert_eq!(source.getLine(), 1);
+        assert_eq!(source.getColumn(), 4);
+        assert_eq!(source.getChar(), Some('\n'));
+        assert_eq!(source.getLine(), 2);
+
+        //The line start is found again when the previous line is not the first
+        for _ in 0..2 {
+            source.getChar();
+        }
+        assert_eq!(source.getLine(), 3);
+        source.unGetChar();
+        assert_eq!(source.getLine(), 2);
+        assert_eq!(source.getColumn(), 2);
+    }
+
+    #[test]
+    fn cursorCountsCharactersNotBytes() {
+        let mut source = inFile::new("test.src", "é\"ü\" x");
+        for _ in 0..5 {
+            source.getChar();
+        }
+        assert_eq!(source.getColumn(), 6);
+        assert_eq!(source.getChar(), Some('x'));
+    }
+
+    #[test]
+    fn tokenPositions() {
+        let lexer = lex("program p is\n  variable count : integer;\nbegin\n\tcount := 10;\nend program.");
+        let count: Vec<&Token> = lexer.tokenList.iter().filter(|token| token.tokenString == "count").collect();
+        assert_eq!(count.len(), 2);
+        assert_eq!((count[0].lineNum, count[0].colNum, count[0].colEnd), (2, 12, 17));
+        assert_eq!((count[1].lineNum, count[1].colNum, count[1].colEnd), (4, 2, 7));
+
+        let setEquals = lexer.tokenList.iter().find(|token| token.tt == tokenTypeEnum::SET_EQUALS).unwrap();
+        assert_eq!((setEquals.lineNum, setEquals.colNum, setEquals.colEnd), (4, 8, 10));
+        assert!(lexer.tokenList.last().unwrap().tt == tokenTypeEnum::EOF);
+    }
+
+    #[test]
+    fn lookaheadOperatorsAndComments() {
+        //Each of these reads a character past the token then puts it back
+        let lexer = lex("a<b <= c>=d/e // line\n/* block\n comment */ f");
+        let types: Vec<String> = lexer.tokenList.iter().map(|token| token.tt.to_string()).collect();
+        assert_eq!(types, vec![
+            "IDENTIFIER", "LESS", "IDENTIFIER", "LESS_EQUALS", "IDENTIFIER", "GREATER_EQUALS",
+            "IDENTIFIER", "DIVIDE", "IDENTIFIER", "IDENTIFIER", "EOF",
+        ]);
+        assert_eq!(lexer.tokenList[9].lineNum, 3);
+        assert!(!lexer.reports.status);
+    }
+
+    #[test]
+    fn largeSourceLexes() {
+        let source = "x := x + 1;\n".repeat(100_000);
+        let lexer = lex(&source);
+        assert_eq!(lexer.tokenList.len(), 6 * 100_000 + 1);
+        assert_eq!(lexer.tokenList[lexer.tokenList.len() - 2].lineNum, 100_000);
+    }
+
+    //Lexes a single literal and gets its type and the diagnostic codes reported for it
+    fn lexLiteral(literal: &str) -> (String, String, Vec<String>) {
+        let lexer = lex(literal);
+        let token = &lexer.tokenList[0];
+        let codes = lexer.reports.diagnostics.iter().map(|diag| diag.code.clone()).collect();
+        return (token.tt.to_string(), token.tokenString.clone(), codes);
+    }
+
+    //Gets the value the parser gives a literal, which is where the ranges are checked
+    fn literalValue(literal: &str) -> Result<Expr, String> {
+        let lexer = lex(literal);
+        return Expr::newCon(lexer.tokenList[0].clone());
+    }
+
+    #[test]
+    fn integerLiterals() {
+        for literal in ["0", "42", "0xFF", "0XfF", "0b1010", "0B1", "1_000_000", "0xDEAD_BEEF", "0b1111_0000"] {
+            let (tokType, text, codes) = lexLiteral(literal);
+            assert_eq!(tokType, "INT", "{}", literal);
+            assert_eq!(text, literal);
+            assert!(codes.is_empty(), "{}: {:?}", literal, codes);
+        }
+        assert_eq!(literalValue("0xFF"), Ok(Expr::IntLiteral(255)));
+        assert_eq!(literalValue("0b1010"), Ok(Expr::IntLiteral(10)));
+        assert_eq!(literalValue("1_000_000"), Ok(Expr::IntLiteral(1_000_000)));
+    }
+
+    #[test]
+    fn floatLiterals() {
+        let cases = [("2.75", 2.75), (".5", 0.5), ("1e-3", 1e-3), ("2.5E10", 2.5e10), ("1e+2", 100.0), ("7.", 7.0), ("1_000.000_5", 1000.0005), ("6E0", 6.0)];
+        for (literal, value) in cases {
+            let (tokType, text, codes) = lexLiteral(literal);
+            assert_eq!(tokType, "FLOAT", "{}", literal);
+            assert_eq!(text, literal);
+            assert!(codes.is_empty(), "{}: {:?}", literal, codes);
+            assert_eq!(literalValue(literal), Ok(Expr::FloatLiteral(value)), "{}", literal);
+        }
+    }
+
+    #[test]
+    fn malformedLiterals() {
+        let cases = [
+            ("1.2.3", "one decimal point"),
+            ("0x", "hexadecimal part has no digits"),
+            ("0xFG", "'g' is not a valid hexadecimal digit"),
+            ("0b102", "'2' is not a valid binary digit"),
+            ("1e", "exponent part has no digits"),
+            ("1e+", "exponent part has no digits"),
+            ("2.5e1.5", "'.' is not a valid exponent digit"),
+            ("1__000", "'_' can only be used between digits"),
+            ("1000_", "'_' can only be used between digits"),
+            ("1_.5", "'_' can only be used between digits"),
+            ("12abc", "'a' is not a valid decimal digit"),
+        ];
+        for (literal, reason) in cases {
+            let lexer = lex(literal);
+            assert_eq!(lexer.reports.diagnostics.len(), 1, "{}", literal);
+            let diagnostic = &lexer.reports.diagnostics[0];
+            assert_eq!(diagnostic.code, "L004", "{}", literal);
+            assert!(diagnostic.message.contains(reason), "{}: {}", literal, diagnostic.message);
+
+            //The whole literal is one token so scanning carries on after it
+            assert_eq!(lexer.tokenList[0].tokenString, literal);
+            assert_eq!(lexer.tokenList.len(), 2);
+        }
+    }
+
+    #[test]
+    fn numberEndsAtOperator() {
+        //A sign is only part of the literal right after an exponent
+        let lexer = lex("1-2 0x1e-3 1e-3");
+        let texts: Vec<String> = lexer.tokenList.iter().map(|token| token.tokenString.clone()).collect();
+        assert_eq!(texts, vec!["1", "-", "2", "0x1e", "-", "3", "1e-3", "EOF"]);
+        assert!(!lexer.reports.status);
+    }
+
+    #[test]
+    fn integerRange() {
+        assert_eq!(literalValue("2147483647"), Ok(Expr::IntLiteral(2147483647)));
+        assert_eq!(literalValue("0x7FFF_FFFF"), Ok(Expr::IntLiteral(i32::MAX as i64)));
+        for literal in ["2147483648", "0x80000000", "0b1_0000_0000_0000_0000_0000_0000_0000_0000", "99999999999999999999999"] {
+            let err = literalValue(literal).err().unwrap_or_default();
+            assert!(err.contains("out of range"), "{}: {}", literal, err);
+        }
+    }
+
+    #[test]
+    fn negativeIntegerRange() {
+        //The second pass folds a - before a number into the literal
+        let lexer = lex("x := -2147483648;");
+        assert_eq!(lexer.tokenList[2].tokenString, "-2147483648");
+        assert_eq!(Expr::newCon(lexer.tokenList[2].clone()), Ok(Expr::IntLiteral(i32::MIN as i64)));
+
+        let lexer = lex("x := -2147483649;");
+        let err = Expr::newCon(lexer.tokenList[2].clone()).err().unwrap_or_default();
+        assert!(err.contains("out of range"), "{}", err);
+    }
+
+    #[test]
+    fn elseIfOnOneLine() {
+        //An if on the line after an else starts a nested if rather than an elsif arm
+        let lexer = lex("else if (a) then\nelse\n    if (b) then");
+        let types: Vec<String> = lexer.tokenList.iter().map(|token| token.tt.to_string()).collect();
+        assert_eq!(types, vec![
+            "ELSIF", "L_PAREN", "IDENTIFIER", "R_PAREN", "THEN",
+            "ELSE", "IF", "L_PAREN", "IDENTIFIER", "R_PAREN", "THEN", "EOF",
+        ]);
+        assert_eq!((lexer.tokenList[0].lineNum, lexer.tokenList[0].colNum, lexer.tokenList[0].colEnd), (1, 1, 8));
+    }
+
+    #[test]
+    fn floatRange() {
+        assert_eq!(literalValue("3.4e38"), Ok(Expr::FloatLiteral(3.4e38)));
+        let err = literalValue("1e39").err().unwrap_or_default();
+        assert!(err.contains("out of range"), "{}", err);
+    }
+}
+
+///////////////////////// /TESTS /////////////////////////
@@ -1,5 +1,7 @@
 pub mod lexer;
 pub mod parser;
 pub mod typechecker;
+pub mod flow;
 pub mod reporting;
-pub mod compiler;
\ No newline at end of file
+pub mod compiler;
+pub mod pipeline;
@@ -30,7 +30,7 @@ use {
     utf8_chars::BufReadCharsExt,
     crate::tokenTypeEnum,
     crate::models::lexer::*,
-    crate::models::reporting::Reporting,
+    crate::models::reporting::{Reporting, Diagnostic},
     std::io::prelude::*,
 };
 
@@ -40,11 +40,44 @@ use {
 
 ///////////////////////// PARSER SECTION /////////////////////////
 
+//The kinds of problem the parser reports, each has its own diagnostic code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParseCode {
+    MissingSemicolon,   //A statement or declaration that does not end with a ;
+    Unclosed,           //A (, [ or block like an if or procedure that is never closed
+    UnexpectedToken,    //A token that can not start a statement, or is in the wrong place in one
+    InvalidExpr,        //An expression missing an operand or operator, or with a literal that does not fit its type
+    InvalidDecl,        //A variable declaration that is not in the form variable [name] : [type]
+    InvalidProcedure,   //A procedure header, parameter list or body that is not laid out right
+    InvalidStmt,        //A statement that is not laid out right, like an if, for, while or case missing part of its header
+    InvalidProgram,     //A program missing its header, begin or end program
+    Internal,           //The parser stopped making progress, which means a bug in the parser
+}
+
+impl ParseCode {
+    //The code shown in the diagnostic
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseCode::MissingSemicolon => "P001",
+            ParseCode::Unclosed => "P002",
+            ParseCode::UnexpectedToken => "P003",
+            ParseCode::InvalidExpr => "P004",
+            ParseCode::InvalidDecl => "P005",
+            ParseCode::InvalidProcedure => "P006",
+            ParseCode::InvalidStmt => "P007",
+            ParseCode::InvalidProgram => "P008",
+            ParseCode::Internal => "P009",
+        }
+    }
+}
+
 //This is the master struct for the parser
 pub struct Parser {
     pub tokenList: Vec<Token>,  //The list of tokens that is passed into it. This comes from the Lexer
     pub reports: Reporting,         //The reporting object, used to report warnings and errors
     pub scope: i32,                 //the scope
+    pub fileName: String,           //The name of the file being parsed, used for diagnostics
+    stmtToken: Token,               //The first token of the statement being parsed, errors point here when no better token is known
 }
 
 impl Parser{
@@ -52,13 +85,240 @@ impl Parser{
     pub fn new(lexer: &mut Lexer) -> Parser {
         let tokenList = lexer.tokenList.clone();
         let mut report: Reporting = Reporting::new();
+        let stmtToken = tokenList.first().cloned().unwrap_or(Token::new(tokenTypeEnum::EOF, "EOF".to_string(), 0, tokenGroup::SYMBOL));
         Parser { 
             tokenList,
             reports: report,
             scope: 0,
+            fileName: lexer.inputFile.fileName.clone(),
+            stmtToken,
         }
     }  
 
+    //Reports a syntax error at the start of the statement currently being parsed
+    fn reportError(&mut self, code: ParseCode, message: String) {
+        let errToken = self.stmtToken.clone();
+        self.reportErrorAt(code, message, &errToken);
+    }
+
+    //Reports a syntax error pointing at the given token
+    //Anything after the first line of the message is added as a note
+    fn reportErrorAt(&mut self, code: ParseCode, message: String, token: &Token) {
+        let mut lines = message.splitn(2, '\n');
+        let mainMsg = lines.next().unwrap_or("").trim().to_string();
+        let mut diagnostic = Diagnostic::error(code.code(), mainMsg).withSpan(token.span(&self.fileName));
+        if let Some(note) = lines.next() {
+            diagnostic = diagnostic.withNote(note.trim().to_string());
+        }
+        self.reports.report(diagnostic);
+    }
+
+    //Reports a general error for something that failed, but only if nothing more specific was reported since errCount
+    //Expressions report their own errors at the token that caused them, so the statement around one does not report it again
+    fn reportIfSilent(&mut self, errCount: usize, code: ParseCode, message: String, token: &Token) {
+        if self.reports.errors.len() == errCount {
+            self.reportErrorAt(code, message, token);
+        }
+    }
+
+    //Checks that the end if, end for, end while, end case or end procedure at endInd is followed by its ';'
+    //The block is dropped along with the ';' so this has to be there before the tokens are drained
+    fn expectEndSemicolon(&mut self, tokenList: &[Token], endInd: usize) -> Result<(), String> {
+        match tokenList.get(endInd + 1) {
+            Some(tok) if tok.tt == tokenTypeEnum::SEMICOLON => {
+                return Ok(());
+            }
+            _ => {
+                let endTok = tokenList[endInd].clone();
+                self.reportErrorAt(ParseCode::MissingSemicolon, format!("Missing ; after {}", endTok.tokenString.to_lowercase().replace('_', " ")), &endTok);
+                return Err("Missing ;".to_string());
+            }
+        }
+    }
+
+    //Gets the size of an array declaration from its size token
+    //The size has to be a positive integer that fits in the i32 the array type keeps
+    fn arraySize(&mut self, sizeToken: &Token) -> Result<i32, String> {
+        match Expr::parseInt(&sizeToken.tokenString).ok().filter(|size| *size > 0).map(i32::try_from) {
+            Some(Ok(size)) => {
+                return Ok(size);
+            }
+            _ => {
+                self.reportErrorAt(ParseCode::InvalidDecl, format!("Invalid array size {}, it must be a positive integer no larger than {}", sizeToken.tokenString, i32::MAX), sizeToken);
+                return Err("Error with variable declaration".to_string());
+            }
+        }
+    }
+
+    //Parses the next statement of a block, used by the program, procedure, if and for block loops
+    //If the statement has a syntax error the rest of it is skipped (panic mode) so the block can carry on
+    //with the next statement and every syntax error is reported in one run
+    fn parseBlockStmt(&mut self, tokenList: &mut Vec<Token>) -> Result<Option<Stmt>, String> {
+        let startToken = tokenList[0].clone();
+        let startLen = tokenList.len();
+        let stmtEnd = Parser::syncPoint(tokenList);
+        let errCount = self.reports.errors.len();
+        let scope = self.scope;
+
+        let scanned = self.parse(tokenList);
+        if let Err(err) = &scanned {
+            //A procedure that failed part way through has not left its scope
+            self.scope = scope;
+            //Makes sure the error is reported even if the failing arm only returned it
+            self.reportIfSilent(errCount, ParseCode::InvalidStmt, err.clone(), &startToken);
+
+            //The parser only ever removes tokens from the front, so skips whatever is left of the broken statement
+            let consumed = startLen - tokenList.len();
+            if consumed < stmtEnd {
+                tokenList.drain(0..stmtEnd - consumed);
+            }
+        }
+        return scanned;
+    }
+
+    //Checks if the procedure keyword at index i starts a forward declaration: procedure f : integer(...) forward;
+    //These have no body or end procedure, so they are skipped when matching procedures with their end procedure
+    fn isForwardDecl(tokenList: &[Token], i: usize) -> bool {
+        let mut k = i + 1;
+        while k < tokenList.len() {
+            match tokenList[k].tt {
+                //The parameter list ends at the first ), forward comes right after it
+                tokenTypeEnum::R_PAREN => {
+                    return (k + 1 < tokenList.len()) && (tokenList[k + 1].tt == tokenTypeEnum::FORWARD);
+                }
+                tokenTypeEnum::SEMICOLON | tokenTypeEnum::BEGIN | tokenTypeEnum::PROCEDURE => {
+                    return false;
+                }
+                _ => {
+                    k += 1;
+                }
+            }
+        }
+        return false;
+    }
+
+    //Finds how many tokens the statement at the start of the list covers, this is where parsing resyncs after an error
+    //If, for, while, case and procedure declarations run to their matching end if, end for, end while, end case or end procedure and the ';' after it
+    //Everything else runs to the next ';', or stops before a begin, the end of the enclosing block or a keyword that starts a new statement
+    //Always covers at least one token so recovery makes progress
+    fn syncPoint(tokenList: &Vec<Token>) -> usize {
+        //A forward declaration runs to its ';', or stops before whatever comes next if that is missing
+        if (tokenList[0].tt == tokenTypeEnum::PROCEDURE) && Self::isForwardDecl(tokenList, 0) {
+            let mut i = 1;
+            while i < tokenList.len() {
+                match tokenList[i].tt {
+                    tokenTypeEnum::SEMICOLON => {
+                        return i + 1;
+                    }
+                    tokenTypeEnum::PROCEDURE | tokenTypeEnum::VARIABLE | tokenTypeEnum::GLOBAL | tokenTypeEnum::BEGIN | tokenTypeEnum::END_PROGRAM if tokenList[i - 1].tt == tokenTypeEnum::FORWARD => {
+                        return i;
+                    }
+                    _ => {
+                        i += 1;
+                    }
+                }
+            }
+            return tokenList.len();
+        }
+
+        let blockTokens = match tokenList[0].tt {
+            tokenTypeEnum::IF => Some((tokenTypeEnum::IF, tokenTypeEnum::END_IF)),
+            tokenTypeEnum::FOR => Some((tokenTypeEnum::FOR, tokenTypeEnum::END_FOR)),
+            tokenTypeEnum::WHILE => Some((tokenTypeEnum::WHILE, tokenTypeEnum::END_WHILE)),
+            tokenTypeEnum::CASE => Some((tokenTypeEnum::CASE, tokenTypeEnum::END_CASE)),
+            tokenTypeEnum::PROCEDURE => Some((tokenTypeEnum::PROCEDURE, tokenTypeEnum::END_PROCEDURE)),
+            _ => None,
+        };
+
+        let mut depth = 0;
+        let mut i = 0;
+        while i < tokenList.len() {
+            let tt = &tokenList[i].tt;
+            match &blockTokens {
+                Some((opener, closer)) => {
+                    if (tt == opener) && !((*tt == tokenTypeEnum::PROCEDURE) && Self::isForwardDecl(tokenList, i)) {
+                        depth += 1;
+                    } else if tt == closer {
+                        depth -= 1;
+                        if depth == 0 {
+                            i += 1;
+                            if (i < tokenList.len()) && (tokenList[i].tt == tokenTypeEnum::SEMICOLON) {
+                                i += 1;
+                            }
+                            return i;
+                        }
+                    } else if *tt == tokenTypeEnum::END_PROGRAM {
+                        //Never skips past the end of the program
+                        return i.max(1);
+                    }
+                }
+                None => {
+                    if *tt == tokenTypeEnum::SEMICOLON {
+                        return i + 1;
+                    }
+                    let stmtBoundary = match tt {
+                        tokenTypeEnum::BEGIN | tokenTypeEnum::ELSE | tokenTypeEnum::ELSIF | tokenTypeEnum::END_IF | tokenTypeEnum::END_FOR |
+                        tokenTypeEnum::END_WHILE | tokenTypeEnum::END_CASE | tokenTypeEnum::END_PROCEDURE | tokenTypeEnum::END_PROGRAM |
+                        tokenTypeEnum::PROCEDURE | tokenTypeEnum::VARIABLE | tokenTypeEnum::GLOBAL | tokenTypeEnum::IF | tokenTypeEnum::FOR |
+                        tokenTypeEnum::WHILE | tokenTypeEnum::CASE | tokenTypeEnum::WHEN | tokenTypeEnum::BREAK | tokenTypeEnum::CONTINUE |
+                        tokenTypeEnum::RETURN => true,
+                        _ => false,
+                    };
+                    //The variable in 'global variable' belongs to the same declaration
+                    let globalDecl = (*tt == tokenTypeEnum::VARIABLE) && (i > 0) && (tokenList[i - 1].tt == tokenTypeEnum::GLOBAL);
+                    if stmtBoundary && !globalDecl && (i > 0) {
+                        return i;
+                    }
+                }
+            }
+            i += 1;
+        }
+        return tokenList.len();
+    }
+
+    //Parses one procedure parameter, a variable declaration with an optional in, out or in out after the :
+    //Parameters without a mode are in parameters
+    //in and out are only modes when a type follows them, so they can still be used as variable names everywhere else
+    fn parseParam(&mut self, paramTokens: &mut Vec<Token>) -> Result<Option<Stmt>, String> {
+        let mut paramMode = ParamMode::In;
+        let isWord = |tok: Option<&Token>, word: &str| tok.map_or(false, |t| (t.tt == tokenTypeEnum::IDENTIFIER) && (t.tokenString == word));
+        let isType = |tok: Option<&Token>| tok.map_or(false, |t| t.tt == tokenTypeEnum::IDENTIFIER);
+        if (paramTokens.len() > 4) && (paramTokens[2].tt == tokenTypeEnum::COLON) {
+            if isWord(paramTokens.get(3), "in") && isWord(paramTokens.get(4), "out") && isType(paramTokens.get(5)) {
+                paramMode = ParamMode::InOut;
+                paramTokens.drain(3..5);
+            } else if isWord(paramTokens.get(3), "in") && isType(paramTokens.get(4)) {
+                paramTokens.drain(3..4);
+            } else if isWord(paramTokens.get(3), "out") && isType(paramTokens.get(4)) {
+                paramMode = ParamMode::Out;
+                paramTokens.drain(3..4);
+            }
+        }
+        match self.parse(paramTokens)? {
+            Some(Stmt::VarDecl(varName, varType, lineNum)) => {
+                return Ok(Some(Stmt::ParamDecl(varName, varType, paramMode, lineNum)));
+            }
+            other => {
+                return Ok(other);
+            }
+        }
+    }
+
+    //Parses the statements of one arm of an if or case statement into a block
+    //Bad statements are already reported by parseBlockStmt, so parsing carries on with the rest of the arm
+    fn parseBodyBlock(&mut self, mut armList: Vec<Token>, lineNum: usize) -> Stmt {
+        let mut armBlock = Stmt::Block(Vec::new(), lineNum);
+        let mut armI = 0;
+        let armLen = armList.len();
+        while !armList.is_empty() && (armI <= armLen) {
+            armI = armI + 1;
+            if let Ok(Some(stmt)) = self.parseBlockStmt(&mut armList) {
+                let _ = armBlock.push_to_block(stmt);
+            }
+        }
+        return armBlock;
+    }
+
     //The public function that is used to parse the entire program, returns the AST if successful
     //Returns the reporting structure if not
     pub fn startParse(&mut self) -> Result<(Reporting, Option<Stmt>), Reporting> {
@@ -83,11 +343,62 @@ impl Parser{
 
     }
 
+    //Finds the last token of the single operand starting at start: a literal or name, an indexed array,
+    //a call, a parenthesized expression, or another not with its own operand
+    //None if the operand is missing or its brackets are never closed
+    fn primaryEnd(tokens: &[Token], start: usize) -> Option<usize> {
+        let first = tokens.get(start)?;
+        match first.tt {
+            tokenTypeEnum::NOT => {
+                return Parser::primaryEnd(tokens, start + 1);
+            }
+            tokenTypeEnum::SEMICOLON | tokenTypeEnum::R_PAREN | tokenTypeEnum::R_BRACKET => {
+                return None;
+            }
+            _ => {}
+        }
+
+        //Calls and parentheses run to their matching ), indexing runs to the matching ]
+        let closer = if (first.tt == tokenTypeEnum::PROCEDURE_CALL) || (first.tt == tokenTypeEnum::L_PAREN) {
+            tokenTypeEnum::R_PAREN
+        } else if tokens.get(start + 1).map(|tok| tok.tt == tokenTypeEnum::L_BRACKET).unwrap_or(false) {
+            tokenTypeEnum::R_BRACKET
+        } else {
+            return Some(start);
+        };
+        let mut depth = 0;
+        for (i, tok) in tokens.iter().enumerate().skip(start) {
+            match tok.tt {
+                tokenTypeEnum::PROCEDURE_CALL | tokenTypeEnum::L_PAREN | tokenTypeEnum::L_BRACKET => {
+                    depth += 1;
+                }
+                tokenTypeEnum::R_PAREN | tokenTypeEnum::R_BRACKET => {
+                    depth -= 1;
+                    if (depth == 0) && (tok.tt == closer) {
+                        return Some(i);
+                    }
+                }
+                tokenTypeEnum::SEMICOLON => {
+                    return None;
+                }
+                _ => {}
+            }
+        }
+        return None;
+    }
+
     //Parses a expressions and returns an Expr which is used within program AST Stmt
     fn parseExpr(&mut self, tokenList: &mut Vec<Token>) -> Result<Expr, String> {
         //Initializes the variable that is being referenced first
         let mut firstOp:Expr = Expr::StringLiteral(("NONE".to_string()));
 
+        //An expression needs at least one token before the ';'
+        if(tokenList.len() < 2) || (tokenList[0].tt == tokenTypeEnum::SEMICOLON) {
+            let errToken = tokenList.first().cloned().unwrap_or(self.stmtToken.clone());
+            self.reportErrorAt(ParseCode::InvalidExpr, "Expected an expression".to_string(), &errToken);
+            return Err("Expected an expression".to_string());
+        }
+
         //Initializes values for finding the end of the expression
         let mut k = 0;
         let mut nextTok = &tokenList[k];
@@ -127,17 +438,24 @@ impl Parser{
                 brackInd += 1;
             }
 
+            //A ] before the [ leaves no name to index
+            if brackInd == 0 {
+                self.reportErrorAt(ParseCode::UnexpectedToken, "Unexpected ]".to_string(), &curStmt[0].clone());
+                return Err("Unexpected ]".to_string());
+            }
+
             //If the end of the index was not found, error
             if brackInd == curStmt.len() {
-                tokenList.drain(..k+1);
-                let errMsg = format!("Error finding the end of the array index on line {}", curStmt[1].lineNum);
-                return(Err(errMsg));
+                tokenList.drain(..(k + 1).min(tokenList.len()));
+                self.reportErrorAt(ParseCode::Unclosed, format!("Missing ] at the end of the index into {}", varName), &curStmt[1].clone());
+                return Err("Missing ]".to_string());
             }
 
             //Removes the index assignment so there is just the ] left for proper parsing of index expression
             indexList.drain(0..2);
 
             //Recursively parses the expr that makes up the index of the arrayRef call
+            let errCount = self.reports.errors.len();
             let parsedExpr = self.parseExpr(&mut indexList);
             let mut indexExpr: Expr;
 
@@ -147,8 +465,7 @@ impl Parser{
                     indexExpr = expr;
                 }
                 Err(err) => {
-                    let errMsg = format!("Error on line {}: {}", tokenList[0].lineNum, err);
-                    self.reports.reportError(errMsg);
+                    self.reportIfSilent(errCount, ParseCode::InvalidExpr, format!("Invalid index into {}: {}", varName, err), &curStmt[1].clone());
                     return Err("Error with expression".to_string());
                 }
             }
@@ -158,17 +475,60 @@ impl Parser{
             firstOp = Expr::ArrayRef((varName), (indexBox));
             //Removes the array reference so there is just the ] left
             let modifier: usize;
-            println!("Next string after array ref {}", curStmt[brackInd + 1].tokenString.clone());
-            if(curStmt[brackInd + 1].tt.clone() == tokenTypeEnum::SEMICOLON){
+            // println!("Next string after array ref {}", curStmt[brackInd + 1].tokenString.clone());
+            //A ) ends the expression the same as a ;, this happens when the reference is the last argument of a call
+            let afterIndex = curStmt.get(brackInd + 1).map(|tok| tok.tt.clone());
+            if(afterIndex == Some(tokenTypeEnum::SEMICOLON)) || (afterIndex == Some(tokenTypeEnum::R_PAREN)){
                 modifier = 1;
             } else {
                 modifier = 0;
             }
             
             curStmt.drain(0..brackInd + modifier);
-            println!("Next string after array ref {}", curStmt[0].tokenString.clone());
+            // println!("Next string after array ref {}", curStmt[0].tokenString.clone());
 
         } 
+        //If the expression starts with not, it applies to the single operand right after it
+        //so not a & b is (not a) & b, the rest of the expression carries on after the operand
+        else if (curStmt[0].tt == tokenTypeEnum::NOT) {
+            let operandEnd = match Parser::primaryEnd(&curStmt, 1) {
+                Some(end) => end,
+                None => {
+                    self.reportErrorAt(ParseCode::InvalidExpr, "Expected a value, name, call or parenthesized expression after not".to_string(), &curStmt[0].clone());
+                    return Err("Missing operand of not".to_string());
+                }
+            };
+
+            //Parses the operand on its own, ended with a ; so the parser can respond correctly
+            let mut operandList: Vec<Token> = curStmt[1..operandEnd + 1].to_vec();
+            let mut endTok = curStmt[operandEnd].clone();
+            endTok.tokenString = ";".to_string();
+            endTok.tt = tokenTypeEnum::SEMICOLON;
+            endTok.tg = tokenGroup::SYMBOL;
+            operandList.push(endTok);
+            let errCount = self.reports.errors.len();
+            let parsedOperand = self.parseExpr(&mut operandList);
+            match parsedOperand {
+                Ok(operand) => {
+                    firstOp = Expr::NotOp(Box::new(operand));
+                }
+                Err(err) => {
+                    self.reportIfSilent(errCount, ParseCode::InvalidExpr, format!("Invalid operand of not: {}", err), &curStmt[0].clone());
+                    return Err(err);
+                }
+            }
+
+            //Nothing else in the expression, the not is all of it
+            match curStmt.get(operandEnd + 1).map(|tok| tok.tt.clone()) {
+                None | Some(tokenTypeEnum::SEMICOLON) | Some(tokenTypeEnum::R_PAREN) => {
+                    return Ok(firstOp);
+                }
+                _ => {
+                    //Leaves the last token of the operand first so the operator is next, the same as after a literal
+                    curStmt.drain(0..operandEnd);
+                }
+            }
+        }
         //If the first token in the expr list is a variable
         else if (curStmt[0].tg == tokenGroup::VARIABLE){
             //If not an array
@@ -205,6 +565,12 @@ impl Parser{
                 parenInd += 1;
             }
 
+            //The expression ended before the parentheses were closed
+            if parenInd >= curStmt.len() {
+                self.reportErrorAt(ParseCode::Unclosed, "Missing ) for this (".to_string(), &curStmt[0].clone());
+                return Err("Missing )".to_string());
+            }
+
             //modifies the end of the parentheses so that the parser can respond correctly
             parStmt[parenInd - 1].tokenString = ";".to_string();
             parStmt[parenInd - 1].tt = tokenTypeEnum::SEMICOLON;
@@ -212,15 +578,16 @@ impl Parser{
 
 
             //Parses the internal part of the parentheses
+            let errCount = self.reports.errors.len();
             let scanned = self.parseExpr(&mut parStmt);                            
             match scanned {
                 Ok(expr) => {
                     firstOp = expr;
                 },
                 Err(reporting) => {
-                    println!("Error parsing paren expression: {:?}", reporting);
-                    let errMsg = format!("Error parsing paren expr: {:?}", self.reports);
-                    return Err(errMsg);
+                    // println!("Error parsing paren expression: {:?}", reporting);
+                    self.reportIfSilent(errCount, ParseCode::InvalidExpr, format!("Invalid expression in parentheses: {}", reporting), &curStmt[0].clone());
+                    return Err(reporting);
                 },
             }
             
@@ -239,28 +606,67 @@ impl Parser{
             let procName = curStmt[0].tokenString.clone();
             //If there are parameters being passed
             if (curStmt[1].tt != tokenTypeEnum::R_PAREN) {
-                let mut paramInd = 0;
                 let mut params: Vec<Expr> = Vec::new();
                 let mut paramTokens: Vec<Token> = Vec::new();
+                let mut scope = 0;
                 let mut p = 1;
                 let mut pToken;
                 
-                //Finds the end of the parameters
+                //Finds the end of the parameters, skipping over nested parentheses and calls
+                //Each parameter ends at a comma outside of them and is parsed on its own
                 while p < curStmt.len() {
                     pToken = curStmt[p].clone();
-                    if(pToken.tt == tokenTypeEnum::R_PAREN) {
-                        break;
+                    if (pToken.tt == tokenTypeEnum::L_PAREN) || (pToken.tt == tokenTypeEnum::PROCEDURE_CALL) {
+                        scope += 1;
+                    } else if (pToken.tt == tokenTypeEnum::R_PAREN) {
+                        if (scope == 0) {
+                            break;
+                        }
+                        scope -= 1;
                     }
 
-                    paramTokens.push(pToken.clone());
+                    if (pToken.tt == tokenTypeEnum::COMMA) && (scope == 0) {
+                        if paramTokens.is_empty() {
+                            self.reportErrorAt(ParseCode::InvalidExpr, format!("Missing parameter in call to {}", procName.clone()), &pToken);
+                            return Err("Missing parameter".to_string());
+                        }
+                        //Ends the parameter with a semicolon so the parser can respond correctly
+                        let mut endTok = pToken.clone();
+                        endTok.tokenString = ";".to_string();
+                        endTok.tt = tokenTypeEnum::SEMICOLON;
+                        endTok.tg = tokenGroup::SYMBOL;
+                        paramTokens.push(endTok);
+
+                        //Parses out the parameter
+                        let paramScan = self.parseExpr(&mut paramTokens);
+                        match paramScan {
+                            Ok(expr)=> {
+                                params.push(expr);
+                            } Err(err) => {
+                                return(Err(err));
+                            }
+                        }
+                        paramTokens = Vec::new();
+                    } else {
+                        paramTokens.push(pToken.clone());
+                    }
                     p += 1;
-                    
                 }
 
-                //Adds the parameter parsed to the vector of parameters
-                paramTokens.push(curStmt[p].clone());
-
-                //Parses out all of the parameters
+                //Parses the last parameter, which ends at the closing parenthesis
+                if p >= curStmt.len() {
+                    self.reportErrorAt(ParseCode::Unclosed, format!("Missing ) at the end of the call to {}", procName.clone()), &curStmt[0].clone());
+                    return Err("Missing )".to_string());
+                }
+                if paramTokens.is_empty() {
+                    self.reportErrorAt(ParseCode::InvalidExpr, format!("Missing parameter in call to {}", procName.clone()), &curStmt[p].clone());
+                    return Err("Missing parameter".to_string());
+                }
+                let mut endTok = curStmt[p].clone();
+                endTok.tokenString = ";".to_string();
+                endTok.tt = tokenTypeEnum::SEMICOLON;
+                endTok.tg = tokenGroup::SYMBOL;
+                paramTokens.push(endTok);
                 let paramScan = self.parseExpr(&mut paramTokens);
                 match paramScan {
                     Ok(expr)=> {
@@ -280,8 +686,9 @@ impl Parser{
                 let procCall = Expr::ProcRef((procName), (Some(params)));
                 
                 //Sets the call properly so it parseExpr can handle the rest of the expression
+                //Leaves the closing parenthesis first, the same as a call with no parameters
                 firstOp = procCall;
-                curStmt.drain(0..p+1);
+                curStmt.drain(0..p);
 
             } 
             //If there are no parameters in the parameter call
@@ -301,6 +708,7 @@ impl Parser{
                     firstOp = constExpr;
                 }
                 Err(msg) => {
+                    self.reportErrorAt(ParseCode::InvalidExpr, msg.clone(), &curStmt[0].clone());
                     return Err(msg);
                 }
             }
@@ -317,8 +725,8 @@ impl Parser{
                         Ok(expr) => {
                             firstOp = expr;
                         } Err(err) => {
-                            let errMsg = format!("Error parsing expression on line on line {}: {}", curStmt[0].lineNum.to_string(), err);
-                            return(Err(errMsg));
+                            self.reportErrorAt(ParseCode::UnexpectedToken, format!("Expected a value but found '{}'", curStmt[0].tokenString), &curStmt[0].clone());
+                            return(Err(err));
                         }
                     }
                 }
@@ -351,9 +759,14 @@ impl Parser{
                 },
                 Err(reporting) => {
                     // println!("Error parsing op on line {}: {:?}",curStmt[1].lineNum, reporting);
-                    println!("BAD OP {}", curStmt[1].tokenString.clone());
-                    let errMsg = format!("Error parsing operator on line {}: {:?}", curStmt[1].lineNum.to_string(), self.reports);
-                    return Err(errMsg);
+                    // println!("BAD OP {}", curStmt[1].tokenString.clone());
+                    //Something on the next line where the operator should be is the next statement, so this one is missing its ;
+                    if curStmt[1].lineNum > curStmt[0].lineNum {
+                        self.reportErrorAt(ParseCode::MissingSemicolon, "Missing ';' at the end of the statement".to_string(), &curStmt[0].clone());
+                    } else {
+                        self.reportErrorAt(ParseCode::UnexpectedToken, format!("Expected an operator but found '{}'", curStmt[1].tokenString), &curStmt[1].clone());
+                    }
+                    return Err(reporting);
                 },
             }
 
@@ -362,6 +775,7 @@ impl Parser{
             subList.drain(0..2); 
             let mut parsedExpr: Expr;
             //parses the remainder of the expression
+            let errCount = self.reports.errors.len();
             let scanned = self.parseExpr(&mut subList);                            
             let mut headerStmt:Expr;
             //Checks if it was parsed successfully
@@ -371,8 +785,8 @@ impl Parser{
                 },
                 Err(reporting) => {
                     // println!("Error parsing expression : {:?}", reporting);
-                    let errMsg = format!("Error parsing expression on line {}: {:?}", subList[0].lineNum.clone(), self.reports);
-                    return Err(errMsg);
+                    self.reportIfSilent(errCount, ParseCode::InvalidExpr, format!("Invalid expression after {}: {}", curStmt[1].tokenString, reporting), &curStmt[1].clone());
+                    return Err(reporting);
                 },
             }
 
@@ -406,8 +820,7 @@ impl Parser{
                 }
                 Err(err) => {
                     // println!("Error parsing expression");
-                    let errMsg = format!("Error parsing expression on line {}: {}", tokenList[0].lineNum, err);
-                    self.reports.reportError(errMsg);
+                    self.reportErrorAt(ParseCode::UnexpectedToken, format!("Expected a value but found '{}'", tokenList[0].tokenString), &tokenList[0].clone());
                     return Err("Error with expression".to_string());
                 }
             }
@@ -432,8 +845,22 @@ impl Parser{
 
         //Sets up the things that will be used here            
         let tokLen: usize = tokenList.len();
+        self.stmtToken = tokenList[0].clone();
         let mut token = &tokenList[0];
-        
+
+        //Only the end markers and bool constants can stand alone, every other statement needs at least its ;
+        if tokLen == 1 {
+            match token.tt {
+                tokenTypeEnum::END_PROGRAM | tokenTypeEnum::END_PROCEDURE | tokenTypeEnum::TRUE | tokenTypeEnum::FALSE => {}
+                _ => {
+                    let endTok = token.clone();
+                    self.reportErrorAt(ParseCode::MissingSemicolon, format!("Unexpected end of statement after '{}'", endTok.tokenString), &endTok);
+                    tokenList.drain(0..1);
+                    return Err("Unexpected end of statement".to_string());
+                }
+            }
+        }
+
         //The main match case, used to determine what should be done with the beginning of each Stmt
         match token.tt {
             //Indicates the beginning of the program
@@ -443,8 +870,9 @@ impl Parser{
                 //Checks the first line
                 let firstToken = &tokenList[0];
                 if let tokenTypeEnum::PROGRAM = firstToken.tt {
-                    let thirdToken = &tokenList[2];
-                    if let tokenTypeEnum::IS = thirdToken.tt {
+                    //A program with nothing after its name has no is to find
+                    let isThird = tokenList.get(2).map(|thirdToken| thirdToken.tt == tokenTypeEnum::IS).unwrap_or(false);
+                    if isThird {
                         //Gets the program name
                         let programName: String = tokenList[1].tokenString.clone();                                
                         
@@ -456,11 +884,11 @@ impl Parser{
                         let mut beginScope = 0;
                         let iterTokList = tokenList.clone();
                         
-                        //Finds where the header ends and the body begin
-                        for token in iterTokList{
+                        //Finds where the header ends and the body begin, forward declarations have no end procedure to match
+                        for (tokInd, token) in iterTokList.iter().enumerate(){
                             if (token.tt == tokenTypeEnum::BEGIN) && (beginScope == 0){
                                 break;
-                            } else if (token.tt == tokenTypeEnum::PROCEDURE) {
+                            } else if (token.tt == tokenTypeEnum::PROCEDURE) && !Self::isForwardDecl(&iterTokList, tokInd) {
                                 beginScope = beginScope + 1;
                                 beginInt = beginInt + 1;
                             } else if (token.tt == tokenTypeEnum::END_PROCEDURE){
@@ -476,19 +904,20 @@ impl Parser{
 
                         //Parses the header
                         let mut newHeader: Vec<Token> = tokenList.iter().cloned().map(|t| t.clone()).collect();
-                        let mut headerBlock = Stmt::Block(Vec::new(), tokenList[0].lineNum.clone());
+                        let headerLine = tokenList.first().map(|tok| tok.lineNum).unwrap_or(self.stmtToken.lineNum);
+                        let mut headerBlock = Stmt::Block(Vec::new(), headerLine);
                         let mut headerI = 0;
                         let headerLen = newHeader.len();
                         //Runs through the header and scans it
                         while(!newHeader.is_empty()){
                             //Ensures that this list does not overflow
                             if(headerI > headerLen){
-                                self.reports.reportError("Infinite loop in header".to_string());
+                                self.reportError(ParseCode::Internal, "Infinite loop in header".to_string());
                                 return Err("infinite loop in header".to_string());
                             }
                             
                             //Parses the next statement out of the header
-                            let scanned = self.parse(&mut newHeader);                            
+                            let scanned = self.parseBlockStmt(&mut newHeader);                            
                             let mut headerStmt:Stmt;
                             //Checks the result of the scanned Stmt
                             match scanned {
@@ -503,38 +932,41 @@ impl Parser{
                                 },
                                 //For a stmt that returns properly but is not properly parsed
                                 Ok((None)) => {
-                                    let errMsg = format!("Error parsing header statement on line {}", newHeader[0].lineNum.clone());
-                                    self.reports.reportError(errMsg);
+                                    let errMsg = format!("Unexpected '{}' in the program header", self.stmtToken.tokenString);
+                                    self.reportError(ParseCode::UnexpectedToken, errMsg);
                                     headerI += 1;
                                 },
-                                //If there is an error parsing the header
+                                //If there is an error parsing the header it has been reported, moves on to the next statement
                                 Err(reporting) => {
-                                    let errMsg = format!("Error parsing header statment on line {}: {:?}", newHeader[0].lineNum.clone(), self.reports);
-                                    return Err(errMsg);
+                                    headerI += 1;
                                 },
                             }
                         }
 
                         //Creates the body tokenList
                         let mut newBody: Vec<Token> = bodyList.iter().cloned().map(|t| t.clone()).collect();
-                        newBody.drain(0..1);
+                        if(newBody.is_empty()){
+                            self.reportError(ParseCode::InvalidProgram, "No begin found for the program body. \n The program body must start with 'begin'".to_string());
+                        } else {
+                            newBody.drain(0..1);
+                        }
                         
 
-                        let mut bodyBlock = Stmt::Block(Vec::new(), "".to_string());
+                        let mut bodyBlock = Stmt::Block(Vec::new(), 0);
                         let mut bodyI = 0;
                         let bodyLen = newBody.len();
                         
                         //Goes through the body block and parses the whole guy
-                        while(newBody[0].tt != tokenTypeEnum::END_PROGRAM){
-                            //Avoids infinite loops
-                            if(bodyI > bodyLen){
+                        while(newBody.is_empty() || (newBody[0].tt != tokenTypeEnum::END_PROGRAM)){
+                            //Avoids infinite loops, and stops if the tokens ran out before the end program
+                            if(newBody.is_empty() || (bodyI > bodyLen)){
                                 
-                                self.reports.reportError("No End Program phrase detected. Program must end with 'End Program' ".to_string());
-                                return Err("No end program".to_string());
+                                self.reportError(ParseCode::InvalidProgram, "No End Program phrase detected. Program must end with 'End Program' ".to_string());
+                                break;
                             }
 
                             //Parses the body list one stmt at a time
-                            let scanned = self.parse(&mut newBody);                            
+                            let scanned = self.parseBlockStmt(&mut newBody);                            
                             let mut bodyStmt:Stmt;
                             //Checks that the stmt returned ok
                             match scanned {
@@ -543,13 +975,12 @@ impl Parser{
                                     bodyI = bodyI + 1;
                                 },
                                 Ok((None)) => {
-                                    let errMsg = format!("Error parsing body statement");
-                                    self.reports.reportError(errMsg);
+                                    let errMsg = format!("Unexpected '{}' in the program body", self.stmtToken.tokenString);
+                                    self.reportError(ParseCode::UnexpectedToken, errMsg);
                                     bodyI = bodyI + 1;
                                 },
                                 Err(reporting) => {
-                                    let errMsg = format!("Error parsing body statment on line {}: {:?}", newBody[0].lineNum.clone(), self.reports);
-                                    return Err(errMsg);
+                                    bodyI = bodyI + 1;
                                 },
                             }
                             
@@ -564,7 +995,7 @@ impl Parser{
                         let boxBody: Box<Stmt> = Box::new(bodyBlock);
 
                         //Creates the programAst Stmt
-                        let programAst = Stmt::Program(programName.clone(), boxHeader, boxBody, "0".to_string());
+                        let programAst = Stmt::Program(programName.clone(), boxHeader, boxBody, 0);
                         // programAst.display(0);
 
                         //Returns the parsed program
@@ -573,14 +1004,14 @@ impl Parser{
                     } 
                     //If the program declaration is incorrect
                     else {
-                        self.reports.reportError("Program declaration incorrect. \n Program must start with: 'program [Program name] is'".to_string());
+                        self.reportError(ParseCode::InvalidProgram, "Program declaration incorrect. \n Program must start with: 'program [Program name] is'".to_string());
                         // println!("Error with program delcaration");
                         return Err("Error with program declaration".to_string());
                     }
                 } 
                 //If there is an error in the program delcaration
                 else {
-                    self.reports.reportError("Program declaration incorrect. \n Program must start with: 'program [Program name] is'".to_string());
+                    self.reportError(ParseCode::InvalidProgram, "Program declaration incorrect. \n Program must start with: 'program [Program name] is'".to_string());
                     // println!("Error with program delcaration");
                     return Err("Error with program declaration".to_string());
                 }
@@ -600,6 +1031,10 @@ impl Parser{
                     //Finds the end of the statement
                     while nextTok.tt != tokenTypeEnum::SEMICOLON {
                         curStmt.push(nextTok);
+                        if(k + 1 >= tokenList.len()){
+                            self.reportErrorAt(ParseCode::MissingSemicolon, format!("Missing ';' at the end of the variable declaration"), nextTok);
+                            return Err("Error with variable declaration".to_string());
+                        }
                         k = k + 1;
                         nextTok = &tokenList[k];
                     }
@@ -614,46 +1049,35 @@ impl Parser{
                     //     println!("< \"{}\" , {}, {} >", token.tokenString, token.tt.to_string(), token.lineNum);
                     // }
 
+                    //Makes sure the declaration is long enough to check
+                    if curStmt.len() < 5 {
+                        self.reportErrorAt(ParseCode::InvalidDecl, format!("Variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type]'"), curStmt[2.min(curStmt.len() - 1)]);
+                        return Err("Error with variable declaration".to_string());
+                    }
+
                     //Checks the validity of the statement, sending errors if it is incorrect
                     if curStmt[2].tt != tokenTypeEnum::COLON {
-                        let errMsg = format!("In line: {}, Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type]'", curStmt[3].lineNum,);
-                        self.reports.reportError(errMsg);
+                        self.reportErrorAt(ParseCode::InvalidDecl, format!("Variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type]'"), curStmt[2]);
                         return Err("Error with variable declaration".to_string());
                     } 
                     //Checks more of the statement
                     else {
                         if (curStmt[4].tt != tokenTypeEnum::SEMICOLON) {
                             if curStmt[4].tt != tokenTypeEnum::L_BRACKET {
-                                let errMsg = format!("In line: {}, Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : integer[arraySize]'", curStmt[3].lineNum.clone());
-                                self.reports.reportError(errMsg);
+                                self.reportErrorAt(ParseCode::InvalidDecl, format!("Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type][arraySize]'"), curStmt[4]);
                                 return Err("Error with variable declaration".to_string());
                             } else {
-                                if curStmt[3].tokenString == "integer" {
+                                if let Ok(elemType) = VarType::new(&curStmt[3].tokenString) {
                                     if curStmt[5].tt == tokenTypeEnum::INT {
-                                        let arSizeStr = curStmt[5].tokenString.clone();
-                                        if let Ok(arSize) = arSizeStr.parse::<usize>() {
-                                            let newVar = Stmt::VarDecl(varName.clone(), VarType::IntArray(arSize.try_into().unwrap()), curStmt[0].lineNum.clone());
-                                            retStmt = newVar;
-                                        } else {
-                                            self.reports.reportError(format!(
-                                                "In line: {}, Invlaid array size", 
-                                                curStmt[3].lineNum, 
-                                            ));
-                                            return Err("Error with variable declaration".to_string());
-                                        }
+                                        let arSize = self.arraySize(curStmt[5])?;
+                                        let newVar = Stmt::VarDecl(varName.clone(), VarType::Array(Box::new(elemType), arSize), curStmt[0].lineNum.clone());
+                                        retStmt = newVar;
                                     } else {
-                                        self.reports.reportError(format!(
-                                            "In line: {}, Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : integer[arraySize]'", 
-                                            curStmt[3].lineNum, 
-                                        ));
+                                        self.reportErrorAt(ParseCode::InvalidDecl, format!("Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type][arraySize]'"), curStmt[5]);
                                         return Err("Error with variable declaration".to_string());
                                     }
                                 } else {
-                                    self.reports.reportError(format!(
-                                        "In line: {}, '{}' is not a valid variable type", 
-                                        curStmt[3].lineNum, 
-                                        curStmt[3].tokenString
-                                    ));
+                                    self.reportErrorAt(ParseCode::InvalidDecl, format!("'{}' is not a valid variable type", curStmt[3].tokenString), curStmt[3]);
                                     return Err("Error with variable declaration".to_string());
                                 }
                             }
@@ -672,11 +1096,7 @@ impl Parser{
                             let newVar = Stmt::VarDecl(varName.clone(), VarType::Float, curStmt[3].lineNum.clone());
                             retStmt = newVar;
                         } else {
-                            self.reports.reportError(format!(
-                                "In line: {}, '{}' is not a valid variable type", 
-                                curStmt[3].lineNum, 
-                                curStmt[3].tokenString
-                            ));
+                            self.reportErrorAt(ParseCode::InvalidDecl, format!("'{}' is not a valid variable type", curStmt[3].tokenString), curStmt[3]);
                             return Err("Error with variable declaration".to_string());
                         }
                     }
@@ -699,6 +1119,10 @@ impl Parser{
                     let mut curStmt: Vec<&Token> = vec![];
                     while nextTok.tt != tokenTypeEnum::SEMICOLON {
                         curStmt.push(nextTok);
+                        if(k + 1 >= tokenList.len()){
+                            self.reportErrorAt(ParseCode::MissingSemicolon, format!("Missing ';' at the end of the variable declaration"), nextTok);
+                            return Err("Error with variable declaration".to_string());
+                        }
                         k = k + 1;
                         nextTok = &tokenList[k];
 
@@ -711,47 +1135,32 @@ impl Parser{
                     curStmt.insert(0, &globalToken);
 
                     //Error checking
+                    //Makes sure the declaration is long enough to check
+                    if curStmt.len() < 5 {
+                        self.reportErrorAt(ParseCode::InvalidDecl, format!("Variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type]'"), curStmt[2.min(curStmt.len() - 1)]);
+                        return Err("Error with global variable declaration".to_string());
+                    }
+
                     if curStmt[2].tt != tokenTypeEnum::COLON {
-                        self.reports.reportError(format!(
-                            "In line: {}, Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type]'", 
-                            curStmt[3].lineNum, 
-                        ));
+                        self.reportErrorAt(ParseCode::InvalidDecl, format!("Variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type]'"), curStmt[2]);
                         return Err("Error with global variable declaration".to_string());
                     } else {
                         if (curStmt[4].tt != tokenTypeEnum::SEMICOLON) {
                             if curStmt[4].tt != tokenTypeEnum::L_BRACKET {
-                                self.reports.reportError(format!(
-                                    "In line: {}, Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : integer[arraySize]'", 
-                                    curStmt[3].lineNum, 
-                                ));
+                                self.reportErrorAt(ParseCode::InvalidDecl, format!("Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type][arraySize]'"), curStmt[4]);
                                 return Err("Error with global variable declaration".to_string());
                             } else {
-                                if curStmt[3].tokenString == "integer" {
+                                if let Ok(elemType) = VarType::new(&curStmt[3].tokenString) {
                                     if curStmt[5].tt == tokenTypeEnum::INT {
-                                        let arSizeStr = curStmt[5].tokenString.clone();
-                                        if let Ok(arSize) = arSizeStr.parse::<usize>() {
-                                            let newVar = Stmt::GlobVarDecl(varName.clone(), VarType::IntArray(arSize.try_into().unwrap()), curStmt[3].lineNum.clone());
-                                            retStmt = newVar;
-                                        } else {
-                                            self.reports.reportError(format!(
-                                                "In line: {}, Invlaid array size", 
-                                                curStmt[3].lineNum, 
-                                            ));
-                                            return Err("Error with variable declaration".to_string());
-                                        }
+                                        let arSize = self.arraySize(curStmt[5])?;
+                                        let newVar = Stmt::GlobVarDecl(varName.clone(), VarType::Array(Box::new(elemType), arSize), curStmt[3].lineNum.clone());
+                                        retStmt = newVar;
                                     } else {
-                                        self.reports.reportError(format!(
-                                            "In line: {}, Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : integer[arraySize]'", 
-                                            curStmt[3].lineNum, 
-                                        ));
+                                        self.reportErrorAt(ParseCode::InvalidDecl, format!("Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type][arraySize]'"), curStmt[5]);
                                         return Err("Error with variable declaration".to_string());
                                     }
                                 } else {
-                                    self.reports.reportError(format!(
-                                        "In line: {}, '{}' is not a valid variable type", 
-                                        curStmt[3].lineNum, 
-                                        curStmt[3].tokenString
-                                    ));
+                                    self.reportErrorAt(ParseCode::InvalidDecl, format!("'{}' is not a valid variable type", curStmt[3].tokenString), curStmt[3]);
                                     return Err("Error with variable declaration".to_string());
                                 }
                             }
@@ -770,11 +1179,7 @@ impl Parser{
                             let newVar = Stmt::GlobVarDecl(varName.clone(), VarType::Float, curStmt[3].lineNum.clone());
                             retStmt = newVar;
                         } else {
-                            self.reports.reportError(format!(
-                                "In line: {}, '{}' is not a valid variable type", 
-                                curStmt[3].lineNum, 
-                                curStmt[3].tokenString
-                            ));
+                            self.reportErrorAt(ParseCode::InvalidDecl, format!("'{}' is not a valid variable type", curStmt[3].tokenString), curStmt[3]);
                             return Err("Error with variable declaration".to_string());
                         }
                     }
@@ -797,59 +1202,44 @@ impl Parser{
                 let mut curStmt: Vec<&Token> = vec![];
                 while nextTok.tt != tokenTypeEnum::SEMICOLON {
                     curStmt.push(nextTok);
+                    if(k + 1 >= tokenList.len()){
+                        self.reportErrorAt(ParseCode::MissingSemicolon, format!("Missing ';' at the end of the variable declaration"), nextTok);
+                        return Err("Error with global variable declaration".to_string());
+                    }
                     k = k + 1;
                     nextTok = &tokenList[k];
 
                 }
                 curStmt.push(nextTok);
-                let varName = &curStmt[1].tokenString;
-
-
-
-                
 
                 //Error checking
+                //Makes sure the declaration is long enough to check
+                if curStmt.len() < 5 {
+                    self.reportErrorAt(ParseCode::InvalidDecl, format!("Variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type]'"), curStmt[2.min(curStmt.len() - 1)]);
+                    return Err("Error with global variable declaration".to_string());
+                }
+                let varName = &curStmt[1].tokenString;
+
                 if curStmt[2].tt != tokenTypeEnum::COLON {
-                    self.reports.reportError(format!(
-                        "In line: {}, Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type]'", 
-                        curStmt[3].lineNum, 
-                    ));
+                    self.reportErrorAt(ParseCode::InvalidDecl, format!("Variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type]'"), curStmt[2]);
                     return Err("Error with global variable declaration".to_string());
                 } else {
                     if (curStmt[4].tt != tokenTypeEnum::SEMICOLON) {
                         if curStmt[4].tt != tokenTypeEnum::L_BRACKET {
-                            self.reports.reportError(format!(
-                                "In line: {}, Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : integer[arraySize]'", 
-                                curStmt[3].lineNum, 
-                            ));
+                            self.reportErrorAt(ParseCode::InvalidDecl, format!("Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type][arraySize]'"), curStmt[4]);
                             return Err("Error with global variable declaration".to_string());
                         } else {
-                            if curStmt[3].tokenString == "integer" {
+                            if let Ok(elemType) = VarType::new(&curStmt[3].tokenString) {
                                 if curStmt[5].tt == tokenTypeEnum::INT {
-                                    let arSizeStr = curStmt[5].tokenString.clone();
-                                    if let Ok(arSize) = arSizeStr.parse::<usize>() {
-                                        let newVar = Stmt::GlobVarDecl(varName.clone(), VarType::IntArray(arSize.try_into().unwrap()), curStmt[3].lineNum.clone());
-                                        retStmt = newVar;
-                                    } else {
-                                        self.reports.reportError(format!(
-                                            "In line: {}, Invlaid array size", 
-                                            curStmt[3].lineNum, 
-                                        ));
-                                        return Err("Error with variable declaration".to_string());
-                                    }
+                                    let arSize = self.arraySize(curStmt[5])?;
+                                    let newVar = Stmt::GlobVarDecl(varName.clone(), VarType::Array(Box::new(elemType), arSize), curStmt[3].lineNum.clone());
+                                    retStmt = newVar;
                                 } else {
-                                    self.reports.reportError(format!(
-                                        "In line: {}, Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : integer[arraySize]'", 
-                                        curStmt[3].lineNum, 
-                                    ));
+                                    self.reportErrorAt(ParseCode::InvalidDecl, format!("Array variable declaration incorrect. \n Must be in this format: 'variable [Variable name] : [variable type][arraySize]'"), curStmt[5]);
                                     return Err("Error with variable declaration".to_string());
                                 }
                             } else {
-                                self.reports.reportError(format!(
-                                    "In line: {}, '{}' is not a valid variable type", 
-                                    curStmt[3].lineNum, 
-                                    curStmt[3].tokenString
-                                ));
+                                self.reportErrorAt(ParseCode::InvalidDecl, format!("'{}' is not a valid variable type", curStmt[3].tokenString), curStmt[3]);
                                 return Err("Error with variable declaration".to_string());
                             }
                         }
@@ -868,11 +1258,7 @@ impl Parser{
                         let newVar = Stmt::GlobVarDecl(varName.clone(), VarType::Float, curStmt[3].lineNum.clone());
                         retStmt = newVar;
                     } else {
-                        self.reports.reportError(format!(
-                            "In line: {}, '{}' is not a valid variable type", 
-                            curStmt[3].lineNum, 
-                            curStmt[3].tokenString
-                        ));
+                        self.reportErrorAt(ParseCode::InvalidDecl, format!("'{}' is not a valid variable type", curStmt[3].tokenString), curStmt[3]);
                         return Err("Error with variable declaration".to_string());
                     }
                 }
@@ -909,6 +1295,12 @@ impl Parser{
                     k += 1;
                 }
 
+                //If the tokens ran out before the end of the statement, the ; is most likely missing from the end of its first line
+                if k == tokenList.len() {
+                    let lastTok = tokenList.iter().take_while(|tok| tok.lineNum == token.lineNum).last().unwrap_or(&token).clone();
+                    self.reportErrorAt(ParseCode::MissingSemicolon, format!("Missing ';' at the end of the statement"), &lastTok);
+                    return Err("Missing semicolon".to_string());
+                }
 
                 //Checks if the first value is an array reference or not
                 if(curStmt[1].tt == tokenTypeEnum::L_BRACKET) {
@@ -932,9 +1324,10 @@ impl Parser{
 
                     //If the end of the index was not found, error
                     if brackInd == curStmt.len() {
-                        let errMsg = format!("THIS IS AN ERROR in finding end of array index on line {}", curStmt[0].lineNum.clone());
+                        let errMsg = format!("Missing ] at the end of the index into {}", varName);
+                        let errToken = curStmt[1].clone();
                         tokenList.drain(..k+1);
-                        self.reports.reportError(errMsg.clone());
+                        self.reportErrorAt(ParseCode::Unclosed, errMsg.clone(), &errToken);
                         return Err(errMsg);
                     }
 
@@ -942,6 +1335,7 @@ impl Parser{
                     indexList.drain(0..2);
 
                     //Parses the expression
+                    let errCount = self.reports.errors.len();
                     let parsedExpr = self.parseExpr(&mut indexList);
                     let mut indexExpr: Expr;
 
@@ -951,8 +1345,7 @@ impl Parser{
                             indexExpr = expr;
                         }
                         Err(err) => {
-                            let errMsg = format!("Error on line {}: {}", tokenList[0].lineNum, err);
-                            self.reports.reportError(errMsg);
+                            self.reportIfSilent(errCount, ParseCode::InvalidExpr, format!("Invalid index into {}: {}", varName, err), &curStmt[1].clone());
                             return Err("Error with expression".to_string());
                         }
                     }
@@ -970,8 +1363,8 @@ impl Parser{
 
                 } 
                 else {
-                    let errMsg = format!("Invalid identifier on line {}", curStmt[0].lineNum.clone());
-                    self.reports.reportError(errMsg.clone());
+                    let errMsg = format!("Invalid identifier {}", curStmt[0].tokenString);
+                    self.reportError(ParseCode::UnexpectedToken, errMsg.clone());
                     return Err(errMsg.clone());
                 }
 
@@ -992,6 +1385,7 @@ impl Parser{
                                 newValueList.drain(..2);
 
                                 //Parses the new value expression
+                                let errCount = self.reports.errors.len();
                                 let parsedExpr = self.parseExpr(&mut newValueList);
                                 let mut newValueExpr: Expr;
 
@@ -1001,8 +1395,7 @@ impl Parser{
                                         newValueExpr = expr;
                                     }
                                     Err(err) => {
-                                        let errMsg = format!("Error on line {}: {}", tokenList[0].lineNum, err);
-                                        self.reports.reportError(errMsg);
+                                        self.reportIfSilent(errCount, ParseCode::InvalidExpr, format!("Invalid value assigned to {}: {}", varName, err), &curStmt[1].clone());
                                         return Err("Error with expression".to_string());
                                     }
                                 }
@@ -1018,6 +1411,7 @@ impl Parser{
                                 let mut newValueList: Vec<Token> = curStmt.iter().cloned().map(|t| t.clone()).collect();
 
                                 //Parses the expression
+                                let errCount = self.reports.errors.len();
                                 let newExpr = self.parseExpr(&mut newValueList);
                                 let retVal: Expr;
                                 match newExpr {
@@ -1025,8 +1419,7 @@ impl Parser{
                                         retVal = expr;
                                     }
                                     Err(err) => {
-                                        let errMsg = format!("Error on line {}: {}", tokenList[0].lineNum, err);
-                                        self.reports.reportError(errMsg);
+                                        self.reportIfSilent(errCount, ParseCode::InvalidExpr, format!("Invalid expression: {}", err), &curStmt[0].clone());
                                         return Err("Error with expression".to_string());
                                     }
                                 }
@@ -1039,206 +1432,123 @@ impl Parser{
                     }
                     //If it is not an operator, it is unaccounted, which is an error
                     _ => {
-                        let errMsg = format!("Error: Found {}, of group {} on line {} when an operator was expected", curStmt[1].tokenString, curStmt[1].tg, curStmt[1].lineNum);
-                        self.reports.reportError(errMsg);
+                        //Something on the next line is the next statement, so this one is missing its ;
+                        if curStmt[1].lineNum > curStmt[0].lineNum {
+                            self.reportErrorAt(ParseCode::MissingSemicolon, "Missing ';' at the end of the statement".to_string(), &curStmt[0].clone());
+                        } else {
+                            let errMsg = format!("Expected := or an operator after {} but found '{}'", curStmt[0].tokenString, curStmt[1].tokenString);
+                            self.reportErrorAt(ParseCode::UnexpectedToken, errMsg, &curStmt[1].clone());
+                        }
                         return Err("Unexpected token found".to_string());
                     }
                 }                
             }
             //The declaration of an if statement
             tokenTypeEnum::IF => {
-                //Sets up the if statement
+                //Finds the matching end if, keeping track of if statements nested inside this one
                 let mut k = 0;
-                let mut nextTok = &tokenList[k];
-                let mut curStmt: Vec<Token> = vec![];
-                let mut ifInd = 0;
-                let ifLen = tokenList.len();
-            
-                // Finds the end of the if
-                while nextTok.tt != tokenTypeEnum::END_IF {
-                    if(ifInd > ifLen) {
-                        let errMsg = format!("For If on line {}, no end if found", token.lineNum);
-                        self.reports.reportError(errMsg);
-                        return Err("No end if".to_string());
+                let mut depth = 0;
+                while k < tokenList.len() {
+                    if tokenList[k].tt == tokenTypeEnum::IF {
+                        depth += 1;
+                    } else if tokenList[k].tt == tokenTypeEnum::END_IF {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
                     }
-                    curStmt.push(nextTok.clone());
-                    k = k + 1;
-                    ifInd = ifInd + 1;
-                    nextTok = &tokenList[k];
+                    k += 1;
                 }
-                curStmt.push(nextTok.clone());
-
-                //Finds the end of the if condition
-                let mut condInt;
-                let mut ifCondition: Expr;
-                // // Extract the condition if it exists
-                if curStmt[1].tt == tokenTypeEnum::L_PAREN {
-                    let mut j = 1;
-                    let mut nextTok = &curStmt[j];
-                    let mut condStmt: Vec<Token> = vec![];
-                
-                    // Finds the end of the condition by findind the then
-                    while nextTok.tt != tokenTypeEnum::THEN {
-                        condStmt.push(nextTok.clone());
-                        j = j + 1;
-                        nextTok = &curStmt[j];
-                    }
-                    condInt = j;
-
-                    //Parses the if condition into an expression
-                    condStmt.drain(0..1);
-                    let mut parsedExpr: Expr;
-                    let scanned = self.parseExpr(&mut condStmt);                            
-                    let mut headerStmt:Expr;
-                    //Checks if it was good
-                    match scanned {
-                        Ok(stmt) => {
-                            parsedExpr = stmt;   
-                        },
-                        Err(err) => {
-                            let errMsg = format!("Error parsing if condition: {}", err);
-                            self.reports.reportError(errMsg);
-                            return Err("Error with if condition".to_string());
-                        },
-                    }
-                    ifCondition = parsedExpr;
-                } else {
-                    let errMsg = format!("Error in if statement on line: {},\nIf statement declarations must follow this format: if([condition]) then", token.lineNum);
-                    self.reports.reportError(errMsg);
-                    return Err("Error with if condition".to_string());
+                if k >= tokenList.len() {
+                    let errMsg = format!("No end if found for this if");
+                    self.reportError(ParseCode::Unclosed, errMsg);
+                    return Err("No end if".to_string());
                 }
-
-                //Checks for an else statement
-                let mut elseInd: usize = 0;
-                let mut holder = 0;
-                curStmt.drain(0..condInt+1);
-                for token in &curStmt {
-                    if(token.tt == tokenTypeEnum::ELSE){
-                        elseInd = holder;
+                let curStmt: Vec<Token> = tokenList[0..k].to_vec();
+
+                //Finds where the elsif and else arms start, skipping the arms of if statements nested in a body
+                let mut armStarts: Vec<usize> = vec![0];
+                let mut nestDepth = 0;
+                for (j, armTok) in curStmt.iter().enumerate().skip(1) {
+                    match armTok.tt {
+                        tokenTypeEnum::IF => { nestDepth += 1; }
+                        tokenTypeEnum::END_IF => { nestDepth -= 1; }
+                        tokenTypeEnum::ELSIF | tokenTypeEnum::ELSE if nestDepth == 0 => { armStarts.push(j); }
+                        _ => {}
                     }
-                    holder = holder + 1;
                 }
-
-                //If an else was found
-                if elseInd != 0 {
-                    let mut ifList = curStmt.clone();
-                    ifList.drain(elseInd..);
-                    
-                    //Parses the if body
-                    let mut newIf: Vec<Token> = ifList.iter().cloned().map(|t| t.clone()).collect();
-                    let mut ifBlock = Stmt::Block(Vec::new(), curStmt[0].lineNum.clone());
-                    let mut ifI = 0;
-                    let ifLen = newIf.len();
-                    while(!newIf.is_empty()){
-                        if(ifI > ifLen){
-                            self.reports.reportError("Infinite loop in if statement".to_string());
-                            return Err("infinite loop in if".to_string());
-                        }
-                        ifI = ifI + 1;
-                        
-                        //Scans each piece of the if body
-                        let scanned = self.parse(&mut newIf);                            
-                        let mut ifStmt:Stmt;
-                        match scanned {
-                            Ok((Some(stmt))) => {
-                                let _ = ifBlock.push_to_block(stmt.clone());
-                            },
-                            Ok((None)) => {
-                                //continue because this shouldnt happen
-                            },
-                            Err(reporting) => {
-                                let errMsg = format!("Error parsing if: {:?}", self.reports);
-                                return Err(errMsg);
-                            },
+                armStarts.push(curStmt.len());
+
+                //Parses each arm, the if and every elsif have a condition and a body, the else just has a body
+                let mut ifCondition: Option<Expr> = None;
+                let mut ifBody: Option<Stmt> = None;
+                let mut elsifArms: Vec<(Expr, Stmt)> = vec![];
+                let mut elseBody: Option<Box<Stmt>> = None;
+                for arm in armStarts.windows(2) {
+                    let armList: Vec<Token> = curStmt[arm[0]..arm[1]].to_vec();
+                    let armLine = armList[0].lineNum.clone();
+
+                    //The else has to be the last arm
+                    if armList[0].tt == tokenTypeEnum::ELSE {
+                        if arm[1] != curStmt.len() {
+                            let errMsg = format!("else must be the last arm of an if statement");
+                            self.reportErrorAt(ParseCode::InvalidStmt, errMsg, &armList[0].clone());
+                            return Err("Error with else".to_string());
                         }
+                        elseBody = Some(Box::new(self.parseBodyBlock(armList[1..].to_vec(), armLine)));
+                        continue;
                     }
 
-
-                    //Parses the else block
-                    let mut elseList = curStmt.split_off(elseInd);                    
-                    let mut newElse: Vec<Token> = elseList.iter().cloned().map(|t| t.clone()).collect();
-                    newElse.drain(0..1);
-                    newElse.drain(newElse.len() - 1..);
-                    // println!("First in else: {}", newElse[0].tokenString);
-                    let mut elseBlock = Stmt::Block(Vec::new(), curStmt[0].lineNum.clone());
-                    let mut elseI = 0;
-                    let elseLen = newElse.len();
-                    while(!newElse.is_empty()){
-                        if(elseI > elseLen){
-                            self.reports.reportError("Infinite loop in else".to_string());
-                            return Err("infinite loop in else".to_string());
+                    //Finds the then that ends the condition
+                    let armName = if armList[0].tt == tokenTypeEnum::IF { "If" } else { "Elsif" };
+                    let thenInd = armList.iter().position(|t| t.tt == tokenTypeEnum::THEN);
+                    let thenInd = match thenInd {
+                        Some(thenInd) if (thenInd > 2) && (armList[1].tt == tokenTypeEnum::L_PAREN) => thenInd,
+                        _ => {
+                            let errMsg = format!("Invalid {} condition\n{} statement declarations must follow this format: {}([condition]) then", armName.to_lowercase(), armName, armName.to_lowercase());
+                            self.reportErrorAt(ParseCode::InvalidStmt, errMsg, &armList.get(1).unwrap_or(&armList[0]).clone());
+                            return Err(format!("Error with {} condition", armName.to_lowercase()));
                         }
-                        let scanned = self.parse(&mut newElse);                            
-                        let mut elseStmt:Stmt;
-                        match scanned {
-                            Ok((Some(stmt))) => {
-                                let _ = elseBlock.push_to_block(stmt.clone());
-                                elseI = elseI + 1;
-                            },
-                            Ok((None)) => {
-                                //continue because this shouldnt happen
-                                elseI = elseI + 1;
+                    };
 
-                            },
-                            Err(reporting) => {
-                                let errMsg = format!("Error parsing else: {:?}", self.reports);
-                                return Err(errMsg);
-                            },
+                    //Parses the condition, the closing ) ends the expression
+                    let mut condStmt: Vec<Token> = armList[2..thenInd].to_vec();
+                    let armCondition: Expr;
+                    let errCount = self.reports.errors.len();
+                    match self.parseExpr(&mut condStmt) {
+                        Ok(expr) => {
+                            armCondition = expr;
                         }
-                        
-                    }
-
-                    //Converts the blocks to boxes
-                    let ifBox = Box::new(ifBlock);
-                    let elseBox = Box::new(elseBlock);
-
-                    //Finishes up and returns
-                    let retStmt = Stmt::If(ifCondition, ifBox, Some(elseBox), curStmt[0].lineNum.clone());
-                    tokenList.drain(0..k+2);
-                    return Ok(Some(retStmt));
-                } 
-                //If there is no else
-                else {
-                    //Sets up stuff
-                    let mut ifList = curStmt.clone();
-                    ifList.drain(ifList.len() - 1..);                    
-
-                    //Parses the header
-                    let mut newIf: Vec<Token> = ifList.iter().cloned().map(|t| t.clone()).collect();
-                    let mut ifBlock = Stmt::Block(Vec::new(), curStmt[0].lineNum.clone());
-                    let mut ifI = 0;
-                    let ifLen = newIf.len();
-                    //parses the if body
-                    while(!newIf.is_empty()){
-                        if(ifI > ifLen){
-                            self.reports.reportError("Infinite loop in if statement".to_string());
-                            return Err("infinite loop in if".to_string());
-                        }
-                        ifI = ifI + 1;
-                        let scanned = self.parse(&mut newIf);                            
-                        let mut ifStmt:Stmt;
-                        match scanned {
-                            Ok((Some(stmt))) => {
-                                let _ = ifBlock.push_to_block(stmt.clone());
-                            },
-                            Ok((None)) => {
-                                //continue because this shouldnt happen
-                            },
-                            Err(reporting) => {
-                                let errMsg = format!("Error parsing if: {:?}", self.reports);
-                                return Err(errMsg);
-                            },
+                        Err(err) => {
+                            let errMsg = format!("Invalid {} condition: {}", armName.to_lowercase(), err);
+                            self.reportIfSilent(errCount, ParseCode::InvalidExpr, errMsg, &armList[0].clone());
+                            return Err(format!("Error with {} condition", armName.to_lowercase()));
                         }
                     }
 
-                    //Converts the blocks to boxes
-                    let ifBox = Box::new(ifBlock);
-
-                    //Finishes up and returns
-                    let retStmt = Stmt::If(ifCondition, ifBox, None, curStmt[0].lineNum.clone());
-                    tokenList.drain(0..k+2);
-                    return Ok(Some(retStmt));
+                    let armBody = self.parseBodyBlock(armList[thenInd + 1..].to_vec(), armLine);
+                    if armList[0].tt == tokenTypeEnum::IF {
+                        ifCondition = Some(armCondition);
+                        ifBody = Some(armBody);
+                    } else {
+                        elsifArms.push((armCondition, armBody));
+                    }
                 }
+
+                //Finishes up and returns, dropping the end if and the ; after it
+                //The first arm always starts with the if token, but a missing one is reported rather than assumed
+                let (ifCondition, ifBody) = match (ifCondition, ifBody) {
+                    (Some(ifCondition), Some(ifBody)) => (ifCondition, ifBody),
+                    _ => {
+                        let errMsg = format!("if statement has no condition and body");
+                        self.reportError(ParseCode::InvalidStmt, errMsg);
+                        return Err("Error with if statement".to_string());
+                    }
+                };
+                let retStmt = Stmt::If(ifCondition, Box::new(ifBody), elsifArms, elseBody, token.lineNum.clone());
+                self.expectEndSemicolon(tokenList, k)?;
+                tokenList.drain(0..k+2);
+                return Ok(Some(retStmt));
             }
             //The declaration of a for loop
             tokenTypeEnum::FOR => {
@@ -1251,9 +1561,9 @@ impl Parser{
             
                 // Finds the end of the for
                 while nextTok.tt != tokenTypeEnum::END_FOR {
-                    if(forInd > forLen) {
-                        let errMsg = format!("For for on line {}, no end for found", token.lineNum);
-                        self.reports.reportError(errMsg);
+                    if(k + 1 >= forLen) {
+                        let errMsg = format!("No end for found for this for");
+                        self.reportError(ParseCode::Unclosed, errMsg);
                         return Err("No end for".to_string());
                     }
                     curStmt.push(nextTok.clone());
@@ -1268,23 +1578,52 @@ impl Parser{
                 let mut forCond: Expr;
                 // // Extract the condition if it exists
                 if curStmt[1].tt == tokenTypeEnum::L_PAREN {
-                    let mut j = 1;
-                    let mut nextTok = &curStmt[j];
-                    let mut condStmt: Vec<Token> = vec![];
-                
-                    // Finds the end of the condition by findind the paren
-                    while nextTok.tt != tokenTypeEnum::R_PAREN {
-                        condStmt.push(nextTok.clone());
-                        j = j + 1;
-                        nextTok = &curStmt[j];
+                    //Finds the ) that closes the header, skipping over the parentheses and calls inside the condition
+                    //The header only has the one ; so a second one means the ) is missing
+                    let mut depth = 0;
+                    let mut semicolons = 0;
+                    let mut closeInd: Option<usize> = None;
+                    for (j, headerTok) in curStmt.iter().enumerate().skip(2) {
+                        match headerTok.tt {
+                            tokenTypeEnum::L_PAREN | tokenTypeEnum::PROCEDURE_CALL => {
+                                depth += 1;
+                            }
+                            tokenTypeEnum::R_PAREN => {
+                                if depth == 0 {
+                                    closeInd = Some(j);
+                                    break;
+                                }
+                                depth -= 1;
+                            }
+                            tokenTypeEnum::SEMICOLON if depth == 0 => {
+                                semicolons += 1;
+                                if semicolons > 1 {
+                                    break;
+                                }
+                            }
+                            _ => {}
+                        }
                     }
+                    let j = match closeInd {
+                        Some(j) => j,
+                        None => {
+                            self.reportErrorAt(ParseCode::Unclosed, format!("Missing ) at the end of the for loop header"), &curStmt[1].clone());
+                            return Err("Error with for condition".to_string());
+                        }
+                    };
                     condInt = j;
 
-                    condStmt.push(nextTok.clone());
-                    condStmt.drain(0..1);
+                    //Takes the assignment and condition, ending the condition with a ; so the parser can respond correctly
+                    let mut condStmt: Vec<Token> = curStmt[2..j + 1].to_vec();
+                    if let Some(endTok) = condStmt.last_mut() {
+                        endTok.tokenString = ";".to_string();
+                        endTok.tt = tokenTypeEnum::SEMICOLON;
+                        endTok.tg = tokenGroup::SYMBOL;
+                    }
 
                     //Parses the for loop condition
-                    let mut parsedStmt: Stmt = Stmt::StringLiteral("NONE".to_string(), "0".to_string());
+                    let mut parsedStmt: Stmt = Stmt::StringLiteral("NONE".to_string(), 0);
+                    let errCount = self.reports.errors.len();
                     let scanned = self.parse(&mut condStmt);                            
                     match scanned {
                         Ok((Some(stmt))) => {
@@ -1294,22 +1633,23 @@ impl Parser{
                             //this shoudlnt happen
                         },
                         Err(err) => {
-                            let errMsg = format!("Error parsing for condition: {}", err);
-                            self.reports.reportError(errMsg);
+                            let errMsg = format!("Invalid for loop assignment: {}", err);
+                            self.reportIfSilent(errCount, ParseCode::InvalidStmt, errMsg, &curStmt[1].clone());
                             return Err("Error with for condition".to_string());
                         },
                     }
                     forDecl = parsedStmt;
 
                     //Parses the for condition
+                    let errCount = self.reports.errors.len();
                     let scanned = self.parseExpr(&mut condStmt);                            
                     match scanned {
                         Ok((stmt)) => {
                             forCond = stmt;
                         },
                         Err(err) => {
-                            let errMsg = format!("Error parsing for condition: {}", err);
-                            self.reports.reportError(errMsg);
+                            let errMsg = format!("Invalid for condition: {}", err);
+                            self.reportIfSilent(errCount, ParseCode::InvalidExpr, errMsg, &curStmt[1].clone());
                             return Err("Error with for condition".to_string());
                         },
                     }
@@ -1317,8 +1657,8 @@ impl Parser{
                 } 
                 //If there is an error in the for loop
                 else {
-                    let errMsg = format!("Error in FOR statement on line: {},\nFor statement declarations must follow this format: for([condition]) then", token.lineNum);
-                    self.reports.reportError(errMsg);
+                    let errMsg = format!("Invalid for loop header\nFor statement declarations must follow this format: for([condition]) then");
+                    self.reportErrorAt(ParseCode::InvalidStmt, errMsg, &curStmt[1].clone());
                     return Err("Error with for condition".to_string());
                 }
 
@@ -1335,11 +1675,11 @@ impl Parser{
                 let ifLen = newFor.len();
                 while(!newFor.is_empty()){
                     if(ifI > ifLen){
-                        self.reports.reportError("Infinite loop in if statement".to_string());
+                        self.reportError(ParseCode::Internal, "Infinite loop in if statement".to_string());
                         return Err("infinite loop in if".to_string());
                     }
                     ifI = ifI + 1;
-                    let scanned = self.parse(&mut newFor);                            
+                    let scanned = self.parseBlockStmt(&mut newFor);                            
                     let mut ifStmt:Stmt;
                     match scanned {
                         Ok((Some(stmt))) => {
@@ -1349,8 +1689,7 @@ impl Parser{
                             //continue as this shouldnt happen
                         },
                         Err(reporting) => {
-                            let errMsg = format!("Error parsing if: {:?}", self.reports);
-                            return Err(errMsg);
+                            //Already reported, carries on with the rest of the block
                         },
                     }
                 }
@@ -1360,9 +1699,244 @@ impl Parser{
 
                 //Finishes up and returns
                 let retStmt = Stmt::For(forDecl.into(), forCond, forBox, tokenList[0].lineNum.clone());
+                self.expectEndSemicolon(tokenList, k)?;
+                tokenList.drain(0..k+2);
+                return Ok(Some(retStmt));
+            }
+            //The declaration of a while loop
+            tokenTypeEnum::WHILE => {
+                //Finds the matching end while, keeping track of while loops nested inside this one
+                let mut k = 0;
+                let mut depth = 0;
+                while k < tokenList.len() {
+                    if tokenList[k].tt == tokenTypeEnum::WHILE {
+                        depth += 1;
+                    } else if tokenList[k].tt == tokenTypeEnum::END_WHILE {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    k += 1;
+                }
+                if k >= tokenList.len() {
+                    let errMsg = format!("No end while found for this while");
+                    self.reportError(ParseCode::Unclosed, errMsg);
+                    return Err("No end while".to_string());
+                }
+                let curStmt: Vec<Token> = tokenList[0..k].to_vec();
+
+                //Finds the ) that closes the condition
+                let mut condEnd = 0;
+                if (curStmt.len() > 1) && (curStmt[1].tt == tokenTypeEnum::L_PAREN) {
+                    let mut parenDepth = 0;
+                    for (j, condTok) in curStmt.iter().enumerate().skip(1) {
+                        if condTok.tt == tokenTypeEnum::L_PAREN {
+                            parenDepth += 1;
+                        } else if condTok.tt == tokenTypeEnum::R_PAREN {
+                            parenDepth -= 1;
+                            if parenDepth == 0 {
+                                condEnd = j;
+                                break;
+                            }
+                        }
+                    }
+                }
+                if condEnd == 0 {
+                    let errMsg = format!("Invalid while condition\nWhile statement declarations must follow this format: while([condition])");
+                    self.reportErrorAt(ParseCode::InvalidStmt, errMsg, &curStmt.get(1).unwrap_or(&curStmt[0]).clone());
+                    return Err("Error with while condition".to_string());
+                }
+
+                //Parses the condition, the closing ) ends the expression
+                let mut condStmt: Vec<Token> = curStmt[2..condEnd + 1].to_vec();
+                let whileCond: Expr;
+                let errCount = self.reports.errors.len();
+                match self.parseExpr(&mut condStmt) {
+                    Ok(expr) => {
+                        whileCond = expr;
+                    }
+                    Err(err) => {
+                        let errMsg = format!("Invalid while condition: {}", err);
+                        self.reportIfSilent(errCount, ParseCode::InvalidExpr, errMsg, &curStmt[1].clone());
+                        return Err("Error with while condition".to_string());
+                    }
+                }
+
+                //Parses the while body
+                let mut newWhile: Vec<Token> = curStmt[condEnd + 1..].to_vec();
+                let mut whileBlock = Stmt::Block(Vec::new(), tokenList[0].lineNum.clone());
+                let mut whileI = 0;
+                let whileLen = newWhile.len();
+                while(!newWhile.is_empty()){
+                    if(whileI > whileLen){
+                        self.reportError(ParseCode::Internal, "Infinite loop in while statement".to_string());
+                        return Err("infinite loop in while".to_string());
+                    }
+                    whileI = whileI + 1;
+                    let scanned = self.parseBlockStmt(&mut newWhile);
+                    match scanned {
+                        Ok((Some(stmt))) => {
+                            let _ = whileBlock.push_to_block(stmt.clone());
+                        },
+                        Ok((None)) => {
+                            //continue as this shouldnt happen
+                        },
+                        Err(reporting) => {
+                            //Already reported, carries on with the rest of the block
+                        },
+                    }
+                }
+
+                //Finishes up and returns, dropping the end while and the ; after it
+                let retStmt = Stmt::While(whileCond, Box::new(whileBlock), tokenList[0].lineNum.clone());
+                self.expectEndSemicolon(tokenList, k)?;
+                tokenList.drain(0..k+2);
+                return Ok(Some(retStmt));
+            }
+            //The declaration of a case statement
+            tokenTypeEnum::CASE => {
+                //Finds the matching end case, keeping track of case statements nested inside this one
+                let mut k = 0;
+                let mut depth = 0;
+                while k < tokenList.len() {
+                    if tokenList[k].tt == tokenTypeEnum::CASE {
+                        depth += 1;
+                    } else if tokenList[k].tt == tokenTypeEnum::END_CASE {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    k += 1;
+                }
+                if k >= tokenList.len() {
+                    let errMsg = format!("No end case found for this case");
+                    self.reportError(ParseCode::Unclosed, errMsg);
+                    return Err("No end case".to_string());
+                }
+                let curStmt: Vec<Token> = tokenList[0..k].to_vec();
+
+                //Finds the ) that closes the value being switched on, which has to be followed by is
+                let mut selEnd = 0;
+                if (curStmt.len() > 1) && (curStmt[1].tt == tokenTypeEnum::L_PAREN) {
+                    let mut parenDepth = 0;
+                    for (j, selTok) in curStmt.iter().enumerate().skip(1) {
+                        if selTok.tt == tokenTypeEnum::L_PAREN {
+                            parenDepth += 1;
+                        } else if selTok.tt == tokenTypeEnum::R_PAREN {
+                            parenDepth -= 1;
+                            if parenDepth == 0 {
+                                selEnd = j;
+                                break;
+                            }
+                        }
+                    }
+                }
+                if (selEnd == 0) || (selEnd + 1 >= curStmt.len()) || (curStmt[selEnd + 1].tt != tokenTypeEnum::IS) {
+                    let errMsg = format!("Invalid case value\nCase statement declarations must follow this format: case([value]) is");
+                    self.reportErrorAt(ParseCode::InvalidStmt, errMsg, &curStmt.get(1).unwrap_or(&curStmt[0]).clone());
+                    return Err("Error with case value".to_string());
+                }
+
+                //Parses the value being switched on, the closing ) ends the expression
+                let mut selStmt: Vec<Token> = curStmt[2..selEnd + 1].to_vec();
+                let caseValue: Expr;
+                let errCount = self.reports.errors.len();
+                match self.parseExpr(&mut selStmt) {
+                    Ok(expr) => {
+                        caseValue = expr;
+                    }
+                    Err(err) => {
+                        let errMsg = format!("Invalid case value: {}", err);
+                        self.reportIfSilent(errCount, ParseCode::InvalidExpr, errMsg, &curStmt[1].clone());
+                        return Err("Error with case value".to_string());
+                    }
+                }
+
+                //Finds where each arm starts, skipping the whens of case statements nested in an arm
+                let armTokens: Vec<Token> = curStmt[selEnd + 2..].to_vec();
+                if armTokens.is_empty() || (armTokens[0].tt != tokenTypeEnum::WHEN) {
+                    let errMsg = format!("Case statement must start its arms with when");
+                    self.reportErrorAt(ParseCode::InvalidStmt, errMsg, &armTokens.first().unwrap_or(&curStmt[selEnd + 1]).clone());
+                    return Err("Error with case arms".to_string());
+                }
+                let mut armStarts: Vec<usize> = vec![];
+                let mut nestDepth = 0;
+                for (j, armTok) in armTokens.iter().enumerate() {
+                    match armTok.tt {
+                        tokenTypeEnum::CASE => { nestDepth += 1; }
+                        tokenTypeEnum::END_CASE => { nestDepth -= 1; }
+                        tokenTypeEnum::WHEN if nestDepth == 0 => { armStarts.push(j); }
+                        _ => {}
+                    }
+                }
+                armStarts.push(armTokens.len());
+
+                //Parses each arm, when [value] then [statements] or when others then [statements]
+                let mut arms: Vec<(Expr, Stmt)> = vec![];
+                let mut othersBody: Option<Box<Stmt>> = None;
+                for arm in armStarts.windows(2) {
+                    let armList: Vec<Token> = armTokens[arm[0]..arm[1]].to_vec();
+                    let armLine = armList[0].lineNum.clone();
+                    let thenInd = match armList.iter().position(|t| t.tt == tokenTypeEnum::THEN) {
+                        Some(thenInd) if thenInd > 1 => thenInd,
+                        _ => {
+                            let errMsg = format!("Invalid case arm\nCase arms must follow this format: when [value] then");
+                            self.reportErrorAt(ParseCode::InvalidStmt, errMsg, &armList[0].clone());
+                            return Err("Error with case arm".to_string());
+                        }
+                    };
+                    let armBody = self.parseBodyBlock(armList[thenInd + 1..].to_vec(), armLine.clone());
+
+                    //The others arm catches every value not listed, so it has to come last
+                    if (thenInd == 2) && (armList[1].tt == tokenTypeEnum::OTHERS) {
+                        if othersBody.is_some() || (arm[1] != armTokens.len()) {
+                            let errMsg = format!("when others must be the last arm of a case statement");
+                            self.reportErrorAt(ParseCode::InvalidStmt, errMsg, &armList[1].clone());
+                            return Err("Error with case arm".to_string());
+                        }
+                        othersBody = Some(Box::new(armBody));
+                        continue;
+                    }
+
+                    //Parses the arm value, ending it with a ; so it parses like any other expression
+                    let mut valueStmt: Vec<Token> = armList[1..thenInd].to_vec();
+                    valueStmt.push(Token::new(tokenTypeEnum::SEMICOLON, ";".to_string(), armLine.clone(), tokenGroup::SYMBOL));
+                    let errCount = self.reports.errors.len();
+                    match self.parseExpr(&mut valueStmt) {
+                        Ok(armValue) => {
+                            arms.push((armValue, armBody));
+                        }
+                        Err(err) => {
+                            let errMsg = format!("Invalid case arm value: {}", err);
+                            self.reportIfSilent(errCount, ParseCode::InvalidExpr, errMsg, &armList[1].clone());
+                            return Err("Error with case arm".to_string());
+                        }
+                    }
+                }
+
+                //Finishes up and returns, dropping the end case and the ; after it
+                let retStmt = Stmt::Case(caseValue, arms, othersBody, tokenList[0].lineNum.clone());
+                self.expectEndSemicolon(tokenList, k)?;
                 tokenList.drain(0..k+2);
                 return Ok(Some(retStmt));
             }
+            //Leaving a loop early (break or exit) or skipping to its next check (continue)
+            tokenTypeEnum::BREAK | tokenTypeEnum::CONTINUE => {
+                if (tokenList.len() < 2) || (tokenList[1].tt != tokenTypeEnum::SEMICOLON) {
+                    let errMsg = format!("Missing ';' after {}", token.tokenString);
+                    self.reportErrorAt(ParseCode::MissingSemicolon, errMsg, &token.clone());
+                    return Err(format!("No ; after {}", token.tokenString));
+                }
+                let retStmt = if token.tt == tokenTypeEnum::BREAK {
+                    Stmt::Break(token.lineNum.clone())
+                } else {
+                    Stmt::Continue(token.lineNum.clone())
+                };
+                tokenList.drain(0..2);
+                return Ok(Some(retStmt));
+            }
             //When a procedure is called but not assigned to something            
             tokenTypeEnum::PROCEDURE => {
                 self.scope += 1;
@@ -1374,8 +1948,12 @@ impl Parser{
                 let mut scope = 0;
                 let mut curStmt: Vec<Token> = vec![];
                 curStmt.push(token.clone());
+                //A forward declaration ends at its ';' instead of an end procedure
+                let forward = Self::isForwardDecl(tokenList, 0);
                 while (k < tokenList.len()) {
-                    if(nextTok.tt == tokenTypeEnum::PROCEDURE){
+                    if forward && ((nextTok.tt == tokenTypeEnum::SEMICOLON) || (nextTok.tt == tokenTypeEnum::PROCEDURE) || (nextTok.tt == tokenTypeEnum::BEGIN)) {
+                        break;
+                    } else if(nextTok.tt == tokenTypeEnum::PROCEDURE) && !Self::isForwardDecl(tokenList, k){
                         scope = scope + 1;
                     } else if ((nextTok.tt == tokenTypeEnum::END_PROCEDURE)){
                         if(scope != 0){
@@ -1387,6 +1965,12 @@ impl Parser{
 
                     } 
                     curStmt.push(nextTok.clone());
+                    if(k + 1 >= tokenList.len()){
+                        let errMsg = format!("No end procedure found for this procedure");
+                        self.reportError(ParseCode::Unclosed, errMsg);
+                        self.scope -= 1;
+                        return Err("No end procedure".to_string());
+                    }
                     k = k + 1;
                     nextTok = &tokenList[k];
 
@@ -1394,39 +1978,52 @@ impl Parser{
                 curStmt.push(nextTok.clone());
                 
                 //Gets the procedure return type
+                //A procedure without a : [type] before its parameters returns nothing, so its name is right before the (
                 let procId = &curStmt[1].tokenString.clone();
-                let procType = VarType::new(&curStmt[3].tokenString);    
+                let returnsValue = curStmt[1].tt != tokenTypeEnum::PROCEDURE_CALL;
+                let mut j = if returnsValue { 4 } else { 2 };
+                //The parameter list has to start before the end procedure
+                if curStmt.len() <= j + 1 {
+                    let errMsg = format!("Invalid procedure declaration: {}", procId);
+                    self.reportErrorAt(ParseCode::InvalidProcedure, errMsg, &curStmt[1].clone());
+                    self.scope -= 1;
+                    return Err("Error with procedure declaration".to_string());
+                }
                 let mut procedureType:VarType;
                 // Gets the procedure type
-                match procType {
-                    Ok(varType) => {
-                        procedureType = varType;
-                    }
-                    Err(err) => {
-                        let errMsg = format!("Error determining procedure type: {}", err);
-                        self.reports.reportError(errMsg.clone());
-                        return Err("Error with procedure type".to_string());
+                if returnsValue {
+                    let procType = VarType::new(&curStmt[3].tokenString);    
+                    match procType {
+                        Ok(varType) => {
+                            procedureType = varType;
+                        }
+                        Err(err) => {
+                            let errMsg = format!("Invalid procedure type: {}", err);
+                            self.reportErrorAt(ParseCode::InvalidProcedure, errMsg, &curStmt[3].clone());
+                            return Err("Error with procedure type".to_string());
+                        }
                     }
+                } else {
+                    procedureType = VarType::Void;
                 }
 
                 //Initialized param stuff
                 let mut paramList = Stmt::Block(Vec::new(), curStmt[0].lineNum.clone());
-                let mut j = 4;
                 //Finds and extracts the parameters
-                if(curStmt[3].tt != tokenTypeEnum::PROCEDURE_CALL){
-                    let errMsg = format!("Invalid procedure declaration: {} on line {}", &curStmt[4].tt, &curStmt[4].lineNum);
-                    self.reports.reportError(errMsg.clone());
+                if returnsValue && (curStmt[3].tt != tokenTypeEnum::PROCEDURE_CALL){
+                    let errMsg = format!("Expected the procedure name and ( after the return type but found '{}'", &curStmt[3].tokenString);
+                    self.reportErrorAt(ParseCode::InvalidProcedure, errMsg, &curStmt[3].clone());
                     return Err("Error with procedure call".to_string());
                 } 
                 //Finds the end of the procedure call
                 else {
                     let mut nextTok = &curStmt[j];
                     let mut paramTokens: Vec<Token> = vec![];
-                    let decLine = curStmt[4].lineNum.clone();
+                    let decLine = curStmt[j].lineNum.clone();
                     while nextTok.tt != tokenTypeEnum::R_PAREN  {
-                        if(nextTok.lineNum != decLine){
-                            let errMsg = format!("Error with procedure reference on line {}, no closing parentheses found", curStmt[0].lineNum.clone());
-                            self.reports.reportError(errMsg.clone());
+                        if(nextTok.lineNum != decLine) || (j + 1 >= curStmt.len()){
+                            let errMsg = format!("Missing ) at the end of the parameters of {}", procId);
+                            self.reportErrorAt(ParseCode::Unclosed, errMsg, &curStmt[if returnsValue { 3 } else { 1 }].clone());
                             return Err("Error with procedure reference".to_string());                            
                         } else {
                             paramTokens.push(nextTok.clone());
@@ -1442,27 +2039,20 @@ impl Parser{
                         if(curToken.tt == tokenTypeEnum::COMMA) {
                             //Parse the parameters
                             let tokenString: String = ";".to_string();
-                            let semicolon = Token::new(crate::tokenTypeEnum::SEMICOLON,tokenString, decLine.to_string(), tokenGroup::SYMBOL);
+                            let semicolon = Token::new(crate::tokenTypeEnum::SEMICOLON,tokenString, decLine, tokenGroup::SYMBOL);
                             curParam.push(semicolon.clone());
                             let mut newCurParam: Vec<Token> = curParam.iter().cloned().map(|t| t.clone()).collect();
-                            let scanParam = self.parse(&mut newCurParam);
+                            let errCount = self.reports.errors.len();
+                            let scanParam = self.parseParam(&mut newCurParam);
                             let mut paramStmt: Stmt;
                             match scanParam {
                                 Ok((Some(stmt))) => {
                                     paramStmt = stmt;
                                     let _ = paramList.push_to_block(paramStmt);
                                 },
-                                Ok((None)) => {
-                                    let errMsg = format!("In line: {}, Error with parameter", curStmt[0].lineNum);
-                                    self.reports.reportError(errMsg.clone());
-                                    let errMsg = format!("Error with procedure statement on line {}", curStmt[0].lineNum.clone());
-                                    return Err(errMsg);
-                                },
-                                Err(reporting) => {
-                                    let errMsg = format!("In line: {}, Error with parameter", curStmt[0].lineNum);
-                                    self.reports.reportError(errMsg.clone());
-                                    let errMsg = format!("Error with procedure statement on line {}", curStmt[0].lineNum.clone());
-                                    return Err(errMsg);
+                                Ok((None)) | Err(_) => {
+                                    self.reportIfSilent(errCount, ParseCode::InvalidProcedure, format!("Invalid parameter in {}", procId), &curParam[0].clone());
+                                    return Err("Error with procedure parameters".to_string());
                                 },
                             }
                             curParam = vec![];
@@ -1475,25 +2065,20 @@ impl Parser{
                     if((paramTokens.len().clone() as i32) != 0){
                         //Parse the parameter
                         let tokenString: String = ";".to_string();
-                        let semicolon = Token::new(crate::tokenTypeEnum::SEMICOLON,tokenString, decLine.to_string(), tokenGroup::SYMBOL);
+                        let semicolon = Token::new(crate::tokenTypeEnum::SEMICOLON,tokenString, decLine, tokenGroup::SYMBOL);
                         curParam.push(semicolon.clone());
                         let mut newCurParam: Vec<Token> = curParam.iter().cloned().map(|t| t.clone()).collect();
-                        let scanParam = self.parse(&mut newCurParam);
+                        let errCount = self.reports.errors.len();
+                        let scanParam = self.parseParam(&mut newCurParam);
                         let mut paramStmt: Stmt;
                         match scanParam {
                             Ok((Some(stmt))) => {
                                 paramStmt = stmt; 
                                 let _ = paramList.push_to_block(paramStmt);
                             },
-                            Ok((None)) => {
-                                self.reports.reportError(format!(
-                                    "In line: {}, Error with parameter", curStmt[0].lineNum
-                                ));
-                                return Err("Error with parsing parameters".to_string());
-                            },
-                            Err(reporting) => {
-                                self.reports.reportError(format!("In line: {}, Error with condition", curStmt[0].lineNum));
-                                return(Err("Error with parameter".to_string()));
+                            Ok((None)) | Err(_) => {
+                                self.reportIfSilent(errCount, ParseCode::InvalidProcedure, format!("Invalid parameter in {}", procId), &curParam[0].clone());
+                                return Err("Error with procedure parameters".to_string());
                             },
                         }
                     }
@@ -1506,14 +2091,28 @@ impl Parser{
                 //Modifies the current list
                 curStmt.drain(0..j+1);
 
+                //A forward declaration only has the signature, the procedure is defined later in the same header
+                if forward {
+                    if (curStmt.len() != 2) || (curStmt[1].tt != tokenTypeEnum::SEMICOLON) {
+                        let errMsg = format!("Forward declaration of procedure {} must end with forward; after the parameters", procId.clone());
+                        self.reportErrorAt(ParseCode::InvalidProcedure, errMsg, &curStmt.get(1).unwrap_or(&curStmt[0]).clone());
+                        self.scope -= 1;
+                        return Err("Error with forward declaration".to_string());
+                    }
+                    let procedureAst = Stmt::ProcForward(procedureType, procId.clone(), Box::new(paramList), tokenList[0].lineNum.clone());
+                    self.scope -= 1;
+                    tokenList.drain(0..k + 1);
+                    return Ok(Some(procedureAst));
+                }
+
                 //Finds where the header ends and the body begins
                 let mut beginInt = 0;
                 let mut beginScope = 0;
                 let iterTokList = curStmt.clone();
-                for token in iterTokList{
+                for (tokInd, token) in iterTokList.iter().enumerate(){
                     if (token.tt == tokenTypeEnum::BEGIN) && (beginScope == 0){
                         break;
-                    } else if (token.tt == tokenTypeEnum::PROCEDURE) {
+                    } else if (token.tt == tokenTypeEnum::PROCEDURE) && !Self::isForwardDecl(&iterTokList, tokInd) {
                         beginScope = beginScope + 1;
                         beginInt = beginInt + 1;
                     } else if (token.tt == tokenTypeEnum::END_PROCEDURE){
@@ -1535,10 +2134,10 @@ impl Parser{
                 let headerLen = newHeader.len();
                 while(!newHeader.is_empty()){
                     if(headerI > headerLen){
-                        self.reports.reportError("Infinite loop in procedure header".to_string());
+                        self.reportError(ParseCode::Internal, "Infinite loop in procedure header".to_string());
                         return Err("infinite loop in procedure header".to_string());
                     }
-                    let scanned = self.parse(&mut newHeader);                            
+                    let scanned = self.parseBlockStmt(&mut newHeader);                            
                     let mut headerStmt:Stmt;
                     match scanned {
                         Ok((Some(stmt))) => {
@@ -1548,9 +2147,7 @@ impl Parser{
                             //continue becuase this shoudlnt happen
                         },
                         Err(reporting) => {
-                            let errMsg = format!("Error parsing header: {:?}", self.reports);
-                            self.reports.reportError(errMsg.clone());
-                            return Err("Error parsing procedure header".to_string());
+                            headerI += 1;
                         },
                     }
                 }     
@@ -1559,18 +2156,23 @@ impl Parser{
                 // println!("Finished parsing procedure header: ");
                 // headerBlock.display(0);
 
-                //Parses the procedure body
+                //Parses the procedure body, which has to start with begin
                 let mut newBody: Vec<Token> = bodyList.iter().cloned().map(|t| t.clone()).collect();
+                if newBody.first().map(|tok| tok.tt != tokenTypeEnum::BEGIN).unwrap_or(true) {
+                    let errMsg = format!("No begin found for the body of procedure {}\nThe procedure body must start with 'begin'", procId.clone());
+                    self.reportErrorAt(ParseCode::InvalidProcedure, errMsg, &newBody.first().unwrap_or(&tokenList[0]).clone());
+                    return Err("Error with procedure body".to_string());
+                }
                 newBody.drain(0..1);
                 let mut bodyBlock = Stmt::Block(Vec::new(), tokenList[0].lineNum.clone());
                 let mut bodyI = 0;
                 let bodyLen = newBody.len();
                 while(!newBody.is_empty()){
                     if(bodyI > bodyLen){
-                        self.reports.reportError("Infinite loop in body".to_string());
+                        self.reportError(ParseCode::Internal, "Infinite loop in body".to_string());
                         return Err("infinite loop in body".to_string());
                     }
-                    let scanned = self.parse(&mut newBody);                            
+                    let scanned = self.parseBlockStmt(&mut newBody);                            
                     let mut headerStmt:Stmt;
                     match scanned {
                         Ok((Some(stmt))) => {
@@ -1582,8 +2184,7 @@ impl Parser{
                             bodyI = bodyI + 1;
                         },
                         Err(reporting) => {
-                            let errMsg = format!("Error parsing procedure body: {:?}", self.reports);
-                            return Err(errMsg);
+                            bodyI = bodyI + 1;
                         },
                     }
                     
@@ -1603,13 +2204,22 @@ impl Parser{
                 
                 self.scope -= 1;
 
+                self.expectEndSemicolon(tokenList, k)?;
                 tokenList.drain(0..k + 2);
                 return Ok(Some(procedureAst));
             }
             //For return statement
             tokenTypeEnum::RETURN => {
+                //The return has to end with a ; whether or not it has a value
+                let semiInd = match tokenList.iter().position(|tok| tok.tt == tokenTypeEnum::SEMICOLON) {
+                    Some(semiInd) => semiInd,
+                    None => {
+                        self.reportErrorAt(ParseCode::MissingSemicolon, "Missing ; at the end of the return statement".to_string(), &token.clone());
+                        return Err("Missing ;".to_string());
+                    }
+                };
                 //Checks if there is a value being returned
-                if tokenList[1].tt != tokenTypeEnum::SEMICOLON {
+                if semiInd != 1 {
                     //Initializes the variable that is being referenced first
                     let mut varRef:Expr;
                     //Initializes the return statement (I DONT THINK THIS IS NEEDED)
@@ -1633,7 +2243,8 @@ impl Parser{
 
                     curStmt.drain(0..1);
 
-                    if(curStmt[0].tt == tokenTypeEnum::L_PAREN){
+                    //Only drops the parentheses when they wrap the whole value
+                    if(curStmt[0].tt == tokenTypeEnum::L_PAREN) && (Parser::primaryEnd(&curStmt, 0) == Some(curStmt.len() - 2)){
                         curStmt.drain(0..1);
                         curStmt.remove(curStmt.len() - 2);
                         
@@ -1660,7 +2271,7 @@ impl Parser{
                 else {
                     let retValue = Expr::VarRef("".to_string());
                     let retStmt = Stmt::Return(retValue, tokenList[0].lineNum.clone());
-                    tokenList.drain(0..3);
+                    tokenList.drain(0..2);
 
                     return(Ok(Some(retStmt)));
                 }
@@ -1680,7 +2291,7 @@ impl Parser{
             //An in constant is found
             tokenTypeEnum::INT => {
                 let mut retStmt:Stmt;
-                println!("integer");
+                // println!("integer");
                 let mut k = 0;
                 let mut nextTok = &tokenList[k];
                 let mut curStmt: Vec<&Token> = vec![];
@@ -1703,9 +2314,9 @@ impl Parser{
                             op1Expr = expr;
                         }
                         Err(err) => {
-                            println!("Error parsing operand 1");
-                            let errMsg = format!("Error with operand 1 on line {}: {}", curStmt[0].lineNum, err);
-                            self.reports.reportError(errMsg);
+                            // println!("Error parsing operand 1");
+                            let errMsg = format!("Invalid constant {}: {}", curStmt[0].tokenString, err);
+                            self.reportErrorAt(ParseCode::InvalidExpr, errMsg, curStmt[0]);
                             return Err("Error with operand 1".to_string());
                         }
                     }
@@ -1717,9 +2328,9 @@ impl Parser{
                             op2Expr = expr;
                         }
                         Err(err) => {
-                            println!("Error parsing operand 2");
-                            let errMsg = format!("Error with operand 2 on line {}: {}", curStmt[0].lineNum, err);
-                            self.reports.reportError(errMsg);
+                            // println!("Error parsing operand 2");
+                            let errMsg = format!("Invalid constant {}: {}", curStmt[2].tokenString, err);
+                            self.reportErrorAt(ParseCode::InvalidExpr, errMsg, curStmt[2]);
                             return Err("Error with operand 2".to_string());
                         }
                     }
@@ -1731,11 +2342,9 @@ impl Parser{
                             opBin = expr;
                         }
                         Err(err) => {
-                            let errMsg = format!("Error with operator on line {}: {}", curStmt[0].lineNum, err);
-                            self.reports.reportError(errMsg);
-                            let errMsg =  format!("Error with operator on line {}", curStmt[0].lineNum.clone());
-                            println!("{}", errMsg);
-                            return Err(errMsg);
+                            let errMsg = format!("Expected an operator but found \'{}\'", curStmt[1].tokenString);
+                            self.reportErrorAt(ParseCode::UnexpectedToken, errMsg.clone(), curStmt[1]);
+                                                        return Err(errMsg);
                         }
                     }
                     
@@ -1753,9 +2362,9 @@ impl Parser{
                             op1Expr = expr;
                         }
                         Err(err) => {
-                            println!("Error parsing operand 1");
-                            let errMsg = format!("Error with operand 1 on line {}: {}", curStmt[0].lineNum, err);
-                            self.reports.reportError(errMsg);
+                            // println!("Error parsing operand 1");
+                            let errMsg = format!("Invalid constant {}: {}", curStmt[0].tokenString, err);
+                            self.reportErrorAt(ParseCode::InvalidExpr, errMsg, curStmt[0]);
                             return Err("Error with operand 1".to_string());
                         }
                     }
@@ -1775,7 +2384,7 @@ impl Parser{
                                         parsedExpr = expr
                                     },
                                     Err(msg) => {
-                                        println!("Error parsing expression from statment");
+                                        // println!("Error parsing expression from statment");
                                         let errMsg = format!("Error parsing body: {:?}", self.reports);
                                         parsedExpr = Expr::IntLiteral(0);
                                     }
@@ -1802,8 +2411,8 @@ impl Parser{
                             opBin = expr;
                         }
                         Err(err) => {
-                            let errMsg = format!("Error with operator on line {}: {}", curStmt[0].lineNum, err);
-                            self.reports.reportError(errMsg.clone());
+                            let errMsg = format!("Expected an operator but found \'{}\'", curStmt[1].tokenString);
+                            self.reportErrorAt(ParseCode::UnexpectedToken, errMsg.clone(), curStmt[1]);
                             return Err(errMsg);
                         }
                     }
@@ -1813,8 +2422,8 @@ impl Parser{
                     tokenList.drain(0..k+1);
                     return Ok(Some(retStmt));
                 } else {
-                    let errMsg = format!("In line: {}, expression is too short'", curStmt[1].lineNum);
-                    self.reports.reportError(errMsg);
+                    let errMsg = format!("Expression is too short");
+                    self.reportErrorAt(ParseCode::InvalidExpr, errMsg, curStmt[0]);
                     return Err("Error with expression".to_string());
                 }
             }
@@ -1843,8 +2452,8 @@ impl Parser{
                             op1Expr = expr;
                         }
                         Err(err) => {
-                            let errMsg = format!("Error with operand 1 on line {}: {}", curStmt[0].lineNum, err);
-                            self.reports.reportError(errMsg);
+                            let errMsg = format!("Invalid constant {}: {}", curStmt[0].tokenString, err);
+                            self.reportErrorAt(ParseCode::InvalidExpr, errMsg, curStmt[0]);
                             return Err("Error with operand 1".to_string());
                         }
                     }
@@ -1856,8 +2465,8 @@ impl Parser{
                             op2Expr = expr;
                         }
                         Err(err) => {
-                            let errMsg = format!("Error with operand 2 on line {}: {}", curStmt[0].lineNum, err);
-                            self.reports.reportError(errMsg);
+                            let errMsg = format!("Invalid constant {}: {}", curStmt[2].tokenString, err);
+                            self.reportErrorAt(ParseCode::InvalidExpr, errMsg, curStmt[2]);
                             return Err("Error with operand 2".to_string());
                         }
                     }
@@ -1869,8 +2478,8 @@ impl Parser{
                             opBin = expr;
                         }
                         Err(err) => {
-                            let errMsg = format!("Error with operator on line {}: {}", curStmt[0].lineNum, err);                            
-                            self.reports.reportError(errMsg);
+                            let errMsg = format!("Expected an operator but found \'{}\'", curStmt[1].tokenString);
+                            self.reportErrorAt(ParseCode::UnexpectedToken, errMsg.clone(), curStmt[1]);
                             return Err("Error with operator".to_string());
                         }
                     }
@@ -1890,8 +2499,8 @@ impl Parser{
                             op1Expr = expr;
                         }
                         Err(err) => {
-                            let errMsg = format!("Error with operand1 on line {}: {}", curStmt[0].lineNum, err);                            
-                            self.reports.reportError(errMsg);
+                            let errMsg = format!("Invalid constant {}: {}", curStmt[0].tokenString, err);
+                            self.reportErrorAt(ParseCode::InvalidExpr, errMsg, curStmt[0]);
                             return Err("Error with operand 1".to_string());
                         }
                     }
@@ -1918,12 +2527,12 @@ impl Parser{
                             
                         },
                         Ok((None)) => {
-                            println!("Parsed complex expression but no statement returned.");
+                            // println!("Parsed complex expression but no statement returned.");
                             parsedExpr = Expr::IntLiteral(0);
                         },
                         Err(reporting) => {
-                            println!("Error parsing expression: {:?}", reporting);
-                            let errMsg = format!("Error parsing body: {:?}", self.reports);
+                            // println!("Error parsing expression: {:?}", reporting);
+                            let errMsg = format!("Error parsing body: {}", reporting);
 
                             return Err(errMsg);
                         },
@@ -1938,8 +2547,8 @@ impl Parser{
                             opBin = expr;
                         }
                         Err(err) => {
-                            let errMsg = format!("Error with operator on line {}: {}", curStmt[0].lineNum, err);
-                            self.reports.reportError(errMsg);
+                            let errMsg = format!("Expected an operator but found \'{}\'", curStmt[1].tokenString);
+                            self.reportErrorAt(ParseCode::UnexpectedToken, errMsg.clone(), curStmt[1]);
                             return Err("Error with operator".to_string());
                         }
                     }
@@ -1950,8 +2559,8 @@ impl Parser{
                     tokenList.drain(0..k+1);
                     return Ok(Some(retStmt));
                 } else {
-                    let errMsg = format!("In line: {}, expression is too short'", curStmt[3].lineNum.clone());
-                    self.reports.reportError(errMsg);
+                    let errMsg = format!("Expression is too short");
+                    self.reportErrorAt(ParseCode::InvalidExpr, errMsg, curStmt[0]);
                     return Err("Error with expression".to_string());
                 }
             }
@@ -1973,29 +2582,43 @@ impl Parser{
                 curStmt.push(token.clone());
                 while nextTok.tt != tokenTypeEnum::SEMICOLON {
                     curStmt.push(nextTok.clone());
+                    if(k + 1 >= tokenList.len()){
+                        self.reportErrorAt(ParseCode::MissingSemicolon, format!("Missing ';' at the end of the procedure call"), nextTok);
+                        return Err("Error parsing procedure call".to_string());
+                    }
                     k = k + 1;
                     nextTok = &tokenList[k];
                 }
                 curStmt.push(nextTok.clone());
 
                 let mut procExpr: Expr;
+                let errCount = self.reports.errors.len();
                 let procCallExpr = self.parseExpr(&mut curStmt.clone());
                 match procCallExpr{
                     Ok(expr) => {
                         procExpr = expr;
                     }
                     Err(ErrMsg) => {
-                        let errMsg = format!("Error with parsing procedure call on line {}", curStmt[0].lineNum.clone());
-                        self.reports.reportError(errMsg.clone());
+                        let errMsg = format!("Invalid call to {}", curStmt[0].tokenString);
+                        self.reportIfSilent(errCount, ParseCode::InvalidExpr, errMsg, &curStmt[0].clone());
                         return Err("Error parsing procedure call".to_string());
                     }
                 }
                 tokenList.drain(0..k + 1);
-                return Ok(Some(Stmt::Expr((procExpr), (curStmt[0].lineNum.clone()))));
+
+                //A call on its own is a statement, whatever it returns is thrown away
+                match procExpr {
+                    Expr::ProcRef(procName, params) => {
+                        return Ok(Some(Stmt::Call(procName, params, curStmt[0].lineNum.clone())));
+                    }
+                    _ => {
+                        return Ok(Some(Stmt::Expr((procExpr), (curStmt[0].lineNum.clone()))));
+                    }
+                }
             }
             _ => {
-                let errMsg = format!("Unexpected token: '{}' on line: {}", token.tokenString, token.lineNum);
-                self.reports.reportError(errMsg.clone());
+                let errMsg = format!("Unexpected token: '{}'", token.tokenString);
+                self.reportErrorAt(ParseCode::UnexpectedToken, errMsg, &token.clone());
                 tokenList.drain(0..1);
                 return Err("Unexpected token found".to_string());
             }
@@ -2093,6 +2716,7 @@ pub enum Expr {
                                             //                      These are boxes because they can contain more BinOps within themselves     
     RelOp(Box<Expr>, Operator, Box<Expr>),      //A relational operation (operand 1, operator (<, >, etc.), operand 2) 
     LogOp(Box<Expr>, Operator, Box<Expr>),      //Operator for logical/bitwise equations (op1, operator (&, |, !), op2)
+    NotOp(Box<Expr>),                           //A logical not of a bool, or bitwise not of an integer (operand)
 
     
 }
@@ -2104,12 +2728,12 @@ impl Expr {
         match expr_type {
             tokenTypeEnum::INT => {
                 let value_str = param1.ok_or("IntLiteral requires an integer parameter".to_string())?;
-                let value = value_str.parse::<i64>().map_err(|e| format!("Failed to parse integer: {}", e))?;
+                let value = Expr::parseInt(&value_str)?;
                 Ok(Expr::IntLiteral(value))
             },
             tokenTypeEnum::FLOAT => {
                 let value_str = param1.ok_or("Float requires a float parameter".to_string())?;
-                let value = value_str.parse::<f32>().map_err(|e| format!("Failed to parse integer: {}", e))?;
+                let value = Expr::parseFloat(&value_str)?;
                 Ok(Expr::FloatLiteral(value))
             },
             tokenTypeEnum::STRING => {
@@ -2170,9 +2794,49 @@ impl Expr {
         }
     }
 
+    //Gets the value of an integer literal as it was written, which can be decimal, hex (0x) or binary (0b) with _ between digits
+    //Integers are 32 bits, so a value that does not fit is an error
+    pub fn parseInt(text: &str) -> Result<i64, String> {
+        let cleaned: String = text.chars().filter(|c| *c != '_').collect::<String>().to_ascii_lowercase();
+        let (negative, unsigned) = match cleaned.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, cleaned.as_str()),
+        };
+        let (radix, digits) = if let Some(hexDigits) = unsigned.strip_prefix("0x") {
+            (16, hexDigits)
+        } else if let Some(binDigits) = unsigned.strip_prefix("0b") {
+            (2, binDigits)
+        } else {
+            (10, unsigned)
+        };
+
+        let outOfRange = format!("Integer literal {} is out of range, integers must be between {} and {}", text, i32::MIN, i32::MAX);
+        let magnitude = match u64::from_str_radix(digits, radix) {
+            Ok(magnitude) => magnitude as i128,
+            Err(err) if *err.kind() == std::num::IntErrorKind::PosOverflow => return Err(outOfRange),
+            Err(_) => return Err(format!("Invalid integer literal '{}'", text)),
+        };
+        let value = if negative { -magnitude } else { magnitude };
+        if value < i32::MIN as i128 || value > i32::MAX as i128 {
+            return Err(outOfRange);
+        }
+        return Ok(value as i64);
+    }
+
+    //Gets the value of a float literal as it was written, with _ between digits
+    //Floats are 32 bits, so a value too big to store is an error
+    pub fn parseFloat(text: &str) -> Result<f32, String> {
+        let cleaned: String = text.chars().filter(|c| *c != '_').collect();
+        match cleaned.parse::<f32>() {
+            Ok(value) if value.is_finite() => Ok(value),
+            Ok(_) => Err(format!("Float literal {} is out of range, floats must be between {:e} and {:e}", text, f32::MIN, f32::MAX)),
+            Err(_) => Err(format!("Invalid float literal '{}'", text)),
+        }
+    }
+
     pub fn newCon(constant: Token) -> Result<Expr, String>{
         if constant.tg.clone() != tokenGroup::CONSTANT {
-            let errMsg = format!("Error parsing constant {} on line {}", constant.tokenString.clone(), constant.lineNum.clone());
+            let errMsg = format!("Error parsing constant {}", constant.tokenString.clone());
             return Err(errMsg);
         } else {
             match constant.tt.clone(){
@@ -2183,16 +2847,22 @@ impl Expr {
                     return Ok(Expr::BoolLiteral(true));
                 }
                 tokenTypeEnum::FLOAT => {
-                    return Ok(Expr::FloatLiteral(constant.tokenString.clone().parse().unwrap()));
+                    match Expr::parseFloat(&constant.tokenString) {
+                        Ok(value) => return Ok(Expr::FloatLiteral(value)),
+                        Err(msg) => return Err(msg),
+                    }
                 }
                 tokenTypeEnum::INT => {
-                    return Ok(Expr::IntLiteral(constant.tokenString.clone().parse().unwrap()));
+                    match Expr::parseInt(&constant.tokenString) {
+                        Ok(value) => return Ok(Expr::IntLiteral(value)),
+                        Err(msg) => return Err(msg),
+                    }
                 }
                 tokenTypeEnum::STRING => {
                     return Ok(Expr::StringLiteral(constant.tokenString.clone()));
                 }
                 _ => {
-                    let errMsg = format!("Error parsing constant {} on line {}: Invalid constant type {}", constant.tokenString.clone(), constant.lineNum.clone(), constant.tt.clone());
+                    let errMsg = format!("Error parsing constant {}: Invalid constant type {}", constant.tokenString.clone(), constant.tt.clone());
                     return Err(errMsg);
                 }
             }
@@ -2216,6 +2886,7 @@ impl fmt::Display for Expr {
             Expr::ProcRef(name, None) => write!(f, "{}()", name),
             Expr::RelOp(left, op, right) => write!(f, "({} {} {})", left, op, right),
             Expr::LogOp(left, op, right) => write!(f, "({} {} {})", left, op, right),
+            Expr::NotOp(operand) => write!(f, "(not {})", operand),
             Expr::BoolLiteral(val) => write!(f, "{}", val),
             Expr::IntArrayLiteral(size, array) => write!(f, "([{}])", size),
 
@@ -2230,7 +2901,8 @@ pub enum VarType {
     Bool,
     Float,
     Str,
-    IntArray(i32),
+    Array(Box<VarType>, i32),                   //An array of the element type with its declared size
+    Void,                                       //The return type of a procedure that returns nothing, never the type of a value
 }
 impl VarType {
     pub fn new(typeStr: &str) -> Result<Self, String> {
@@ -2239,12 +2911,28 @@ impl VarType {
             "bool" => Ok(VarType::Bool),
             "float" => Ok(VarType::Float),
             "string" => Ok(VarType::Str),
-            "int[]" => Ok(VarType::IntArray(0)),
-            
             _ => Err(format!("Unsupported var type: {}", typeStr)),
         }
     }
 }
+//How a procedure parameter is passed
+//In parameters are copied into the procedure, out and in out parameters are passed as a pointer to the caller's variable
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamMode {
+    In,
+    Out,
+    InOut,
+}
+impl fmt::Display for ParamMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamMode::In => write!(f, "in"),
+            ParamMode::Out => write!(f, "out"),
+            ParamMode::InOut => write!(f, "in out"),
+        }
+    }
+}
+
 impl fmt::Display for VarType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -2252,7 +2940,8 @@ impl fmt::Display for VarType {
             VarType::Bool => write!(f, "Bool"),
             VarType::Float => write!(f, "Float"),
             VarType::Str => write!(f, "Str"),
-            VarType::IntArray(arr) => write!(f, "IntArray[{}]", arr),
+            VarType::Array(elemType, size) => write!(f, "{}[{}]", elemType, size),
+            VarType::Void => write!(f, "Void"),
         }
     }
 }
@@ -2261,26 +2950,32 @@ impl fmt::Display for VarType {
 //Statements make up the nodes of the AST and are made up of expressions
 #[derive(Debug, Clone, PartialEq)]
 pub enum Stmt {
-    StringLiteral(String, String),
-    Expr(Expr, String),                     // Expression statement
-    Assign(Expr, Expr, String),           // Assignment statement: variable refernce, expression to assign to
-    VarDecl(String, VarType, String),       // Variable declaration statement
-    GlobVarDecl(String, VarType, String),       // Variable declaration statement
-    If(Expr, Box<Stmt>, Option<Box<Stmt>>, String),  // If statement: condition, body, optional else body
-    For(Rc<Stmt>, Expr, Box<Stmt>, String),          // For statement: assignment, condition, Box of commands for statement
-    Block(Vec<Stmt>, String),               // Block statement: list of statements
-    Error(Reporting, String),
-    Return(Expr, String),
-    Program(String, Box<Stmt>, Box<Stmt>, String), //The program AST: Name, header block, body block, lineNum
-    ProcDecl(VarType, String, Box<Stmt>, Box<Stmt>, Box<Stmt>, String), //Procedure AST: type, Name, parameter, Header, body
+    StringLiteral(String, usize),
+    Expr(Expr, usize),                     // Expression statement
+    Assign(Expr, Expr, usize),           // Assignment statement: variable refernce, expression to assign to
+    VarDecl(String, VarType, usize),       // Variable declaration statement
+    GlobVarDecl(String, VarType, usize),       // Variable declaration statement
+    ParamDecl(String, VarType, ParamMode, usize), // Procedure parameter declaration: name, type, how it is passed
+    If(Expr, Box<Stmt>, Vec<(Expr, Stmt)>, Option<Box<Stmt>>, usize),  // If statement: condition, body, (condition, body) elsif arms, optional else body
+    Case(Expr, Vec<(Expr, Stmt)>, Option<Box<Stmt>>, usize), // Case statement: value, (arm value, arm body) list, optional others body
+    For(Rc<Stmt>, Expr, Box<Stmt>, usize),          // For statement: assignment, condition, Box of commands for statement
+    While(Expr, Box<Stmt>, usize),         // While statement: condition, Box of commands for statement
+    Break(usize),                          // Leaves the innermost loop (break or exit)
+    Continue(usize),                       // Skips to the next check of the innermost loop's condition
+    Block(Vec<Stmt>, usize),               // Block statement: list of statements
+    Error(Reporting, usize),
+    Return(Expr, usize),
+    Call(String, Option<Vec<Expr>>, usize), // Procedure call statement: procedure name, optional arguments, the return value is thrown away
+    Program(String, Box<Stmt>, Box<Stmt>, usize), //The program AST: Name, header block, body block, lineNum
+    ProcDecl(VarType, String, Box<Stmt>, Box<Stmt>, Box<Stmt>, usize), //Procedure AST: type, Name, parameter, Header, body
+    ProcForward(VarType, String, Box<Stmt>, usize), //Forward declaration of a procedure defined later in the same header: type, Name, parameter
 }
 //Functions for Stmt
 impl Stmt {
     // Function to push a statement into a Block variant
     pub fn push_to_block(&mut self, stmt: Stmt) -> Result<(), String> {
-        let fakeLine = "num".to_string();
         match self {
-            Stmt::Block(stmts, fakeLine) => {
+            Stmt::Block(stmts, lineNum) => {
                 stmts.push(stmt);
                 Ok(())
             },
@@ -2289,63 +2984,131 @@ impl Stmt {
     }
 
     pub fn display(&self, indent: usize) {
+        print!("{}", self.treeString(indent));
+    }
+
+    //Builds the tree that display prints as a string, used when the AST is written to a file
+    pub fn treeString(&self, indent: usize) -> String {
         let indentation = " ".repeat(indent);
+        let mut tree = String::new();
         match self {
-            Stmt::StringLiteral(s, lineNum) => println!("{}StringLiteral({})", indentation, s),
-            Stmt::Expr(expr, lineNum) => println!("{}Expr({})", indentation, expr),
-            Stmt::Assign(var, expr, lineNum) => println!("{}Assign({}, {})", indentation, var, expr),
-            Stmt::VarDecl(var, vartype, lineNum) => println!("{}VarDecl({}, {})", indentation, var, vartype),
-            Stmt::GlobVarDecl(var, vartype, lineNum) => println!("{}GlobVarDecl({}, {})", indentation, var, vartype),
-            Stmt::If(cond, body, else_body, lineNum) => {
-                println!("{}If (", indentation);
-                println!("{}  Condition: {}", indentation, cond);
-                println!("{}  Body: ", indentation);
-                body.display(indent + 2);
+            Stmt::StringLiteral(s, lineNum) => { tree.push_str(&format!("{}StringLiteral({})\n", indentation, s)); }
+            Stmt::Expr(expr, lineNum) => { tree.push_str(&format!("{}Expr({})\n", indentation, expr)); }
+            Stmt::Assign(var, expr, lineNum) => { tree.push_str(&format!("{}Assign({}, {})\n", indentation, var, expr)); }
+            Stmt::VarDecl(var, vartype, lineNum) => { tree.push_str(&format!("{}VarDecl({}, {})\n", indentation, var, vartype)); }
+            Stmt::GlobVarDecl(var, vartype, lineNum) => { tree.push_str(&format!("{}GlobVarDecl({}, {})\n", indentation, var, vartype)); }
+            Stmt::ParamDecl(var, vartype, mode, lineNum) => { tree.push_str(&format!("{}ParamDecl({}, {} {})\n", indentation, var, mode, vartype)); }
+            Stmt::If(cond, body, elsif_arms, else_body, lineNum) => {
+                tree.push_str(&format!("{}If (\n", indentation));
+                tree.push_str(&format!("{}  Condition: {}\n", indentation, cond));
+                tree.push_str(&format!("{}  Body: \n", indentation));
+                tree.push_str(&body.treeString(indent + 2));
+                //The elsif arms are shown at the same level as the if, in the order they are checked
+                for (elsif_cond, elsif_body) in elsif_arms {
+                    tree.push_str(&format!("{}  Elsif Condition: {}\n", indentation, elsif_cond));
+                    tree.push_str(&format!("{}  Body: \n", indentation));
+                    tree.push_str(&elsif_body.treeString(indent + 2));
+                }
                 if let Some(else_stmt) = else_body {
-                    println!("{}  Else: ", indentation);
-                    else_stmt.display(indent + 2);
+                    tree.push_str(&format!("{}  Else: \n", indentation));
+                    tree.push_str(&else_stmt.treeString(indent + 2));
                 }
-                println!("{})", indentation);
+                tree.push_str(&format!("{})\n", indentation));
+            }
+            Stmt::Case(value, arms, others, lineNum) => {
+                tree.push_str(&format!("{}Case (\n", indentation));
+                tree.push_str(&format!("{}  Value: {}\n", indentation, value));
+                for (armValue, armBody) in arms {
+                    tree.push_str(&format!("{}  When {}: \n", indentation, armValue));
+                    tree.push_str(&armBody.treeString(indent + 3));
+                }
+                if let Some(othersBody) = others {
+                    tree.push_str(&format!("{}  Others: \n", indentation));
+                    tree.push_str(&othersBody.treeString(indent + 3));
+                }
+                tree.push_str(&format!("{})\n", indentation));
             }
             Stmt::For(assignment, cond, body, lineNum) => {
-                println!("{}For (", indentation);
-                println!("{}  Assignment: ", indentation);
-                assignment.display(indent + 3);
-                println!("{}  Condition: {}", indentation, cond);
-                println!("{}  Body: ", indentation);
-                body.display(indent + 3);
-                println!("{})", indentation);
+                tree.push_str(&format!("{}For (\n", indentation));
+                tree.push_str(&format!("{}  Assignment: \n", indentation));
+                tree.push_str(&assignment.treeString(indent + 3));
+                tree.push_str(&format!("{}  Condition: {}\n", indentation, cond));
+                tree.push_str(&format!("{}  Body: \n", indentation));
+                tree.push_str(&body.treeString(indent + 3));
+                tree.push_str(&format!("{})\n", indentation));
             }
+            Stmt::While(cond, body, lineNum) => {
+                tree.push_str(&format!("{}While (\n", indentation));
+                tree.push_str(&format!("{}  Condition: {}\n", indentation, cond));
+                tree.push_str(&format!("{}  Body: \n", indentation));
+                tree.push_str(&body.treeString(indent + 3));
+                tree.push_str(&format!("{})\n", indentation));
+            }
+            Stmt::Break(lineNum) => { tree.push_str(&format!("{}Break\n", indentation)); }
+            Stmt::Continue(lineNum) => { tree.push_str(&format!("{}Continue\n", indentation)); }
             Stmt::Block(stmts, lineNum) => {
-                println!("{}Block([", indentation);
+                tree.push_str(&format!("{}Block([\n", indentation));
                 for stmt in stmts {
-                    stmt.display(indent + 2);
+                    tree.push_str(&stmt.treeString(indent + 2));
                 }
-                println!("{}])", indentation);
+                tree.push_str(&format!("{}])\n", indentation));
             },
-            Stmt::Error(reporting, lineNum) => println!("{}Error({:?})", indentation, reporting),
-            Stmt::Return(expr, lineNum) => println!("{}Return({})", indentation, expr),
+            Stmt::Error(reporting, lineNum) => { tree.push_str(&format!("{}Error({:?})\n", indentation, reporting)); }
+            Stmt::Return(expr, lineNum) => { tree.push_str(&format!("{}Return({})\n", indentation, expr)); }
+            Stmt::Call(name, params, lineNum) => { tree.push_str(&format!("{}Call({})\n", indentation, Expr::ProcRef(name.clone(), params.clone()))); }
             Stmt::Program(name, header, body, lineNum) => {
-                println!("{}{}:(", indentation,name);
-                println!(" {}Header:",indentation);
-                header.display(indent + 1);
-                println!(" {}Body:",indentation);
-                body.display(indent + 1);
-                println!("{})", indentation);
+                tree.push_str(&format!("{}{}:(\n", indentation,name));
+                tree.push_str(&format!(" {}Header:\n",indentation));
+                tree.push_str(&header.treeString(indent + 1));
+                tree.push_str(&format!(" {}Body:\n",indentation));
+                tree.push_str(&body.treeString(indent + 1));
+                tree.push_str(&format!("{})\n", indentation));
             }
             Stmt::ProcDecl(procType, name, params, header, body, lineNum) => {
-                println!("{}{} {}:(", indentation,procType,name);
-                println!(" {}Params:",indentation);
-                params.display(indent + 1);
+                tree.push_str(&format!("{}{} {}:(\n", indentation,procType,name));
+                tree.push_str(&format!(" {}Params:\n",indentation));
+                tree.push_str(&params.treeString(indent + 1));
                 
-                println!(" {}Header:",indentation);
-                header.display(indent + 1);
-                println!(" {}Body:",indentation);
-                body.display(indent + 1);
-                println!("{})", indentation);
+                tree.push_str(&format!(" {}Header:\n",indentation));
+                tree.push_str(&header.treeString(indent + 1));
+                tree.push_str(&format!(" {}Body:\n",indentation));
+                tree.push_str(&body.treeString(indent + 1));
+                tree.push_str(&format!("{})\n", indentation));
+            }
+            Stmt::ProcForward(procType, name, params, lineNum) => {
+                tree.push_str(&format!("{}Forward {} {}:(\n", indentation,procType,name));
+                tree.push_str(&format!(" {}Params:\n",indentation));
+                tree.push_str(&params.treeString(indent + 1));
+                tree.push_str(&format!("{})\n", indentation));
             }
             
         }
+        tree
+    }
+
+    //Gets the line number the statement was found on
+    pub fn lineNum(&self) -> usize {
+        match self {
+            Stmt::StringLiteral(_, lineNum) => lineNum.clone(),
+            Stmt::Expr(_, lineNum) => lineNum.clone(),
+            Stmt::Assign(_, _, lineNum) => lineNum.clone(),
+            Stmt::VarDecl(_, _, lineNum) => lineNum.clone(),
+            Stmt::GlobVarDecl(_, _, lineNum) => lineNum.clone(),
+            Stmt::ParamDecl(_, _, _, lineNum) => lineNum.clone(),
+            Stmt::If(_, _, _, _, lineNum) => lineNum.clone(),
+            Stmt::Case(_, _, _, lineNum) => lineNum.clone(),
+            Stmt::For(_, _, _, lineNum) => lineNum.clone(),
+            Stmt::While(_, _, lineNum) => lineNum.clone(),
+            Stmt::Break(lineNum) => lineNum.clone(),
+            Stmt::Continue(lineNum) => lineNum.clone(),
+            Stmt::Block(_, lineNum) => lineNum.clone(),
+            Stmt::Error(_, lineNum) => lineNum.clone(),
+            Stmt::Return(_, lineNum) => lineNum.clone(),
+            Stmt::Call(_, _, lineNum) => lineNum.clone(),
+            Stmt::Program(_, _, _, lineNum) => lineNum.clone(),
+            Stmt::ProcDecl(_, _, _, _, _, lineNum) => lineNum.clone(),
+            Stmt::ProcForward(_, _, _, lineNum) => lineNum.clone(),
+        }
     }
 
     //Used to get an Expr from a returned Stmt if the Stmt is just a Expr
@@ -2365,3 +3128,189 @@ fn printTokList(tokList: &Vec<Token>){
 }
 
 ///////////////////////// /PARSER SECTION /////////////////////////
+
+
+
+
+
+///////////////////////// TESTS /////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //Parses the source, giving back the diagnostics and the tree
+    fn parseSource(source: &str) -> (Reporting, Option<Stmt>) {
+        let mut lexer = Lexer::fromSource("test.src", source);
+        lexer.scanThrough();
+        let mut parser = Parser::new(&mut lexer);
+        match parser.startParse() {
+            Ok((reports, ast)) => (reports, ast),
+            Err(reports) => (reports, None),
+        }
+    }
+
+    //Parses a program with the given statements as its body and gets the body, failing on any syntax error
+    fn parseBody(body: &str) -> Vec<Stmt> {
+        let source = format!("program test is\n    variable a : bool;\n    variable c : bool;\n    variable i : integer;\nbegin\n{}\nend program.\n", body);
+        let (reports, ast) = parseSource(&source);
+        assert!(!reports.status, "{:?}", reports.errors);
+        match ast {
+            Some(Stmt::Program(name, header, body, lineNum)) => match *body {
+                Stmt::Block(stmts, blockLine) => stmts,
+                other => panic!("program body is not a block: {:?}", other),
+            },
+            other => panic!("no program parsed: {:?}", other),
+        }
+    }
+
+    fn var(name: &str) -> Box<Expr> {
+        return Box::new(Expr::VarRef(name.to_string()));
+    }
+
+    fn not(operand: Box<Expr>) -> Box<Expr> {
+        return Box::new(Expr::NotOp(operand));
+    }
+
+    //Gets the value of each assignment in the body
+    fn assignedValues(body: &str) -> Vec<Expr> {
+        return parseBody(body).into_iter().map(|stmt| match stmt {
+            Stmt::Assign(target, value, lineNum) => value,
+            other => panic!("expected an assignment: {:?}", other),
+        }).collect();
+    }
+
+    #[test]
+    fn notTakesOneOperand() {
+        let values = assignedValues("a := not a & c;\na := not a | c;\na := not a;\na := c & not a;");
+        assert_eq!(values, vec![
+            Expr::LogOp(not(var("a")), Operator::And, var("c")),
+            Expr::LogOp(not(var("a")), Operator::Or, var("c")),
+            *not(var("a")),
+            Expr::LogOp(var("c"), Operator::And, not(var("a"))),
+        ]);
+    }
+
+    #[test]
+    fn notOperandForms() {
+        let values = assignedValues("a := not (a & c) & c;\na := not not a & c;\na := not getbool() | c;\ni := 1;");
+        assert_eq!(values[0], Expr::LogOp(not(Box::new(Expr::LogOp(var("a"), Operator::And, var("c")))), Operator::And, var("c")));
+        assert_eq!(values[1], Expr::LogOp(not(not(var("a"))), Operator::And, var("c")));
+        assert_eq!(values[2], Expr::LogOp(not(Box::new(Expr::ProcRef("getbool".to_string(), None))), Operator::Or, var("c")));
+
+        let values = assignedValues("a := not c[i + 1] & a;");
+        let index = Box::new(Expr::ArthOp(var("i"), Operator::Add, Box::new(Expr::IntLiteral(1))));
+        assert_eq!(values[0], Expr::LogOp(not(Box::new(Expr::ArrayRef("c".to_string(), index))), Operator::And, var("a")));
+    }
+
+    #[test]
+    fn forConditions() {
+        let body = parseBody("for (i := 0; (i < 3) & a)\n    i := i + 1;\nend for;\nfor (i := 3; getbool(i))\n    i := i - 1;\nend for;");
+        let conditions: Vec<Expr> = body.into_iter().map(|stmt| match stmt {
+            Stmt::For(assignment, cond, forBody, lineNum) => cond,
+            other => panic!("expected a for loop: {:?}", other),
+        }).collect();
+        assert_eq!(conditions, vec![
+            Expr::LogOp(Box::new(Expr::RelOp(var("i"), Operator::Less, Box::new(Expr::IntLiteral(3)))), Operator::And, var("a")),
+            Expr::ProcRef("getbool".to_string(), Some(vec![Expr::VarRef("i".to_string())])),
+        ]);
+    }
+
+    #[test]
+    fn forMissingParen() {
+        let (reports, ast) = parseSource("program test is\n    variable i : integer;\nbegin\n    for (i := 0; i < 3\n        i := i + 1;\n    end for;\n    putinteger(i);\nend program.\n");
+        assert!(reports.status);
+        assert_eq!(reports.errors, vec!["Missing ) at the end of the for loop header"]);
+    }
+
+    #[test]
+    fn nestedIfInElse() {
+        let body = parseBody("if (a) then\n    i := 1;\nelse\n    if (c) then\n        i := 2;\n    end if;\n    i := 3;\nend if;");
+        match &body[..] {
+            [Stmt::If(cond, ifBody, elsifArms, Some(elseBody), lineNum)] => {
+                assert!(elsifArms.is_empty());
+                match &**elseBody {
+                    Stmt::Block(stmts, blockLine) => {
+                        assert_eq!(stmts.len(), 2, "{:?}", stmts);
+                        assert!(matches!(stmts[0], Stmt::If(..)), "{:?}", stmts[0]);
+                    }
+                    other => panic!("else body is not a block: {:?}", other),
+                }
+            }
+            other => panic!("expected one if with an else: {:?}", other),
+        }
+
+        //On one line it is an elsif arm sharing the end if
+        let body = parseBody("if (a) then\n    i := 1;\nelse if (c) then\n    i := 2;\nend if;");
+        assert!(matches!(&body[..], [Stmt::If(_, _, elsifArms, None, _)] if elsifArms.len() == 1), "{:?}", body);
+    }
+
+    #[test]
+    fn reportsEverySyntaxError() {
+        let source = "program test is
+    variable k : integer;
+    procedure f : integer (variable n : integer)
+        variable m : integer;
+        return n;
+    end procedure;
+    procedure g : integer (variable n : integer)
+    begin
+        return n
+    end procedure;
+begin
+    k := ( 1;
+    for (k := 0; k < 3
+        k := k + 1;
+    end for;
+    if (k < 2) then
+        k := 2;
+    end if
+    k := ( 2 + 3;
+    putinteger(k);
+end program.
+";
+        let (reports, ast) = parseSource(source);
+        assert!(reports.status);
+        let lines: Vec<usize> = reports.diagnostics.iter().map(|diag| diag.span.as_ref().map(|span| span.line).unwrap_or(0)).collect();
+        assert_eq!(lines, vec![3, 9, 12, 13, 18, 19], "{:?}", reports.errors);
+        assert!(reports.errors[0].starts_with("No begin found for the body of procedure f"));
+        assert_eq!(reports.errors[1], "Missing ; at the end of the return statement");
+        assert_eq!(reports.errors[4], "Missing ; after end if");
+    }
+
+    #[test]
+    fn errorCodesAndCarets() {
+        //Each error has the code for its kind and points at the token that caused it
+        let cases = [
+            ("i := 99999999999;", "P004", 6),
+            ("i := 1", "P001", 6),
+            ("i := ( 1;", "P002", 6),
+            ("then;", "P003", 1),
+            ("i := 1 + ;", "P004", 10),
+            ("if i < 1 then\n    i := 1;\nend if;", "P007", 4),
+        ];
+        for (body, code, col) in cases {
+            let source = format!("program test is\n    variable a : bool;\n    variable c : bool;\n    variable i : integer;\nbegin\n{}\nend program.\n", body);
+            let (reports, ast) = parseSource(&source);
+            let diag = &reports.diagnostics[0];
+            let span = diag.span.as_ref().unwrap();
+            assert_eq!((diag.code.as_str(), span.line, span.startCol), (code, 6, col), "{}: {}", body, diag.message);
+            assert!(!diag.message.contains("line"), "{}", diag.message);
+        }
+    }
+
+    #[test]
+    fn truncatedStatements() {
+        //Each of these used to run off the end of its token list
+        let bodies = ["i := ( 1;", "return (;", "a := ][0] & c;", "i := 1 +", "if (a) then\n    i := 1;\nend if", "i := c[1"];
+        for body in bodies {
+            let source = format!("program test is\n    variable a : bool;\n    variable c : bool[2];\n    variable i : integer;\nbegin\n{}\nend program.\n", body);
+            let (reports, ast) = parseSource(&source);
+            assert!(reports.status, "no error for {}", body);
+        }
+        let (reports, ast) = parseSource("program test is\n    global ; end if; gs : integer;\nbegin\nend program.\n");
+        assert!(reports.status);
+    }
+}
+
+///////////////////////// /TESTS /////////////////////////
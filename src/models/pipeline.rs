@@ -0,0 +1,307 @@
+///////////////////////// Setup /////////////////////////
+
+//Rules
+#![allow(non_snake_case)]
+#![allow(non_camel_case_types)]
+#![allow(unused_imports)]
+#![allow(dead_code)]
+#![allow(unused_parens)]
+#![allow(unused_mut)]
+#![allow(unused_variables)]
+
+//package imports
+use {
+    crate::models::{lexer::{Lexer, Token}, parser::{Parser, Stmt, ParseCode}, typechecker::{
+        SymbolTable, SyntaxChecker, CheckCode
+    }, flow::FlowChecker, compiler::Compiler, reporting::{Reporting, Diagnostic},
+    }, inkwell::{context::Context, module::Module, values::PointerValue, OptimizationLevel},
+    inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple},
+    std::{collections::HashMap, fmt},
+};
+
+///////////////////////// /Setup /////////////////////////
+
+
+
+///////////////////////// PIPELINE SECTION /////////////////////////
+//This section runs every stage of the compiler over a source string, in order
+//It is what main.rs uses, and it can be called directly by anything else that wants to drive the compiler
+
+//The different stages the compiler can stop at and write out
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmitType {
+    Tokens,
+    Ast,
+    LlvmIr,
+    Bitcode,
+    Asm,
+    Object,
+    Exe,
+}
+
+impl EmitType {
+    //Gets the emit type from the name given on the command line
+    pub fn new(name: &str) -> Result<EmitType, String> {
+        match name {
+            "tokens" => Ok(EmitType::Tokens),
+            "ast" => Ok(EmitType::Ast),
+            "llvm-ir" => Ok(EmitType::LlvmIr),
+            "bc" => Ok(EmitType::Bitcode),
+            "asm" => Ok(EmitType::Asm),
+            "obj" => Ok(EmitType::Object),
+            "exe" => Ok(EmitType::Exe),
+            _ => Err(format!("Unknown emit type '{}', expected one of tokens, ast, llvm-ir, bc, asm, obj, exe", name)),
+        }
+    }
+
+    //The file extension used when no output path is given
+    pub fn extension(&self) -> &str {
+        match self {
+            EmitType::Tokens => "tokens",
+            EmitType::Ast => "ast",
+            EmitType::LlvmIr => "ll",
+            EmitType::Bitcode => "bc",
+            EmitType::Asm => "s",
+            EmitType::Object => "o",
+            EmitType::Exe => "",
+        }
+    }
+}
+
+//The options for a single run of the pipeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub fileName: String,           //The name used for the source in diagnostics
+    pub emit: EmitType,             //The stage to stop at, Exe stops at the object code since linking is left to the caller
+    pub target: Option<String>,     //The target triple to generate code for, the host if None
+    pub boundsChecks: bool,         //Whether array accesses are checked at runtime, on by default in every build of the compiler
+}
+
+impl Options {
+    pub fn new(fileName: &str, emit: EmitType) -> Options {
+        Options {
+            fileName: fileName.to_string(),
+            emit,
+            target: None,
+            boundsChecks: true,
+        }
+    }
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options::new("<input>", EmitType::Object)
+    }
+}
+
+//Everything a successful run produced
+pub struct Artifacts {
+    pub tokens: Vec<Token>,         //The tokens from the lexer
+    pub ast: Option<Stmt>,          //The program tree, None if emit stopped at the tokens
+    pub output: Vec<u8>,            //The contents for the emit type, text for tokens, ast, llvm-ir and asm, bytes otherwise
+    pub diagnostics: Diagnostics,   //Any warnings that were reported along the way
+}
+
+//The diagnostics from a run, along with the source they point into so they can be rendered
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostics {
+    pub fileName: String,
+    pub source: String,
+    pub reports: Reporting,
+    pub ast: Option<Stmt>,          //The tree the parser recovered, if the run got that far
+}
+
+impl Diagnostics {
+    pub fn new(fileName: &str, source: &str) -> Diagnostics {
+        Diagnostics {
+            fileName: fileName.to_string(),
+            source: source.to_string(),
+            reports: Reporting::new(),
+            ast: None,
+        }
+    }
+
+    //Whether any of the diagnostics are errors
+    pub fn hasErrors(&self) -> bool {
+        return self.reports.status;
+    }
+
+    //Renders every diagnostic with the lines of source they point at
+    pub fn render(&self) -> String {
+        return self.reports.render(&self.source);
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+//Runs the source through every stage up to the one asked for in the options
+//Returns the artifacts on success, or every diagnostic reported if any stage failed
+pub fn compile_source(source: &str, options: Options) -> Result<Artifacts, Diagnostics> {
+    let mut diagnostics = Diagnostics::new(&options.fileName, source);
+
+    //Scans the source into tokens
+    let mut myLexer = Lexer::fromSource(&options.fileName, source);
+    myLexer.scanThrough();
+    diagnostics.reports.absorb(myLexer.reports.clone());
+    if diagnostics.hasErrors() {
+        return Err(diagnostics);
+    }
+    let tokens = myLexer.tokenList.clone();
+
+    //Stops if the tokens are all that was asked for
+    if options.emit == EmitType::Tokens {
+        return Ok(Artifacts {
+            tokens,
+            ast: None,
+            output: myLexer.tokenListString().into_bytes(),
+            diagnostics,
+        });
+    }
+
+    //Parses the tokens into the program AST
+    let mut myParser = Parser::new(&mut myLexer);
+    let programAst: Stmt;
+    match myParser.startParse() {
+        Ok((reporting, Some(stmt))) => {
+            diagnostics.reports.absorb(reporting);
+            if diagnostics.hasErrors() {
+                //The parser recovers from errors, so the partial tree is kept
+                diagnostics.ast = Some(stmt);
+                return Err(diagnostics);
+            }
+            programAst = stmt;
+        }
+        Ok((reporting, None)) => {
+            diagnostics.reports.absorb(reporting);
+            diagnostics.reports.report(Diagnostic::error(ParseCode::InvalidProgram.code(), "Parsing succeeded, but no program was returned".to_string()));
+            return Err(diagnostics);
+        }
+        Err(reporting) => {
+            diagnostics.reports.absorb(reporting);
+            return Err(diagnostics);
+        }
+    }
+
+    //Stops if the tree is all that was asked for
+    if options.emit == EmitType::Ast {
+        return Ok(Artifacts {
+            tokens,
+            output: programAst.treeString(0).into_bytes(),
+            ast: Some(programAst),
+            diagnostics,
+        });
+    }
+
+    //Type checks the program
+    let mut checkerTable = SymbolTable::new();
+    let mut myChecker = SyntaxChecker::new(programAst.clone(), &mut checkerTable, "Main".to_string());
+    myChecker.fileName = options.fileName.clone();
+    let programValid: bool = myChecker.checkProgram();
+    diagnostics.reports.absorb(myChecker.reports.clone());
+    if !programValid || diagnostics.hasErrors() {
+        if !diagnostics.hasErrors() {
+            diagnostics.reports.report(Diagnostic::error(CheckCode::Internal.code(), "Type checking failed".to_string()));
+        }
+        diagnostics.ast = Some(programAst);
+        return Err(diagnostics);
+    }
+
+    //Follows the control flow, a procedure that can end without returning a value is an error
+    let mut myFlow = FlowChecker::new();
+    myFlow.fileName = options.fileName.clone();
+    let flowValid = myFlow.checkProgram(&programAst);
+    diagnostics.reports.absorb(myFlow.reports.clone());
+    if !flowValid {
+        diagnostics.ast = Some(programAst);
+        return Err(diagnostics);
+    }
+
+    //Generates the LLVM module
+    let mut globalTable: HashMap<String, PointerValue> = HashMap::new();
+    let context = Context::create();
+    let mut myGen = Compiler::new(programAst.clone(), &context, &mut globalTable, "Program".to_string());
+    myGen.fileName = options.fileName.clone();
+    myGen.boundsChecks = options.boundsChecks;
+    let genResult = myGen.compileProgram().map(|module| module.clone());
+    diagnostics.reports.absorb(myGen.reports.clone());
+    let finalMod: Module;
+    match genResult {
+        Ok(module) => {
+            finalMod = module;
+        }
+        Err(errMsg) => {
+            if !diagnostics.hasErrors() {
+                diagnostics.reports.report(Diagnostic::error("C001", errMsg));
+            }
+            diagnostics.ast = Some(programAst);
+            return Err(diagnostics);
+        }
+    }
+
+    //Sets up the target machine, the host unless a triple was given
+    Target::initialize_all(&InitializationConfig::default());
+    let targTriple = match &options.target {
+        Some(triple) => TargetTriple::create(triple),
+        None => TargetMachine::get_default_triple(),
+    };
+    let target: Target;
+    match Target::from_triple(&targTriple) {
+        Ok(targ) => {
+            target = targ;
+        }
+        Err(err) => {
+            diagnostics.reports.reportError("C002", format!("Failed to get target {}: {}", targTriple.as_str().to_string_lossy(), err), None);
+            return Err(diagnostics);
+        }
+    }
+    let targetMachine: TargetMachine;
+    match target.create_target_machine(
+        &targTriple,
+        "generic",                      //Generic cpu for the target
+        "",                             //No feature inclusion
+        OptimizationLevel::None,        //No optimizations
+        RelocMode::PIC,                 //position independent, linkers build PIE executables by default
+        CodeModel::Default,             //default code model
+    ) {
+        Some(machine) => {
+            targetMachine = machine;
+        }
+        None => {
+            diagnostics.reports.reportError("C002", format!("Could not create a target machine for {}", targTriple.as_str().to_string_lossy()), None);
+            return Err(diagnostics);
+        }
+    }
+
+    //Tags the module with the target so every output agrees on it
+    finalMod.set_triple(&targTriple);
+    finalMod.set_data_layout(&targetMachine.get_target_data().get_data_layout());
+
+    //Produces the requested output
+    let output: Vec<u8> = match options.emit {
+        EmitType::LlvmIr => finalMod.print_to_string().to_string().into_bytes(),
+        EmitType::Bitcode => finalMod.write_bitcode_to_memory().as_slice().to_vec(),
+        _ => {
+            let fileType = if options.emit == EmitType::Asm { FileType::Assembly } else { FileType::Object };
+            match targetMachine.write_to_memory_buffer(&finalMod, fileType) {
+                Ok(buffer) => buffer.as_slice().to_vec(),
+                Err(err) => {
+                    diagnostics.reports.reportError("C003", format!("Error generating code: {}", err), None);
+                    return Err(diagnostics);
+                }
+            }
+        }
+    };
+
+    return Ok(Artifacts {
+        tokens,
+        ast: Some(programAst),
+        output,
+        diagnostics,
+    });
+}
+
+///////////////////////// /PIPELINE SECTION /////////////////////////
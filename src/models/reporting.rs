@@ -1,5 +1,157 @@
+//How serious a diagnostic is
+#[derive(Debug, Clone, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let severityStr = match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        };
+        write!(f, "{}", severityStr)
+    }
+}
+
+//The place in the source a diagnostic points at
+//Lines and columns start at 1, endCol is one past the last character
+//A startCol of 0 means the column is not known and the whole line is marked
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub file: String,
+    pub line: usize,
+    pub startCol: usize,
+    pub endCol: usize,
+}
+
+impl Span {
+    pub fn new(file: String, line: usize, startCol: usize, endCol: usize) -> Span {
+        Span {
+            file,
+            line,
+            startCol,
+            endCol,
+        }
+    }
+
+    //A span that only knows the line, used for the statements in the AST which do not keep their columns
+    pub fn line(file: String, line: usize) -> Span {
+        Span::new(file, line, 0, 0)
+    }
+}
+
+//A single error, warning or note produced by one of the stages
+//Codes are grouped by stage: L for the lexer, P for the parser, T for the type checker, F for the flow checker and C for code generation
+//Within a stage each kind of problem has its own number, so a code says what went wrong
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: String,
+    pub message: String,
+    pub span: Option<Span>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: &str, message: String) -> Diagnostic {
+        Diagnostic {
+            severity,
+            code: code.to_string(),
+            message,
+            span: None,
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn error(code: &str, message: String) -> Diagnostic {
+        Diagnostic::new(Severity::Error, code, message)
+    }
+
+    pub fn warning(code: &str, message: String) -> Diagnostic {
+        Diagnostic::new(Severity::Warning, code, message)
+    }
+
+    //Attaches the location of the problem
+    pub fn withSpan(mut self, span: Span) -> Diagnostic {
+        self.span = Some(span);
+        self
+    }
 
+    //Adds an extra note printed under the source snippet
+    pub fn withNote(mut self, note: String) -> Diagnostic {
+        self.notes.push(note);
+        self
+    }
+
+    //Renders the diagnostic with the line of source it points at and a caret underline, for example:
+    //  error[P001]: Unexpected token
+    //   --> prog.src:3:10
+    //    |
+    //  3 |     x := $;
+    //    |          ^
+    pub fn render(&self, source: &str) -> String {
+        let mut rendered = format!("{}[{}]: {}\n", self.severity, self.code, self.message);
+
+        if let Some(span) = &self.span {
+            let lineText = if span.line > 0 { source.lines().nth(span.line - 1) } else { None };
+            let gutter = " ".repeat(span.line.to_string().len());
+
+            if span.startCol > 0 {
+                rendered.push_str(&format!("{}--> {}:{}:{}\n", gutter, span.file, span.line, span.startCol));
+            } else if span.line > 0 {
+                rendered.push_str(&format!("{}--> {}:{}\n", gutter, span.file, span.line));
+            } else {
+                rendered.push_str(&format!("{}--> {}\n", gutter, span.file));
+            }
+
+            if let Some(lineText) = lineText {
+                let lineText = lineText.trim_end_matches('\r');
+                let lineChars: Vec<char> = lineText.chars().collect();
+
+                //Works out which characters to underline, the whole line if the column is not known
+                let (start, end) = if span.startCol > 0 {
+                    let start = (span.startCol - 1).min(lineChars.len());
+                    let end = span.endCol.saturating_sub(1).max(start + 1);
+                    (start, end)
+                } else {
+                    let start = lineChars.iter().position(|c| !c.is_whitespace()).unwrap_or(0);
+                    (start, lineChars.len().max(start + 1))
+                };
+
+                //Copies the whitespace before the caret so tabs line up the same as in the source
+                let padding: String = lineChars.iter().take(start).map(|c| if *c == '\t' { '\t' } else { ' ' }).collect();
 
+                rendered.push_str(&format!("{} |\n", gutter));
+                rendered.push_str(&format!("{} | {}\n", span.line, lineText));
+                rendered.push_str(&format!("{} | {}{}\n", gutter, padding, "^".repeat(end - start)));
+            }
+
+            for note in &self.notes {
+                rendered.push_str(&format!("{} = note: {}\n", gutter, note));
+            }
+        } else {
+            for note in &self.notes {
+                rendered.push_str(&format!(" = note: {}\n", note));
+            }
+        }
+
+        return rendered;
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.span {
+            Some(span) if span.startCol > 0 => write!(f, "{}:{}:{}: {}[{}]: {}", span.file, span.line, span.startCol, self.severity, self.code, self.message),
+            Some(span) => write!(f, "{}:{}: {}[{}]: {}", span.file, span.line, self.severity, self.code, self.message),
+            None => write!(f, "{}[{}]: {}", self.severity, self.code, self.message),
+        }
+    }
+}
 
 //Structure for reporting errors and warnings
 #[derive(Debug, Clone, PartialEq)]
@@ -7,6 +159,7 @@ pub struct Reporting {
     pub status: bool,
     pub warnings: Vec<String>,
     pub errors: Vec<String>,
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl Reporting {
@@ -15,16 +168,55 @@ impl Reporting {
             status: false,
             warnings: Vec::new(),
             errors: Vec::new(),
+            diagnostics: Vec::new(),
         }
     }
 
-    pub fn reportError(&mut self, message: String) {
-        self.errors.push(message.clone());
-        self.status = true;
+    //Records an error, pointing at the place in the source if there is one
+    //Some have no place at all, like a problem setting up the target
+    pub fn reportError(&mut self, code: &str, message: String, span: Option<Span>) {
+        let mut diagnostic = Diagnostic::error(code, message);
+        diagnostic.span = span;
+        self.report(diagnostic);
     }
 
-    pub fn reportWarning(&mut self, message: String) {
-        self.warnings.push(message.clone());
+    //Records a warning, pointing at the place in the source if there is one
+    pub fn reportWarning(&mut self, code: &str, message: String, span: Option<Span>) {
+        let mut diagnostic = Diagnostic::warning(code, message);
+        diagnostic.span = span;
+        self.report(diagnostic);
+    }
+
+    //Records a full diagnostic, the message is also kept in the plain error or warning list
+    pub fn report(&mut self, diagnostic: Diagnostic) {
+        match diagnostic.severity {
+            Severity::Error => {
+                self.errors.push(diagnostic.message.clone());
+                self.status = true;
+            }
+            Severity::Warning => {
+                self.warnings.push(diagnostic.message.clone());
+            }
+            Severity::Note => {}
+        }
+        self.diagnostics.push(diagnostic);
+    }
+
+    //Adds all the diagnostics from another stage or scope to this one
+    pub fn absorb(&mut self, other: Reporting) {
+        for diagnostic in other.diagnostics {
+            self.report(diagnostic);
+        }
+    }
+
+    //Renders every diagnostic against the source text, in the order they were reported
+    pub fn render(&self, source: &str) -> String {
+        let mut rendered = String::new();
+        for diagnostic in &self.diagnostics {
+            rendered.push_str(&diagnostic.render(source));
+            rendered.push('\n');
+        }
+        return rendered;
     }
 }
 
@@ -32,4 +224,4 @@ impl std::fmt::Display for Reporting {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Errors: {:?}, Warnings: {:?}", self.errors, self.warnings)
     }
-}
\ No newline at end of file
+}
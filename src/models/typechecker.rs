@@ -38,8 +38,11 @@ use {
         Parser,
         Stmt,
         Expr,
+        Operator,
         VarType,
+        ParamMode,
     },
+    crate::models::reporting::{Reporting, Diagnostic, Span},
     std::io::prelude::*,
 
 };
@@ -49,6 +52,51 @@ use {
 
 
 ///////////////////////// TYPE CHECKING SECTION /////////////////////////
+//The kinds of problem the type checker reports, each has its own diagnostic code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckCode {
+    TypeMismatch,       //A value of one type used where another type is needed
+    Undeclared,         //A variable or procedure that is not declared in any visible scope
+    WrongKind,          //A name used as the wrong kind of thing, like calling a variable or indexing something that is not an array
+    ArgCount,           //A call with the wrong number of arguments
+    ArgMismatch,        //An argument that does not fit its parameter
+    Redeclared,         //A name declared twice in the same scope
+    ArraySize,          //Arrays of different sizes used together
+    IndexRange,         //An array index known at compile time that is outside the array
+    LoopControl,        //A break or continue outside of any loop
+    ForwardDecl,        //A forward declaration that is never defined or does not match its definition
+    CaseArm,            //A case arm value that is not an integer constant, or is handled twice
+    ReturnValue,        //A return that is missing its value, or has one in a procedure that does not return anything
+    BadParams,          //A procedure parameter list that is not made of parameter declarations
+    InvalidExpr,        //A statement or expression that failed for a reason already reported, or one that has no more specific code
+    Internal,           //A tree or symbol table the checker cannot make sense of, which means a bug in an earlier stage
+    LossyConversion,    //Warning: an implicit conversion that may change the value
+}
+
+impl CheckCode {
+    //The code shown in the diagnostic, errors are numbered from T001 and warnings from T101
+    pub fn code(&self) -> &'static str {
+        match self {
+            CheckCode::TypeMismatch => "T001",
+            CheckCode::Undeclared => "T002",
+            CheckCode::WrongKind => "T003",
+            CheckCode::ArgCount => "T004",
+            CheckCode::ArgMismatch => "T005",
+            CheckCode::Redeclared => "T006",
+            CheckCode::ArraySize => "T007",
+            CheckCode::IndexRange => "T008",
+            CheckCode::LoopControl => "T009",
+            CheckCode::ForwardDecl => "T010",
+            CheckCode::CaseArm => "T011",
+            CheckCode::ReturnValue => "T012",
+            CheckCode::BadParams => "T013",
+            CheckCode::InvalidExpr => "T014",
+            CheckCode::Internal => "T015",
+            CheckCode::LossyConversion => "T101",
+        }
+    }
+}
+
 //The main type checking structure
 pub struct SyntaxChecker<'a> {
     pub valid: bool,                        //The validity of the program
@@ -58,6 +106,12 @@ pub struct SyntaxChecker<'a> {
     pub globalTable: &'a mut SymbolTable,   //The global table, passed through every scope
     pub name: String,                       //the name of the program (or procedure if in a nested scope)
     pub checked: bool,                      //Set to true when the checking has been finished (used by procedures when scope increases)
+    pub retType: Option<VarType>,           //The return type of the procedure being checked, None for the program
+    pub params: Vec<ParamSig>,              //The signature of the procedure being checked, used for recursive calls
+    pub loopDepth: i32,                     //How many loops the statement being checked is inside, break and continue need at least one
+    pub reports: Reporting,                 //The errors and warnings found while checking
+    pub fileName: String,                   //The name of the source file, used for diagnostics
+    curLine: usize,                        //The line of the statement currently being checked
 }
 //The methods within typeChecker
 impl<'a> SyntaxChecker<'a> {
@@ -71,6 +125,12 @@ impl<'a> SyntaxChecker<'a> {
             globalTable,
             name,
             checked: false,
+            retType: None,
+            params: Vec::new(),
+            loopDepth: 0,
+            reports: Reporting::new(),
+            fileName: String::new(),
+            curLine: 0,
         }
     }
 
@@ -84,21 +144,67 @@ impl<'a> SyntaxChecker<'a> {
     where
         'a: 'b,
     {
+        //The procedures of the enclosing scopes can be called from inside, its variables can not be used
+        let mut procTable = SymbolTable::new();
+        for (itemName, item) in self.localTable.symTab.iter() {
+            if item.hashType != HashItemType::Variable {
+                procTable.symTab.insert(itemName.clone(), item.clone());
+            }
+        }
+
         SyntaxChecker {
             valid: true,
             ast: procAst,
             scope: curScope + 1,
-            localTable: SymbolTable::new(),
+            localTable: procTable,
             globalTable: self.globalTable,
             name,
             checked: false,
+            retType: None,
+            params: Vec::new(),
+            loopDepth: 0,
+            reports: Reporting::new(),
+            fileName: self.fileName.clone(),
+            curLine: self.curLine.clone(),
+        }
+    }
+
+    //Reports a semantic error on the line of the statement currently being checked
+    //Anything after the first line of the message is added as a note
+    fn reportError(&mut self, code: CheckCode, message: String) {
+        let mut lines = message.splitn(2, '\n');
+        let mainMsg = lines.next().unwrap_or("").trim().to_string();
+        let mut diagnostic = Diagnostic::error(code.code(), mainMsg).withSpan(Span::line(self.fileName.clone(), self.curLine));
+        if let Some(note) = lines.next() {
+            diagnostic = diagnostic.withNote(note.trim().to_string());
+        }
+        self.reports.report(diagnostic);
+    }
+
+    //Reports a warning on the line of the statement currently being checked
+    //Expressions can be checked more than once, so the same warning on the same line is only reported once
+    fn reportWarning(&mut self, code: CheckCode, message: String) {
+        let diagnostic = Diagnostic::warning(code.code(), message).withSpan(Span::line(self.fileName.clone(), self.curLine));
+        if !self.reports.diagnostics.contains(&diagnostic) {
+            self.reports.report(diagnostic);
+        }
+    }
+
+    //Reports a general error for something that failed, but only if nothing more specific was reported since errCount
+    //This keeps one mistake from being reported again by every statement it sits inside
+    fn reportIfSilent(&mut self, errCount: usize, message: String) {
+        if self.reports.errors.len() == errCount {
+            self.reportError(CheckCode::InvalidExpr, message);
         }
     }
 
     //The main outward facing checker, checks the two parts of the program
+    //Checking carries on past statements with errors so that every error in the program is reported
+    //Returns false if any errors were found
     pub fn checkProgram(&mut self) -> bool {
         match &self.ast.clone() {
             Stmt::Program(name, header, body, lineNum) => {
+                let mut allGood = true;
                 
                 //Parses and checks the header
                 let head = header.clone();
@@ -108,14 +214,15 @@ impl<'a> SyntaxChecker<'a> {
                     for instr in instrs {
                         let good = self.checkStmt(instr.clone());
                         if (!good){
-                            println!("Error in header:");
-                            instr.display(0);
-                            return false;
-                        } else {
+                            allGood = false;
                         }
                     }
+                    if !self.checkForwardsDefined(instrs) {
+                        allGood = false;
+                    }
                 } else {
-                    println!("Problem with AST: header must be a Block");
+                    self.reportError(CheckCode::Internal, "Problem with AST: header must be a Block".to_string());
+                    allGood = false;
                 }
 
                 // println!("Finished checking header:");
@@ -128,128 +235,73 @@ impl<'a> SyntaxChecker<'a> {
                     for instr in instrs {
                         let good = self.checkStmt(instr.clone());
                         if (!good){
-                            println!("Error in body:");
-                            return false;
-                        } else {
-                            //continue
+                            allGood = false;
                         }
                     }
                 } else {
-                    println!("Problem with AST: header must be a Block");
+                    self.reportError(CheckCode::Internal, "Problem with AST: body must be a Block".to_string());
+                    allGood = false;
                 }
                 self.checked = true;
-                return true
+                return allGood;
             }
             _ => {
-                println!("TypeChecker must be passed a Program AST");
+                self.reportError(CheckCode::Internal, "TypeChecker must be passed a Program AST".to_string());
                 return false;
             }
         }
     }
     
 
-    //For checking the compatability between 2 variable/constant types
-    fn checkTypeCompatability(&mut self, target: VarType, new: VarType) -> bool {
-        match target.clone(){
-            VarType::Bool => {
-                match new.clone(){
-                    VarType::Bool => {
-                        return true;
-                    }
-                    VarType::Float => {
-                        return false;
-                    }
-                    VarType::Int => {
-                        return true;
-                    }
-                    VarType::Str => {
-                        return false;
-                    }
-                    VarType::IntArray(size) => {
-                        return false;
-                    }
-                }
-            }
-            VarType::Float => {
-                match new.clone(){
-                    VarType::Bool => {
-                        return false;
-                    }
-                    VarType::Float => {
-                        return true;
-                    }
-                    VarType::Int => {
-                        return true;
-                    }
-                    VarType::Str => {
-                        return false;
-                    }
-                    VarType::IntArray(size) => {
-                        return false;
-                    }
+    //Gets the signature given by the parameter block of a procedure declaration
+    fn paramSignature(params: &Stmt) -> Vec<ParamSig> {
+        let mut signature: Vec<ParamSig> = Vec::new();
+        if let Stmt::Block(paramStmts, lineNum) = params {
+            for paramStmt in paramStmts {
+                if let Stmt::ParamDecl(varName, varType, paramMode, lineNum) = paramStmt {
+                    signature.push(ParamSig::new(varName.clone(), varType.clone(), paramMode.clone()));
                 }
             }
-            VarType::Int => {
-                // println!("Checking int");
-                match new.clone(){
-                    VarType::Bool => {
-                        return true;
-                    }
-                    VarType::Float => {
-                        return true;
-                    }
-                    VarType::Int => {
-                        return true;
-                    }
-                    VarType::Str => {
-                        return false;
-                    }
-                    VarType::IntArray(size) => {
-                        return false;
-                    }
+        }
+        return signature;
+    }
+
+    //Makes sure every forward declaration in a header is followed by the definition of its procedure
+    fn checkForwardsDefined(&mut self, instrs: &Vec<Stmt>) -> bool {
+        let mut allGood = true;
+        for (instrInd, instr) in instrs.iter().enumerate() {
+            if let Stmt::ProcForward(retType, procName, params, lineNum) = instr {
+                let defined = instrs[instrInd + 1..].iter().any(|later| matches!(later, Stmt::ProcDecl(_, laterName, _, _, _, _) if laterName == procName));
+                if !defined {
+                    self.curLine = lineNum.clone();
+                    self.reportError(CheckCode::ForwardDecl, format!("Procedure {} is declared forward but never defined in the same header", procName.clone()));
+                    allGood = false;
                 }
             }
-            VarType::Str => {
-                match new.clone(){
-                    VarType::Bool => {
-                        return false;
-                    }
-                    VarType::Float => {
-                        return false;
-                    }
-                    VarType::Int => {
-                        return false;
-                    }
-                    VarType::Str => {
-                        return true;
-                    }
-                    VarType::IntArray(size) => {
-                        return false;
+        }
+        return allGood;
+    }
+
+    //For checking the compatability between 2 variable/constant types
+    //A value is compatible if the coercion table has a conversion from its type to the target
+    fn checkTypeCompatability(&mut self, target: VarType, new: VarType) -> bool {
+        return Coercion::find(&new, &target).allowed();
+    }
+
+    //Warns when using a value of one type as another goes through a conversion that can change the value
+    //Integer constants small enough to be exact as a float are not warned about, so things like f := 1 stay quiet
+    fn warnIfLossy(&mut self, from: Option<VarType>, to: &VarType, value: &Expr, what: String) {
+        if let Some(fromType) = from {
+            let coercion = Coercion::find(&fromType, to);
+            if coercion == Coercion::IntToFloat {
+                if let Some(constVal) = self.constIntValue(value) {
+                    if Coercion::exactInFloat(constVal) {
+                        return;
                     }
                 }
             }
-            VarType::IntArray(targSize) => {
-                match new.clone(){
-                    VarType::Bool => {
-                        return false;
-                    }
-                    VarType::Float => {
-                        return false;
-                    }
-                    VarType::Int => {
-                        return false;
-                    }
-                    VarType::Str => {
-                        return false;
-                    }
-                    VarType::IntArray(newSize) => {
-                        if(targSize == newSize){
-                            return true;
-                        } else {
-                            return false;
-                        }
-                    }
-                }
+            if coercion.losesPrecision() {
+                self.reportWarning(CheckCode::LossyConversion, format!("Converting {} to {} for {} may lose precision", fromType, to, what));
             }
         }
     }
@@ -287,14 +339,14 @@ impl<'a> SyntaxChecker<'a> {
                                     return compat;
                                 }
                                 None => {
-                                    let varGlobTypeCheck = self.localTable.getType(&varName.clone());
+                                    let varGlobTypeCheck = self.globalTable.getType(&varName.clone());
                                     match varGlobTypeCheck{
                                         Some(varType) => {
                                             let compat = self.checkTypeCompatability(target.clone(), varType.clone());
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -312,14 +364,14 @@ impl<'a> SyntaxChecker<'a> {
                                     return compat;
                                 }
                                 None => {
-                                    let varGlobTypeCheck = self.localTable.getType(&varName.clone());
+                                    let varGlobTypeCheck = self.globalTable.getType(&varName.clone());
                                     match varGlobTypeCheck{
                                         Some(varType) => {
                                             let compat = self.checkTypeCompatability(target.clone(), varType.clone());
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -329,7 +381,14 @@ impl<'a> SyntaxChecker<'a> {
                             
                         }
                         Expr::ArrayRef(name, index) => {
-                            return true;
+                            match self.arrayElementType(&name) {
+                                Some(elemType) => {
+                                    return self.checkTypeCompatability(target.clone(), elemType);
+                                }
+                                None => {
+                                    return false;
+                                }
+                            }
                         }
                         
                         //Operations
@@ -339,6 +398,9 @@ impl<'a> SyntaxChecker<'a> {
                         Expr::LogOp(op1, op, op2) => {
                             return true;
                         }
+                        Expr::NotOp(operand) => {
+                            return true;
+                        }
                         Expr::RelOp(op1, op, op2) => {
                             return true;
                         }
@@ -379,14 +441,14 @@ impl<'a> SyntaxChecker<'a> {
                                     return compat;
                                 }
                                 None => {
-                                    let varGlobTypeCheck = self.localTable.getType(&varName.clone());
+                                    let varGlobTypeCheck = self.globalTable.getType(&varName.clone());
                                     match varGlobTypeCheck{
                                         Some(varType) => {
                                             let compat = self.checkTypeCompatability(target.clone(), varType.clone());
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -407,14 +469,14 @@ impl<'a> SyntaxChecker<'a> {
                                     return compat;
                                 }
                                 None => {
-                                    let varGlobTypeCheck = self.localTable.getType(&varName.clone());
+                                    let varGlobTypeCheck = self.globalTable.getType(&varName.clone());
                                     match varGlobTypeCheck{
                                         Some(varType) => {
                                             let compat = self.checkTypeCompatability(target.clone(), varType.clone());
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -424,7 +486,14 @@ impl<'a> SyntaxChecker<'a> {
                             
                         }
                         Expr::ArrayRef(name, index) => {
-                            return true;
+                            match self.arrayElementType(&name) {
+                                Some(elemType) => {
+                                    return self.checkTypeCompatability(target.clone(), elemType);
+                                }
+                                None => {
+                                    return false;
+                                }
+                            }
                         }
                         
                         //Operations
@@ -434,6 +503,9 @@ impl<'a> SyntaxChecker<'a> {
                         Expr::LogOp(op1, op, op2) => {
                             return false;
                         }
+                        Expr::NotOp(operand) => {
+                            return false;
+                        }
                         Expr::RelOp(op1, op, op2) => {
                             return false;
                         }
@@ -475,7 +547,7 @@ impl<'a> SyntaxChecker<'a> {
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -494,14 +566,14 @@ impl<'a> SyntaxChecker<'a> {
                                     return compat;
                                 }
                                 None => {
-                                    let varGlobTypeCheck = self.localTable.getType(&varName.clone());
+                                    let varGlobTypeCheck = self.globalTable.getType(&varName.clone());
                                     match varGlobTypeCheck{
                                         Some(varType) => {
                                             let compat = self.checkTypeCompatability(target.clone(), varType.clone());
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -511,7 +583,14 @@ impl<'a> SyntaxChecker<'a> {
                             
                         }
                         Expr::ArrayRef(name, index) => {
-                            return true;
+                            match self.arrayElementType(&name) {
+                                Some(elemType) => {
+                                    return self.checkTypeCompatability(target.clone(), elemType);
+                                }
+                                None => {
+                                    return false;
+                                }
+                            }
                         }
                         
                         //Operations
@@ -521,13 +600,20 @@ impl<'a> SyntaxChecker<'a> {
                         Expr::LogOp(op1, op, op2) => {
                             return true;
                         }
+                        Expr::NotOp(operand) => {
+                            return true;
+                        }
                         Expr::RelOp(op1, op, op2) => {
                             return true;
                         }
 
                     }
                 }
-                VarType::IntArray(targetSizee) => {
+                //Nothing can be converted to void, it is only the return type of a procedure that returns nothing
+                VarType::Void => {
+                    return false;
+                }
+                VarType::Array(targElem, targetSizee) => {
                     match new{
                         //Literals
                         Expr::IntLiteral(val) => {
@@ -543,7 +629,7 @@ impl<'a> SyntaxChecker<'a> {
                             return false;
                         }
                         Expr::IntArrayLiteral(size, val) => {
-                            if (targetSizee == size){
+                            if (*targElem == VarType::Int && targetSizee == size){
                                 return true;
                             } else {
                                 return false
@@ -559,14 +645,14 @@ impl<'a> SyntaxChecker<'a> {
                                     return compat;
                                 }
                                 None => {
-                                    let varGlobTypeCheck = self.localTable.getType(&varName.clone());
+                                    let varGlobTypeCheck = self.globalTable.getType(&varName.clone());
                                     match varGlobTypeCheck{
                                         Some(varType) => {
                                             let compat = self.checkTypeCompatability(target.clone(), varType.clone());
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -587,14 +673,14 @@ impl<'a> SyntaxChecker<'a> {
                                     return compat;
                                 }
                                 None => {
-                                    let varGlobTypeCheck = self.localTable.getType(&varName.clone());
+                                    let varGlobTypeCheck = self.globalTable.getType(&varName.clone());
                                     match varGlobTypeCheck{
                                         Some(varType) => {
                                             let compat = self.checkTypeCompatability(target.clone(), varType.clone());
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -604,16 +690,34 @@ impl<'a> SyntaxChecker<'a> {
                             
                         }
                         Expr::ArrayRef(name, index) => {
-                            return false;
+                            match self.arrayElementType(&name) {
+                                Some(elemType) => {
+                                    return self.checkTypeCompatability(target.clone(), elemType);
+                                }
+                                None => {
+                                    return false;
+                                }
+                            }
                         }
                         
                         //Operations
                         Expr::ArthOp(op1, op, op2) => {
-                            return false;
+                            //Whole array arithmetic gives an array
+                            match self.exprType(&Expr::ArthOp(op1.clone(), op.clone(), op2.clone())) {
+                                Some(newType) => {
+                                    return self.checkTypeCompatability(target.clone(), newType);
+                                }
+                                None => {
+                                    return false;
+                                }
+                            }
                         }
                         Expr::LogOp(op1, op, op2) => {
                             return false;
                         }
+                        Expr::NotOp(operand) => {
+                            return false;
+                        }
                         Expr::RelOp(op1, op, op2) => {
                             return false;
                         }
@@ -648,14 +752,14 @@ impl<'a> SyntaxChecker<'a> {
                                     return compat;
                                 }
                                 None => {
-                                    let varGlobTypeCheck = self.localTable.getType(&varName.clone());
+                                    let varGlobTypeCheck = self.globalTable.getType(&varName.clone());
                                     match varGlobTypeCheck{
                                         Some(varType) => {
                                             let compat = self.checkTypeCompatability(target.clone(), varType.clone());
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -676,14 +780,14 @@ impl<'a> SyntaxChecker<'a> {
                                     return compat;
                                 }
                                 None => {
-                                    let varGlobTypeCheck = self.localTable.getType(&varName.clone());
+                                    let varGlobTypeCheck = self.globalTable.getType(&varName.clone());
                                     match varGlobTypeCheck{
                                         Some(varType) => {
                                             let compat = self.checkTypeCompatability(target.clone(), varType.clone());
                                             return compat;
                                         }
                                         None => {
-                                            println!("Variable {} not defined", varName.clone());
+                                            self.reportError(CheckCode::Undeclared, format!("Variable {} not defined", varName.clone()));
                                             return false;
                                         }
                                     }
@@ -693,7 +797,14 @@ impl<'a> SyntaxChecker<'a> {
                             
                         }
                         Expr::ArrayRef(name, index) => {
-                            return false;
+                            match self.arrayElementType(&name) {
+                                Some(elemType) => {
+                                    return self.checkTypeCompatability(target.clone(), elemType);
+                                }
+                                None => {
+                                    return false;
+                                }
+                            }
                         }
                         
                         //Operations
@@ -703,6 +814,9 @@ impl<'a> SyntaxChecker<'a> {
                         Expr::LogOp(op1, op, op2) => {
                             return false;
                         }
+                        Expr::NotOp(operand) => {
+                            return false;
+                        }
                         Expr::RelOp(op1, op, op2) => {
                             return false;
                         }
@@ -743,185 +857,383 @@ impl<'a> SyntaxChecker<'a> {
         }
     }
 
-    pub fn checkExpr(&mut self, mut checkExpr: Expr) -> bool{
-        match checkExpr.clone(){
-            //Literals
-            Expr::IntLiteral(val) => {
+    //Gets the type of the elements in an array variable, None if it is not defined or not an array
+    fn arrayElementType(&mut self, arrName: &String) -> Option<VarType> {
+        match self.checkVar(arrName.clone()) {
+            Some(VarType::Array(elemType, size)) => {
+                return Some(*elemType);
+            }
+            _ => {
+                return None;
+            }
+        }
+    }
+
+    //Checks that the elements of an array used as an operand are one of the allowed types
+    fn checkElementOperand(&mut self, arrName: &String, allowed: &[VarType], opName: &str) -> bool {
+        match self.arrayElementType(arrName) {
+            Some(elemType) => {
+                if allowed.contains(&elemType) {
+                    return true;
+                }
+                self.reportError(CheckCode::TypeMismatch, format!("Cannot use element of {} array {} in {} operation", elemType, arrName.clone(), opName));
+                return false;
+            }
+            //Already reported when the reference itself was checked
+            None => {
                 return true;
             }
-            Expr::FloatLiteral(val) => {
+        }
+    }
+
+    //Checks a value being assigned to a target of the given type, used for variables, array elements and whole arrays
+    //The value has to be valid and its type has to fit the target
+    fn checkAssignValue(&mut self, targType: VarType, targName: &String, newValue: Expr, lineNum: usize) -> bool {
+        let errCount = self.reports.errors.len();
+        if !self.checkExpr(newValue.clone()) {
+            self.reportIfSilent(errCount, format!("Error with assigned value on line {}", lineNum.clone()));
+            return false;
+        }
+        match self.exprType(&newValue) {
+            Some(newType) => {
+                if self.checkTypeCompatability(targType.clone(), newType.clone()) {
+                    return true;
+                }
+                //Whole arrays are copied, so they have to be the same size
+                if let (VarType::Array(_, targSize), VarType::Array(_, newSize)) = (targType.clone(), newType.clone()) {
+                    if targSize != newSize {
+                        self.reportError(CheckCode::ArraySize, format!("Cannot assign {} to {} of type {}, array sizes do not match", newType, targName.clone(), targType));
+                        return false;
+                    }
+                }
+                self.reportError(CheckCode::TypeMismatch, format!("Cannot assign {} to {} of type {}", newType, targName.clone(), targType));
+                return false;
+            }
+            None => {
                 return true;
             }
-            Expr::StringLiteral(val) => {
+        }
+    }
+
+    //Works out the value of an integer expression made only of literals, None if it is not known until runtime
+    //Negative numbers are already single literals from the lexer
+    fn constIntValue(&self, expr: &Expr) -> Option<i64> {
+        match expr {
+            Expr::IntLiteral(val) => Some(*val),
+            Expr::ArthOp(op1, op, op2) => {
+                let val1 = self.constIntValue(op1)?;
+                let val2 = self.constIntValue(op2)?;
+                match op {
+                    Operator::Add => val1.checked_add(val2),
+                    Operator::Sub => val1.checked_sub(val2),
+                    Operator::Mul => val1.checked_mul(val2),
+                    Operator::Div => val1.checked_div(val2),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    //Checks an array index that is known at compile time against the declared size of the array
+    fn checkConstIndex(&mut self, arrName: &String, indexExpr: &Expr, size: i32) -> bool {
+        if let Some(index) = self.constIntValue(indexExpr) {
+            if index < 0 || index >= size as i64 {
+                self.reportError(CheckCode::IndexRange, format!("Array index {} is out of bounds for {}[{}] on line {}", index, arrName.clone(), size, self.curLine.clone()));
+                return false;
+            }
+        }
+        return true;
+    }
+
+    //Checks that an array index is an integer, a bool is used as 0 or 1
+    fn checkIndexType(&mut self, arrName: &String, indexExpr: &Expr) -> bool {
+        match self.exprType(indexExpr) {
+            Some(VarType::Int) | Some(VarType::Bool) | None => {
                 return true;
             }
-            Expr::BoolLiteral(val) => {
+            Some(indexType) => {
+                self.reportError(CheckCode::TypeMismatch, format!("Array index for {} must be an integer, found {}", arrName.clone(), indexType));
+                return false;
+            }
+        }
+    }
+
+    //Works out the type an expression evaluates to, used once the expression itself has been checked
+    //Returns None if the type cannot be worked out (for example an undefined name)
+    fn exprType(&mut self, expr: &Expr) -> Option<VarType> {
+        match expr {
+            Expr::IntLiteral(val) => Some(VarType::Int),
+            Expr::FloatLiteral(val) => Some(VarType::Float),
+            Expr::StringLiteral(val) => Some(VarType::Str),
+            Expr::BoolLiteral(val) => Some(VarType::Bool),
+            Expr::IntArrayLiteral(size, array) => Some(VarType::Array(Box::new(VarType::Int), *size)),
+            Expr::VarRef(name) => self.checkVar(name.clone()),
+            Expr::ProcRef(name, params) => self.procReturnType(name),
+            //Indexing an array gives one of its elements
+            Expr::ArrayRef(name, index) => self.arrayElementType(name),
+            //Arithmetic is done in the common type of the two sides
+            Expr::ArthOp(op1, op, op2) => {
+                let type1 = self.exprType(op1)?;
+                let type2 = self.exprType(op2)?;
+                Coercion::commonType(&type1, &type2)
+            }
+            Expr::RelOp(op1, op, op2) => Some(VarType::Bool),
+            //Logical operators on bools give a bool, on integers they are bitwise
+            Expr::LogOp(op1, op, op2) => {
+                let type1 = self.exprType(op1)?;
+                if type1 == VarType::Int {
+                    Some(VarType::Int)
+                } else {
+                    Some(VarType::Bool)
+                }
+            }
+            //Not keeps the type of its operand
+            Expr::NotOp(operand) => self.exprType(operand),
+        }
+    }
+
+    //Strings can not be compared, not even for equality, since the language only defines relational operators on numbers and bools
+    fn reportStringCompare(&mut self, operand: String) {
+        self.reportError(CheckCode::TypeMismatch, format!("Cannot compare {} on line {}, relational operators only work on integer, float and bool values, not strings", operand, self.curLine));
+    }
+
+    //Checks that the two operands of an arithmetic or relational operation can be converted to a common type
+    //Whole arrays are worked on element by element, so two arrays have to be the same size
+    fn checkOperandPair(&mut self, op1: &Expr, op2: &Expr, opName: &str) -> bool {
+        match (self.exprType(op1), self.exprType(op2)) {
+            (Some(VarType::Array(_, size1)), Some(VarType::Array(_, size2))) if size1 != size2 => {
+                self.reportError(CheckCode::ArraySize, format!("Array sizes do not match in {} operation: {} and {}", opName, size1, size2));
+                return false;
+            }
+            (Some(type1), Some(type2)) => {
+                if Coercion::commonType(&type1, &type2).is_none() {
+                    self.reportError(CheckCode::TypeMismatch, format!("Cannot use {} and {} together in {} operation", type1, type2, opName));
+                    return false;
+                }
                 return true;
             }
-            Expr::IntArrayLiteral(size, array) => {
+            _ => {
                 return true;
             }
-            
-            //References
-            Expr::VarRef(varName) => {
-                //Gets the type if defined in local scope
-                let checkLocVar = self.localTable.get(&varName.clone());
-                match checkLocVar{
-                    Some(var) => {
-                        if var.hashType != HashItemType::Variable {
-                            println!("{} is not a variable", varName.clone());
-                            return false;
-                        } else {
-                            return true;
-                        }
-                    }
-                    None => {
-                        let checkGlobVar = self.globalTable.get(&varName.clone());
-                            match checkGlobVar{
-                                Some(var) => {
-                                    if var.hashType != HashItemType::Variable {
-                                        println!("{} is not a variable", varName.clone());
-                                        return false;
-                                    } else {
-                                        return true;
-                                    }
-                                }
-                                None => {
-                                    println!("Variable {} is not defined", varName.clone());
-                                    return false;
-                                }
-                            }
-                    }
-                }
-                
+        }
+    }
+
+    //Checks the condition of an if or for statement
+    //The condition has to be a valid expression that gives a bool (integers are allowed and treated as bools)
+    fn checkCondition(&mut self, condition: Expr, stmtName: &str) -> bool {
+        let errCount = self.reports.errors.len();
+        if !self.checkExpr(condition.clone()) {
+            self.reportIfSilent(errCount, format!("Error in {} condition", stmtName));
+            return false;
+        }
+
+        match self.exprType(&condition) {
+            Some(VarType::Bool) | Some(VarType::Int) | None => {
+                return true;
             }
-            Expr::ProcRef(procName, params) => {
-                if (self.checked.clone() == false) & (self.name.clone() == procName.clone()){
-                    return true;
-                } else {
-                    //Gets the type if defined in local scope
-                    let checkProc = self.localTable.get(&procName.clone());
-                    match checkProc.clone(){
-                        Some(proc) => {
-                            if let HashItemType::Procedure(procAst, procParamList, mut procSt) = proc.hashType.clone() {
-                                //Proc found, need to check params now
-                                match params.clone(){
-                                    Some(paramsVec) => {
-                                        if (procParamList.len() == paramsVec.len()) {
-                                            //the numbers are correct at least
-                                            let mut i = 0;
-                                            //Checks all of the params
-                                            for param in paramsVec.clone() {
-                                                let targetTypeCheck = procSt.getType(&procParamList[i].clone());
-                                                match targetTypeCheck{
-                                                    Some(targetType) => {
-                                                        let compatable = self.checkExprTypeCompatability(targetType.clone(), param.clone());
-                                                        if compatable {
-                                                            //Continue to checking next param
-                                                        } else {
-                                                            println!("Error with call to procedure {}: param {} is type {}, which is incompatible with given type {}", procName.clone(), procParamList[i].clone(), targetType.clone(), param.clone());
-                                                            return false;
-                                                        }
-                                                    }
-                                                    None => {
-                                                        println!("Some sort of error with the procedure symbol table. Could not located defined parameter in table");
-                                                        return false;
-                                                    }
-                                                }
-                                                i += 1;
-                                            }
-                                            return true;
-
-                                        } else {
-                                            println!("Error with call to procedure {}: {} params required, {} provided", procName.clone(), paramsVec.len().to_string(), procParamList.len().clone().to_string())
-                                        }
-                                    }
-                                    None => {
-                                        if (procParamList.len() == 0){
-                                            return true;
-                                        } else {
-                                            println!("Procedure call to {} missing parameters", procName.clone());
-                                            return false;
-                                        }
-                                    }
-                                }
-                                return true;
-                            } else {
-                                println!("{} is not defined as a procedure", procName.clone());
-                                return false;
-                            }
+            Some(condType) => {
+                self.reportError(CheckCode::TypeMismatch, format!("{} condition must be a bool expression, found {}", stmtName, condType));
+                return false;
+            }
+        }
+    }
+
+    //Finds the signature of a procedure, including the one currently being checked so recursive calls work
+    //Reports an error and returns None if the name is not a procedure
+    fn procSignature(&mut self, procName: &String) -> Option<Vec<ParamSig>> {
+        if !self.checked && (self.name == *procName) {
+            return Some(self.params.clone());
+        }
+        let procItem = match self.localTable.get(procName) {
+            Some(item) => Some(item.clone()),
+            None => self.globalTable.get(procName).cloned(),
+        };
+        match procItem {
+            Some(item) => {
+                if let HashItemType::Procedure(procAst, signature, procSt) = item.hashType {
+                    return Some(signature);
+                }
+                self.reportError(CheckCode::WrongKind, format!("{} is not defined as a procedure", procName.clone()));
+                return None;
+            }
+            None => {
+                self.reportError(CheckCode::Undeclared, format!("Procedure {} is not defined", procName.clone()));
+                return None;
+            }
+        }
+    }
+
+    //Checks a call to a procedure, it has to exist and be given the right number and types of arguments
+    //Every argument is checked so all of the mistakes in one call are reported together
+    fn checkCall(&mut self, procName: String, params: Option<Vec<Expr>>) -> bool {
+        let signature = match self.procSignature(&procName) {
+            Some(signature) => signature,
+            None => {
+                return false;
+            }
+        };
+        let args = params.unwrap_or_default();
+        if args.len() != signature.len() {
+            self.reportError(CheckCode::ArgCount, format!("Call to {} gives {} arguments, but it takes {}", procName.clone(), args.len(), signature.len()));
+            return false;
+        }
+
+        let mut argsGood = true;
+        for (i, (arg, param)) in args.iter().zip(signature.iter()).enumerate() {
+            //Out parameters are passed by reference, so the argument has to be a variable of exactly the parameter type
+            if param.mode != ParamMode::In {
+                if !self.checkOutArgument(&procName, i + 1, param, arg) {
+                    argsGood = false;
+                }
+                continue;
+            }
+            if !self.checkExpr(arg.clone()) {
+                argsGood = false;
+                continue;
+            }
+            match self.exprType(arg) {
+                Some(argType) => {
+                    if self.checkTypeCompatability(param.paramType.clone(), argType.clone()) {
+                        //Warns if the argument is converted in a way that can change it
+                        self.warnIfLossy(Some(argType), &param.paramType, arg, format!("argument {} of {}", i + 1, procName.clone()));
+                    } else {
+                        self.reportError(CheckCode::ArgMismatch, format!("Argument {} of call to {} has type {}, but parameter {} expects {}", i + 1, procName.clone(), argType, param.name.clone(), param.paramType));
+                        argsGood = false;
+                    }
+                }
+                None => {
+                    //The type could not be worked out, whatever caused that has already been reported
+                }
+            }
+        }
+        return argsGood;
+    }
+
+    //Checks the argument given for an out or in out parameter
+    //The procedure writes through a pointer to it, so it has to be a variable or array element of exactly the parameter type
+    fn checkOutArgument(&mut self, procName: &String, position: usize, param: &ParamSig, arg: &Expr) -> bool {
+        let argType = match arg {
+            Expr::VarRef(varName) => {
+                let argItem = match self.localTable.get(varName) {
+                    Some(item) => Some(item.clone()),
+                    None => self.globalTable.get(varName).cloned(),
+                };
+                match argItem {
+                    Some(mut item) if item.hashType == HashItemType::Variable => item.getType(),
+                    Some(_) => {
+                        self.reportError(CheckCode::ArgMismatch, format!("{} is a procedure, so it cannot be given for {} parameter {} of {}", varName.clone(), param.mode, param.name.clone(), procName.clone()));
+                        return false;
+                    }
+                    None => {
+                        self.reportError(CheckCode::Undeclared, format!("Variable {} is not defined", varName.clone()));
+                        return false;
+                    }
+                }
+            }
+            Expr::ArrayRef(arrName, indexExpr) => {
+                if !self.checkExpr(arg.clone()) {
+                    return false;
+                }
+                match self.arrayElementType(arrName) {
+                    Some(elemType) => elemType,
+                    None => {
+                        return false;
+                    }
+                }
+            }
+            _ => {
+                self.reportError(CheckCode::ArgMismatch, format!("Argument {} of call to {} must be a variable or array element, {} parameter {} is written to, found {}", position, procName.clone(), param.mode, param.name.clone(), arg));
+                return false;
+            }
+        };
+        if argType != param.paramType {
+            self.reportError(CheckCode::ArgMismatch, format!("Argument {} of call to {} has type {}, but {} parameter {} expects exactly {}", position, procName.clone(), argType, param.mode, param.name.clone(), param.paramType));
+            return false;
+        }
+        return true;
+    }
+
+    //Gets the return type of a procedure, including the one currently being checked so recursive calls work
+    fn procReturnType(&mut self, procName: &String) -> Option<VarType> {
+        if !self.checked && (self.name == *procName) {
+            return self.retType.clone();
+        }
+        return self.checkVar(procName.clone());
+    }
+
+    pub fn checkExpr(&mut self, mut checkExpr: Expr) -> bool{
+        match checkExpr.clone(){
+            //Literals
+            Expr::IntLiteral(val) => {
+                return true;
+            }
+            Expr::FloatLiteral(val) => {
+                return true;
+            }
+            Expr::StringLiteral(val) => {
+                return true;
+            }
+            Expr::BoolLiteral(val) => {
+                return true;
+            }
+            Expr::IntArrayLiteral(size, array) => {
+                return true;
+            }
+            
+            //References
+            Expr::VarRef(varName) => {
+                //Gets the type if defined in local scope
+                let checkLocVar = self.localTable.get(&varName.clone());
+                match checkLocVar{
+                    Some(var) => {
+                        if var.hashType != HashItemType::Variable {
+                            self.reportError(CheckCode::WrongKind, format!("{} is not a variable", varName.clone()));
+                            return false;
+                        } else {
+                            return true;
                         }
-                        None => {
-                            //CHECK IN GLOBAL SCOPE GOES HERE
-                            //Gets the type if defined in local scope
-                            let checkGlobProc = self.globalTable.get(&procName.clone());
-                            match checkGlobProc.clone(){
-                                Some(proc) => {
-                                    if let HashItemType::Procedure(procAst, procParamList, mut procSt) = proc.hashType.clone() {
-                                        //Proc found, need to check params now
-                                        match params.clone(){
-                                            Some(paramsVec) => {
-                                                if (procParamList.len() == paramsVec.len()) {
-                                                    //the numbers are correct at least
-                                                    let mut i = 0;
-                                                    //Checks all of the params
-                                                    for param in paramsVec.clone() {
-                                                        let targetTypeCheck = procSt.getType(&procParamList[i].clone());
-                                                        match targetTypeCheck{
-                                                            Some(targetType) => {
-                                                                let compatable = self.checkExprTypeCompatability(targetType.clone(), param.clone());
-                                                                if compatable {
-                                                                    //Continue to checking next param
-                                                                } else {
-                                                                    println!("Error with call to procedure {}: param {} is type {}, which is incompatible with given type {}", procName.clone(), procParamList[i].clone(), targetType.clone(), param.clone());
-                                                                    return false;
-                                                                }
-                                                            }
-                                                            None => {
-                                                                println!("Some sort of error with the procedure symbol table. Could not located defined parameter in table");
-                                                                return false;
-                                                            }
-                                                        }
-                                                        i += 1;
-                                                    }
-                                                    return true;
-
-                                                } else {
-                                                    println!("Error with call to procedure {}: {} params required, {} provided", procName.clone(), paramsVec.len().to_string(), procParamList.len().clone().to_string());
-                                                    return false;
-                                                }
-                                            }
-                                            None => {
-                                                if (procParamList.len() == 0){
-                                                    return true;
-                                                } else {
-                                                    println!("Procedure call to {} missing parameters", procName.clone());
-                                                    return false;
-                                                }
-                                            }
-                                        }
-                                    } else {
-                                        println!("{} is not defined as a procedure", procName.clone());
+                    }
+                    None => {
+                        let checkGlobVar = self.globalTable.get(&varName.clone());
+                            match checkGlobVar{
+                                Some(var) => {
+                                    if var.hashType != HashItemType::Variable {
+                                        self.reportError(CheckCode::WrongKind, format!("{} is not a variable", varName.clone()));
                                         return false;
+                                    } else {
+                                        return true;
                                     }
                                 }
+                                //The procedure being checked is not in a table yet, but its name is still not a variable
+                                None if !self.checked && (self.name == varName) => {
+                                    self.reportError(CheckCode::WrongKind, format!("{} is not a variable", varName.clone()));
+                                    return false;
+                                }
                                 None => {
-                                    println!("Procedure {} is not defined", procName.clone());
+                                    self.reportError(CheckCode::Undeclared, format!("Variable {} is not defined", varName.clone()));
                                     return false;
                                 }
                             }
-                        }
                     }
                 }
                 
             }
+            Expr::ProcRef(procName, params) => {
+                //A procedure that returns nothing has no value to use, so it can only be called as a statement
+                if self.procReturnType(&procName) == Some(VarType::Void) {
+                    self.reportError(CheckCode::WrongKind, format!("Procedure {} does not return a value, so it cannot be used in an expression", procName.clone()));
+                    return false;
+                }
+                return self.checkCall(procName, params);
+            }
             Expr::ArrayRef(varName, indexExpr) => {
                 let existVar: VarType;
                 let checkLocVar = self.localTable.get(&varName.clone());
                 match checkLocVar{
                     Some(var) => {
                         if var.hashType != HashItemType::Variable {
-                            println!("{} is not a variable", varName.clone());
+                            self.reportError(CheckCode::WrongKind, format!("{} is not a variable", varName.clone()));
                             return false;
                         } else {
                             existVar = var.clone().getType().clone();
@@ -932,14 +1244,14 @@ impl<'a> SyntaxChecker<'a> {
                             match checkGlobVar{
                                 Some(var) => {
                                     if var.hashType != HashItemType::Variable {
-                                        println!("{} is not a variable", varName.clone());
+                                        self.reportError(CheckCode::WrongKind, format!("{} is not a variable", varName.clone()));
                                         return false;
                                     } else {
                                         existVar = var.clone().getType().clone();
                                     }
                                 }
                                 None => {
-                                    println!("Variable {} is not defined", varName.clone());
+                                    self.reportError(CheckCode::Undeclared, format!("Variable {} is not defined", varName.clone()));
                                     return false;
                                 }
                             }
@@ -947,18 +1259,22 @@ impl<'a> SyntaxChecker<'a> {
                 }
                 
                 match existVar{
-                    VarType::IntArray(size) => {
-                        let checkedExpr =  self.checkExpr(*indexExpr);
+                    VarType::Array(elemType, size) => {
+                        let checkedExpr =  self.checkExpr(*indexExpr.clone());
                         if checkedExpr {
-                            return true;
+                            if !self.checkIndexType(&varName, &indexExpr) {
+                                return false;
+                            }
+                            //Indexes known at compile time have to be inside the array
+                            return self.checkConstIndex(&varName, &indexExpr, size);
                         }
                         else {
-                            println!("Error with array index");
+                            self.reportError(CheckCode::InvalidExpr, format!("Error with array index"));
                             return false;
                         }
                     }
                     _ => {
-                        println!("Variable {} is not an array", varName.clone());
+                        self.reportError(CheckCode::WrongKind, format!("Variable {} is not an array", varName.clone()));
                         return false;
                     }
                 }                
@@ -967,20 +1283,22 @@ impl<'a> SyntaxChecker<'a> {
             //Operations
             Expr::ArthOp(op1, op, op2) => {
                 //First checks operand 1 to ensure it is valid
+                let errCount = self.reports.errors.len();
                 let checkedOp1 = self.checkExpr(*op1.clone());
                 if !checkedOp1 {
-                    println!("Error in operand one of arithmetic operation");
+                    self.reportIfSilent(errCount, format!("Error in operand one of arithmetic operation"));
                     return false;
                 }
                 //Checks operand 2
+                let errCount = self.reports.errors.len();
                 let checkedOp2 = self.checkExpr(*op2.clone());
                 if !checkedOp2{
-                    println!("Error in operand two of arithmetic operation");
+                    self.reportIfSilent(errCount, format!("Error in operand two of arithmetic operation"));
                     return false;
                 }
 
                 //Since both are good, need to ensure both are compatabile with ArthOps
-                match *op1 {
+                match *op1.clone() {
                     Expr::IntLiteral(val) => {
                         //continue
                     }
@@ -988,15 +1306,15 @@ impl<'a> SyntaxChecker<'a> {
                         //continue
                     }
                     Expr::StringLiteral(val) => {
-                        println!("Cannot use string in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use string in arithmetic operation"));
                         return false;
                     }
                     Expr::BoolLiteral(val) => {
-                        println!("Cannot use boolean as operand in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use boolean as operand in arithmetic operation"));
                         return false;
                     }
                     Expr::IntArrayLiteral(size, val) => {
-                        println!("Cannot use entire array in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use entire array in arithmetic operation"));
                         return false;
                     }
                     Expr::VarRef(varName) => {
@@ -1007,7 +1325,7 @@ impl<'a> SyntaxChecker<'a> {
                                 op1Type = foundType;
                             }
                             None => {
-                                println!("Referenced to undefined {}", varName.clone());
+                                self.reportError(CheckCode::Undeclared, format!("Referenced to undefined {}", varName.clone()));
                                 return false;
                             }
                         }
@@ -1020,11 +1338,11 @@ impl<'a> SyntaxChecker<'a> {
                             VarType::Int => {
                                 //continue
                             }
-                            VarType::IntArray(size) => {
+                            VarType::Array(elemType, size) if *elemType == VarType::Int || *elemType == VarType::Float => {
                                 //continue
                             }
                             _ => {
-                                println!("Cannot use variable {} of type {} in arithmetic operation", varName.clone(), op1Type.clone());
+                                self.reportError(CheckCode::TypeMismatch, format!("Cannot use variable {} of type {} in arithmetic operation", varName.clone(), op1Type.clone()));
                                 return false;
                             }
                         }
@@ -1040,7 +1358,7 @@ impl<'a> SyntaxChecker<'a> {
                                     op1Type = foundType;
                                 }
                                 None => {
-                                    println!("Referenced to undefined {}", procName.clone());
+                                    self.reportError(CheckCode::Undeclared, format!("Referenced to undefined {}", procName.clone()));
                                     return false;
                                 }
                             }
@@ -1054,30 +1372,36 @@ impl<'a> SyntaxChecker<'a> {
                                     //continue
                                 }
                                 _ => {
-                                    println!("Cannot use procedure {} of type {} in arithmetic operation", procName.clone(), op1Type.clone());
+                                    self.reportError(CheckCode::TypeMismatch, format!("Cannot use procedure {} of type {} in arithmetic operation", procName.clone(), op1Type.clone()));
                                     return false;
                                 }
                             }
                         }
                     }
                     Expr::ArrayRef(varName, indexExpr) => {
-                        //continue
+                        if !self.checkElementOperand(&varName, &[VarType::Int, VarType::Float], "arithmetic") {
+                            return false;
+                        }
                     }
                     Expr::ArthOp(operand1, op, operand2) => {
                         //continue
                     }
                     Expr::LogOp(operand1, oeprator, operand2) => {
-                        println!("Cannot use a logical operation as an operand in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use a logical operation as an operand in arithmetic operation"));
+                        return false;
+                    }
+                    Expr::NotOp(operand) => {
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use a logical operation as an operand in arithmetic operation"));
                         return false;
                     }
                     Expr::RelOp(operand1, operator, operand2) => {
-                        println!("Cannot use a relational operation as an operand in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use a relational operation as an operand in arithmetic operation"));
                         return false;
                     }
                 }
 
                 //Checks the compatability of operand 2
-                match *op2 {
+                match *op2.clone() {
                     Expr::IntLiteral(val) => {
                         //continue
                     }
@@ -1085,15 +1409,15 @@ impl<'a> SyntaxChecker<'a> {
                         //continue
                     }
                     Expr::StringLiteral(val) => {
-                        println!("Cannot use string in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use string in arithmetic operation"));
                         return false;
                     }
                     Expr::BoolLiteral(val) => {
-                        println!("Cannot use boolean as operand in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use boolean as operand in arithmetic operation"));
                         return false;
                     }
                     Expr::IntArrayLiteral(size, val) => {
-                        println!("Cannot use entire array in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use entire array in arithmetic operation"));
                         return false;
                     }
                     Expr::VarRef(varName) => {
@@ -1104,7 +1428,7 @@ impl<'a> SyntaxChecker<'a> {
                                 op1Type = foundType;
                             }
                             None => {
-                                println!("Referenced to undefined {}", varName.clone());
+                                self.reportError(CheckCode::Undeclared, format!("Referenced to undefined {}", varName.clone()));
                                 return false;
                             }
                         }
@@ -1117,11 +1441,11 @@ impl<'a> SyntaxChecker<'a> {
                             VarType::Int => {
                                 //continue
                             }
-                            VarType::IntArray(size) => {
+                            VarType::Array(elemType, size) if *elemType == VarType::Int || *elemType == VarType::Float => {
                                 //continue
                             }
                             _ => {
-                                println!("Cannot use variable {} of type {} in arithmetic operation", varName.clone(), op1Type.clone());
+                                self.reportError(CheckCode::TypeMismatch, format!("Cannot use variable {} of type {} in arithmetic operation", varName.clone(), op1Type.clone()));
                                 return false;
                             }
                         }
@@ -1138,7 +1462,7 @@ impl<'a> SyntaxChecker<'a> {
                                     op1Type = foundType;
                                 }
                                 None => {
-                                    println!("Referenced to undefined {}", procName.clone());
+                                    self.reportError(CheckCode::Undeclared, format!("Referenced to undefined {}", procName.clone()));
                                     return false;
                                 }
                             }
@@ -1152,66 +1476,126 @@ impl<'a> SyntaxChecker<'a> {
                                     //continue
                                 }
                                 _ => {
-                                    println!("Cannot use procedure {} of type {} in arithmetic operation", procName.clone(), op1Type.clone());
+                                    self.reportError(CheckCode::TypeMismatch, format!("Cannot use procedure {} of type {} in arithmetic operation", procName.clone(), op1Type.clone()));
                                     return false;
                                 }
                             }
                         }
                     }
                     Expr::ArrayRef(varName, indexExpr) => {
-                        //continue
+                        if !self.checkElementOperand(&varName, &[VarType::Int, VarType::Float], "arithmetic") {
+                            return false;
+                        }
                     }
                     Expr::ArthOp(operand1, op, operand2) => {
                         //continue
                     }
                     Expr::LogOp(operand1, oeprator, operand2) => {
-                        println!("Cannot use a logical operation as an operand in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use a logical operation as an operand in arithmetic operation"));
+                        return false;
+                    }
+                    Expr::NotOp(operand) => {
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use a logical operation as an operand in arithmetic operation"));
                         return false;
                     }
                     Expr::RelOp(operand1, operator, operand2) => {
-                        println!("Cannot use a relational operation as an operand in arithmetic operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use a relational operation as an operand in arithmetic operation"));
                         return false;
                     }
                 }
 
 
+                //Both sides have to convert to a common type, whole arrays are worked on element by element
+                if !self.checkOperandPair(&op1, &op2, "arithmetic") {
+                    return false;
+                }
+
                 //Now that we are here and everything has been checked, we are good
                 return true;
             }
             
             Expr::LogOp(op1, op, op2) => {
                 //First checks operand 1 to ensure it is valid
+                let errCount = self.reports.errors.len();
+                let checkedOp1 = self.checkExpr(*op1.clone());
+                if !checkedOp1 {
+                    self.reportIfSilent(errCount, format!("Error in operand one of logical operation"));
+                    return false;
+                }
+                //Checks operand 2
+                let errCount = self.reports.errors.len();
+                let checkedOp2 = self.checkExpr(*op2.clone());
+                if !checkedOp2{
+                    self.reportIfSilent(errCount, format!("Error in operand two of logical operation"));
+                    return false;
+                }
+
+                //Logical operators work on two bools, or bitwise on two integers
+                //Operands are relational operations, other logical operations, or anything else that gives a bool or an integer
+                match (self.exprType(&op1), self.exprType(&op2)) {
+                    (Some(VarType::Bool), Some(VarType::Bool)) | (Some(VarType::Int), Some(VarType::Int)) => {
+                        return true;
+                    }
+                    //Already reported when the operand was checked
+                    (None, _) | (_, None) => {
+                        return true;
+                    }
+                    (Some(type1), Some(type2)) => {
+                        self.reportError(CheckCode::TypeMismatch, format!("Logical operation needs two bools or two integers, found {} and {}", type1, type2));
+                        return false;
+                    }
+                }
+            }
+            //Not is logical on a bool and bitwise on an integer
+            Expr::NotOp(operand) => {
+                let errCount = self.reports.errors.len();
+                if !self.checkExpr(*operand.clone()) {
+                    self.reportIfSilent(errCount, format!("Error in operand of not operation"));
+                    return false;
+                }
+                match self.exprType(&operand) {
+                    Some(VarType::Bool) | Some(VarType::Int) | None => {
+                        return true;
+                    }
+                    Some(operandType) => {
+                        self.reportError(CheckCode::TypeMismatch, format!("Not needs a bool or an integer, found {}", operandType));
+                        return false;
+                    }
+                }
+            }
+            Expr::RelOp(op1, op, op2) => {
+                //First checks operand 1 to ensure it is valid
+                let errCount = self.reports.errors.len();
                 let checkedOp1 = self.checkExpr(*op1.clone());
                 if !checkedOp1 {
-                    println!("Error in operand one of arithmetic operation");
+                    self.reportIfSilent(errCount, format!("Error in operand one of relational operation"));
                     return false;
                 }
                 //Checks operand 2
+                let errCount = self.reports.errors.len();
                 let checkedOp2 = self.checkExpr(*op2.clone());
                 if !checkedOp2{
-                    println!("Error in operand two of arithmetic operation");
+                    self.reportIfSilent(errCount, format!("Error in operand two of relational operation"));
                     return false;
                 }
 
                 //Since both are good, need to ensure both are compatabile with ArthOps
-                match *op1 {
+                match *op1.clone() {
                     Expr::IntLiteral(val) => {
                         //continue
                     }
                     Expr::FloatLiteral(val) => {
-                        println!("Cannot use float as operand in logical operation");
-                        return false;
+                        //continue
                     }
                     Expr::StringLiteral(val) => {
-                        println!("Cannot use string as operand in arithmetic operation");
+                        self.reportStringCompare(format!("string \"{}\"", val));
                         return false;
                     }
                     Expr::BoolLiteral(val) => {
-                        println!("Cannot use string as operand in arithmetic operation");
-                        return false;
+                        //continue
                     }
                     Expr::IntArrayLiteral(size, val) => {
-                        println!("Cannot use entire array as operand in logical operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use entire array as operand in logical operation"));
                         return false;
                     }
                     Expr::VarRef(varName) => {
@@ -1222,7 +1606,7 @@ impl<'a> SyntaxChecker<'a> {
                                 op1Type = foundType;
                             }
                             None => {
-                                println!("Referenced to undefined {}", varName.clone());
+                                self.reportError(CheckCode::Undeclared, format!("Referenced to undefined {}", varName.clone()));
                                 return false;
                             }
                         }
@@ -1232,11 +1616,18 @@ impl<'a> SyntaxChecker<'a> {
                             VarType::Int => {
                                 //continue
                             }
-                            VarType::IntArray(size) => {
+                            VarType::Float => {
+                                //continue
+                            }
+                            VarType::Bool => {
                                 //continue
                             }
+                            VarType::Str => {
+                                self.reportStringCompare(format!("variable {}", varName.clone()));
+                                return false;
+                            }
                             _ => {
-                                println!("Cannot use variable {} of type {} in logical operation", varName.clone(), op1Type.clone());
+                                self.reportError(CheckCode::TypeMismatch, format!("Cannot use variable {} of type {} in relational operation", varName.clone(), op1Type.clone()));
                                 return false;
                             }
                         }
@@ -1250,7 +1641,7 @@ impl<'a> SyntaxChecker<'a> {
                                 op1Type = foundType;
                             }
                             None => {
-                                println!("Referenced to undefined {}", procName.clone());
+                                self.reportError(CheckCode::Undeclared, format!("Referenced to undefined {}", procName.clone()));
                                 return false;
                             }
                         }
@@ -1260,48 +1651,59 @@ impl<'a> SyntaxChecker<'a> {
                             VarType::Int => {
                                 //continue
                             }
+                            VarType::Float => {
+                                //continue
+                            }
+                            VarType::Bool => {
+                                //continue
+                            }
+                            VarType::Str => {
+                                self.reportStringCompare(format!("procedure {}", procName.clone()));
+                                return false;
+                            }
                             _ => {
-                                println!("Cannot use procedure {} of type {} in logical operation", procName.clone(), op1Type.clone());
+                                self.reportError(CheckCode::TypeMismatch, format!("Cannot use procedure {} of type {} in relational operation", procName.clone(), op1Type.clone()));
                                 return false;
                             }
                         }
                     }
                     Expr::ArrayRef(varName, indexExpr) => {
-                        //continue
+                        if !self.checkElementOperand(&varName, &[VarType::Int, VarType::Float, VarType::Bool], "relational") {
+                            return false;
+                        }
                     }
                     Expr::ArthOp(operand1, op, operand2) => {
                         //continue
                     }
                     Expr::LogOp(operand1, oeprator, operand2) => {
-                        println!("Cannot use a logical operation as an operand in logical operation");
-                        return false;
+                        //continue
+                    }
+                    Expr::NotOp(operand) => {
+                        //continue
                     }
                     Expr::RelOp(operand1, operator, operand2) => {
-                        println!("Cannot use a relational operation as an operand in logical operation");
-                        return false;
+                        //continue
                     }
                }
 
 
-                //Checks the compatability of operand 2
-                match *op2 {
+               //Checks the compatability of operand 2
+                match *op2.clone() {
                     Expr::IntLiteral(val) => {
                         //continue
                     }
                     Expr::FloatLiteral(val) => {
-                        println!("Cannot use float as operand in logical operation");
-                        return false;
+                        //continue
                     }
                     Expr::StringLiteral(val) => {
-                        println!("Cannot use string as operand in arithmetic operation");
+                        self.reportStringCompare(format!("string \"{}\"", val));
                         return false;
                     }
                     Expr::BoolLiteral(val) => {
-                        println!("Cannot use string as operand in arithmetic operation");
-                        return false;
+                        //continue
                     }
                     Expr::IntArrayLiteral(size, val) => {
-                        println!("Cannot use entire array as operand in logical operation");
+                        self.reportError(CheckCode::TypeMismatch, format!("Cannot use entire array as operand in logical operation"));
                         return false;
                     }
                     Expr::VarRef(varName) => {
@@ -1312,7 +1714,7 @@ impl<'a> SyntaxChecker<'a> {
                                 op1Type = foundType;
                             }
                             None => {
-                                println!("Referenced to undefined {}", varName.clone());
+                                self.reportError(CheckCode::Undeclared, format!("Referenced to undefined {}", varName.clone()));
                                 return false;
                             }
                         }
@@ -1322,11 +1724,18 @@ impl<'a> SyntaxChecker<'a> {
                             VarType::Int => {
                                 //continue
                             }
-                            VarType::IntArray(size) => {
+                            VarType::Float => {
+                                //continue
+                            }
+                            VarType::Bool => {
                                 //continue
                             }
+                            VarType::Str => {
+                                self.reportStringCompare(format!("variable {}", varName.clone()));
+                                return false;
+                            }
                             _ => {
-                                println!("Cannot use variable {} of type {} in logical operation", varName.clone(), op1Type.clone());
+                                self.reportError(CheckCode::TypeMismatch, format!("Cannot use variable {} of type {} in relational operation", varName.clone(), op1Type.clone()));
                                 return false;
                             }
                         }
@@ -1340,7 +1749,7 @@ impl<'a> SyntaxChecker<'a> {
                                 op1Type = foundType;
                             }
                             None => {
-                                println!("Referenced to undefined {}", procName.clone());
+                                self.reportError(CheckCode::Undeclared, format!("Referenced to undefined {}", procName.clone()));
                                 return false;
                             }
                         }
@@ -1350,259 +1759,76 @@ impl<'a> SyntaxChecker<'a> {
                             VarType::Int => {
                                 //continue
                             }
+                            VarType::Float => {
+                                //continue
+                            }
+                            VarType::Bool => {
+                                //continue
+                            }
+                            VarType::Str => {
+                                self.reportStringCompare(format!("procedure {}", procName.clone()));
+                                return false;
+                            }
                             _ => {
-                                println!("Cannot use procedure {} of type {} in logical operation", procName.clone(), op1Type.clone());
+                                self.reportError(CheckCode::TypeMismatch, format!("Cannot use procedure {} of type {} in relational operation", procName.clone(), op1Type.clone()));
                                 return false;
                             }
                         }
                     }
                     Expr::ArrayRef(varName, indexExpr) => {
-                        //continue
+                        if !self.checkElementOperand(&varName, &[VarType::Int, VarType::Float, VarType::Bool], "relational") {
+                            return false;
+                        }
                     }
                     Expr::ArthOp(operand1, op, operand2) => {
                         //continue
                     }
                     Expr::LogOp(operand1, oeprator, operand2) => {
-                        println!("Cannot use a logical operation as an operand in logical operation");
-                        return false;
+                        //continue
+                    }
+                    Expr::NotOp(operand) => {
+                        //continue
                     }
                     Expr::RelOp(operand1, operator, operand2) => {
-                        println!("Cannot use a relational operation as an operand in logical operation");
-                        return false;
+                        //continue
                     }
                 }
 
-                //Now that we are here and everything has been checked, we are good
-                return true;
-            }
-            Expr::RelOp(op1, op, op2) => {
-                //First checks operand 1 to ensure it is valid
-                let checkedOp1 = self.checkExpr(*op1.clone());
-                if !checkedOp1 {
-                    println!("Error in operand one of relational operation");
-                    return false;
-                }
-                //Checks operand 2
-                let checkedOp2 = self.checkExpr(*op2.clone());
-                if !checkedOp2{
-                    println!("Error in operand two of relational operation");
+                //Both sides have to convert to a common type to be compared
+                if !self.checkOperandPair(&op1, &op2, "relational") {
                     return false;
                 }
 
-                //Since both are good, need to ensure both are compatabile with ArthOps
-                match *op1 {
-                    Expr::IntLiteral(val) => {
-                        //continue
-                    }
-                    Expr::FloatLiteral(val) => {
-                        //continue
-                    }
-                    Expr::StringLiteral(val) => {
-                        println!("Cannot use string as operand in relational operation");
+                //Now that we are here and everything has been checked, we are good
+                return true;
+            }
+        }
+    }
+    
+    //Checks each statement one at a time, returns a bool if there's an error
+    pub fn checkStmt(&mut self, mut checkStmt: Stmt) -> bool{
+        //Errors found in this statement are reported on its line
+        let stmtLine = checkStmt.lineNum();
+        if stmtLine != 0 {
+            self.curLine = stmtLine;
+        }
+        match (checkStmt){
+            //For checking and declaring local variables
+            Stmt::VarDecl(varName, varType, lineNum) => {
+                if self.scope != 0 {
+                    let defined = self.localTable.checkItem(&varName.clone());
+                    if(defined){
+                        self.reportError(CheckCode::Redeclared, format!("Variable {} defined twice", varName.clone()));
                         return false;
-                    }
-                    Expr::BoolLiteral(val) => {
-                        //continue
-                    }
-                    Expr::IntArrayLiteral(size, val) => {
-                        println!("Cannot use entire array as operand in logical operation");
-                        return false;
-                    }
-                    Expr::VarRef(varName) => {
-                        let mut op1Type: VarType;
-                        let op1TypeCheck = self.checkVar(varName.clone());
-                        match op1TypeCheck{
-                            Some(foundType) => {
-                                op1Type = foundType;
-                            }
-                            None => {
-                                println!("Referenced to undefined {}", varName.clone());
-                                return false;
-                            }
-                        }
-                    
-                        //Now we have to check if the type is compatible with the arthop
-                        match op1Type{
-                            VarType::Int => {
-                                //continue
-                            }
-                            VarType::Float => {
-                                //continue
-                            }
-                            VarType::Bool => {
-                                //continue
-                            }
-                            _ => {
-                                println!("Cannot use variable {} of type {} in relational operation", varName.clone(), op1Type.clone());
-                                return false;
-                            }
-                        }
-                    
-                    }
-                    Expr::ProcRef(procName, params) => {
-                        let mut op1Type: VarType;
-                        let op1TypeCheck = self.checkVar(procName.clone());
-                        match op1TypeCheck{
-                            Some(foundType) => {
-                                op1Type = foundType;
-                            }
-                            None => {
-                                println!("Referenced to undefined {}", procName.clone());
-                                return false;
-                            }
-                        }
-                    
-                        //Now we have to check if the type is compatible with the arthop
-                        match op1Type{
-                            VarType::Int => {
-                                //continue
-                            }
-                            VarType::Float => {
-                                //continue
-                            }
-                            VarType::Bool => {
-                                //continue
-                            }
-                            _ => {
-                                println!("Cannot use procedure {} of type {} in relational operation", procName.clone(), op1Type.clone());
-                                return false;
-                            }
-                        }
-                    }
-                    Expr::ArrayRef(varName, indexExpr) => {
-                        //continue
-                    }
-                    Expr::ArthOp(operand1, op, operand2) => {
-                        //continue
-                    }
-                    Expr::LogOp(operand1, oeprator, operand2) => {
-                        //continue
-                    }
-                    Expr::RelOp(operand1, operator, operand2) => {
-                        //continue
-                    }
-               }
-
-
-               //Checks the compatability of operand 2
-                match *op2 {
-                    Expr::IntLiteral(val) => {
-                        //continue
-                    }
-                    Expr::FloatLiteral(val) => {
-                        //continue
-                    }
-                    Expr::StringLiteral(val) => {
-                        println!("Cannot use string as operand in relational operation");
-                        return false;
-                    }
-                    Expr::BoolLiteral(val) => {
-                        //continue
-                    }
-                    Expr::IntArrayLiteral(size, val) => {
-                        println!("Cannot use entire array as operand in logical operation");
-                        return false;
-                    }
-                    Expr::VarRef(varName) => {
-                        let mut op1Type: VarType;
-                        let op1TypeCheck = self.checkVar(varName.clone());
-                        match op1TypeCheck{
-                            Some(foundType) => {
-                                op1Type = foundType;
-                            }
-                            None => {
-                                println!("Referenced to undefined {}", varName.clone());
-                                return false;
-                            }
-                        }
-                    
-                        //Now we have to check if the type is compatible with the arthop
-                        match op1Type{
-                            VarType::Int => {
-                                //continue
-                            }
-                            VarType::Float => {
-                                //continue
-                            }
-                            VarType::Bool => {
-                                //continue
-                            }
-                            _ => {
-                                println!("Cannot use variable {} of type {} in relational operation", varName.clone(), op1Type.clone());
-                                return false;
-                            }
-                        }
-                    
-                    }
-                    Expr::ProcRef(procName, params) => {
-                        let mut op1Type: VarType;
-                        let op1TypeCheck = self.checkVar(procName.clone());
-                        match op1TypeCheck{
-                            Some(foundType) => {
-                                op1Type = foundType;
-                            }
-                            None => {
-                                println!("Referenced to undefined {}", procName.clone());
-                                return false;
-                            }
-                        }
-                    
-                        //Now we have to check if the type is compatible with the arthop
-                        match op1Type{
-                            VarType::Int => {
-                                //continue
-                            }
-                            VarType::Float => {
-                                //continue
-                            }
-                            VarType::Bool => {
-                                //continue
-                            }
-                            _ => {
-                                println!("Cannot use procedure {} of type {} in relational operation", procName.clone(), op1Type.clone());
-                                return false;
-                            }
-                        }
-                    }
-                    Expr::ArrayRef(varName, indexExpr) => {
-                        //continue
-                    }
-                    Expr::ArthOp(operand1, op, operand2) => {
-                        //continue
-                    }
-                    Expr::LogOp(operand1, oeprator, operand2) => {
-                        //continue
-                    }
-                    Expr::RelOp(operand1, operator, operand2) => {
-                        //continue
-                    }
-                }
-
-                //Now that we are here and everything has been checked, we are good
-                return true;
-            }
-        }
-    }
-    
-    //Checks each statement one at a time, returns a bool if there's an error
-    pub fn checkStmt(&mut self, mut checkStmt: Stmt) -> bool{
-        match (checkStmt){
-            //For checking and declaring local variables
-            Stmt::VarDecl(varName, varType, lineNum) => {
-                if self.scope != 0 {
-                    let defined = self.localTable.checkItem(&varName.clone());
-                    if(defined){
-                        println!("Error: variable: {} defined twice", varName.clone());
-                        return false;
-                    } else {
-                        let item = HashItem::newVar(varName.clone(), varType.clone());
-                        self.localTable.symTab.insert(varName.clone(), item.clone());
-                        return true;
+                    } else {
+                        let item = HashItem::newVar(varName.clone(), varType.clone());
+                        self.localTable.symTab.insert(varName.clone(), item.clone());
+                        return true;
                     }
                 } else {
                     let defined = self.globalTable.checkItem(&varName.clone());
                     if(defined){
-                        println!("Error: variable: {} defined twice", varName.clone());
+                        self.reportError(CheckCode::Redeclared, format!("Variable {} defined twice", varName.clone()));
                         return false;
                     } else {
                         let item = HashItem::newVar(varName.clone(), varType.clone());
@@ -1612,13 +1838,17 @@ impl<'a> SyntaxChecker<'a> {
                 }
                 
                 
+            }
+            //Parameters are declared in the procedure's scope like any other local variable
+            Stmt::ParamDecl(varName, varType, paramMode, lineNum) => {
+                return self.checkStmt(Stmt::VarDecl(varName, varType, lineNum));
             }
             //For checking and declaring global variables
             Stmt::GlobVarDecl(varName, varType, lineNum) => {
 
                 let defined = self.globalTable.checkItem(&varName.clone());
                 if(defined){
-                    println!("Error: variable: {} defined twice", varName.clone());
+                    self.reportError(CheckCode::Redeclared, format!("Variable {} defined twice", varName.clone()));
                     return false;
                 } else {
                     let item = HashItem::newVar(varName.clone(), varType.clone());
@@ -1627,76 +1857,105 @@ impl<'a> SyntaxChecker<'a> {
                 }
                     
             }
+            //For a forward declaration, the signature is registered so procedures before the definition can call it
+            Stmt::ProcForward(retType, procName, params, lineNum) => {
+                let signature = Self::paramSignature(&params);
+                let forwardAst = Box::new(Stmt::ProcForward(retType.clone(), procName.clone(), params.clone(), lineNum.clone()));
+                let procItem = HashItem::newProc(procName.clone(), retType.clone(), HashItemType::newProcItem(forwardAst, signature, SymbolTable::newEmpty()));
+                if self.scope != 0 {
+                    self.localTable.symTab.insert(procName.clone(), procItem);
+                } else {
+                    self.globalTable.symTab.insert(procName.clone(), procItem);
+                }
+                return true;
+            }
             //For checking a procedure
             Stmt::ProcDecl(retType, procName, params, header, body, lineNum) => {
                 // println!("procedure declaration");
                 let procAst = Stmt::Program(procName.clone(), header.clone(), body.clone(), lineNum.clone());
                 
-                let mut paramStrings: Vec<String> = Vec::new();
+                let mut signature: Vec<ParamSig> = Vec::new();
 
                 let curScope = self.scope.clone();
 
                 let mut procChecker: SyntaxChecker = self.newScope(procAst, curScope, procName.clone());
-                //Iterates through the parameters, registering them in the Symboltable and copying the names to the list of params
+                procChecker.retType = Some(retType.clone());
+                let mut paramsGood = true;
+                //Iterates through the parameters, registering them in the Symboltable and building the signature calls are checked against
                 if let Stmt::Block(ref instrs, lineNum) = *params.clone() {
                     for instr in instrs {
                         let good = procChecker.checkStmt(instr.clone());
                         if (!good){
-                            println!("Error in Procedure parameter definition on line {}:", lineNum.clone());
-                            return false;
-                        } else {
-                            match instr.clone(){
-                                Stmt::VarDecl(varName, VarType, lineNum) => {
-                                    paramStrings.push(varName.clone());
-                                }
-                                _ => {
-                                    println!("Error with procedure {} declaration on line {}:\n Procedure parameters must be variable declarations in the following format:\n    variable <identifier> : <type_mark>", procName.clone(), lineNum.clone());
-                                    return false;
-                                }
+                            paramsGood = false;
+                        }
+                        match instr.clone(){
+                            Stmt::ParamDecl(varName, varType, paramMode, lineNum) => {
+                                signature.push(ParamSig::new(varName.clone(), varType.clone(), paramMode));
+                            }
+                            _ => {
+                                procChecker.reportError(CheckCode::BadParams, format!("Error with procedure {} declaration on line {}:\n Procedure parameters must be variable declarations in the following format:\n    variable <identifier> : [in | out | in out] <type_mark>", procName.clone(), lineNum.clone()));
+                                paramsGood = false;
                             }
                         }
                     }
                 } else {
-                    println!("Error in Procedure parameter definition on line {}:", lineNum.clone());
-                    // instr.display(0);
-                    return false;
+                    procChecker.reportError(CheckCode::BadParams, format!("Error in Procedure parameter definition on line {}:", lineNum.clone()));
+                    paramsGood = false;
                 }
+                procChecker.params = signature.clone();
 
-
-                //Checks the procedure to make sure its all good
+                //Checks the procedure to make sure its all good, even if the parameters were not so all of its errors are found
                 let procGood = procChecker.checkProgram();
+                let procTable = procChecker.localTable.clone();
 
+                //Keeps the errors found inside the procedure
+                let procReports = procChecker.reports.clone();
+                self.reports.absorb(procReports);
 
-                //If the procedure is good, appends to the symboltable and moved on
-                if(!procGood){
-                    println!("Error in procedure {} defined on line {}", procName.clone(), lineNum.clone());
-                    return false;
-                } else {
-                    if curScope != 0 {
-                        //Sets up the things and inserts the procedure into the symboltable
-                        let mut procItemType = HashItemType::newProcItem(body.clone(), paramStrings.clone(), procChecker.localTable.clone());
-                        let mut procItem: HashItem = HashItem::newProc(procName.clone(), retType.clone(), procItemType);
-                        self.localTable.symTab.insert(procName.clone(), procItem.clone());
-                        
-                        return true;
-                    } else {
-                        //Sets up the things and inserts the procedure into the symboltable
-                        let mut procItemType = HashItemType::newProcItem(body.clone(), paramStrings.clone(), procChecker.localTable.clone());
-                        let mut procItem: HashItem = HashItem::newProc(procName.clone(), retType.clone(), procItemType);
-                        self.globalTable.symTab.insert(procName.clone(), procItem.clone());
-                        return true;
+                //A procedure that was declared forward has to match that declaration, calls before it were checked against it
+                let mut forwardGood = true;
+                let declared = if curScope != 0 { self.localTable.get(&procName).cloned() } else { self.globalTable.get(&procName).cloned() };
+                if let Some(mut declaredItem) = declared {
+                    if let HashItemType::Procedure(declaredAst, declaredSig, declaredSt) = declaredItem.hashType.clone() {
+                        if let Stmt::ProcForward(_, _, _, forwardLine) = *declaredAst {
+                            if (declaredItem.getType() != retType) || (declaredSig != signature) {
+                                self.reportError(CheckCode::ForwardDecl, format!("Procedure {} does not match its forward declaration on line {}", procName.clone(), forwardLine));
+                                forwardGood = false;
+                            }
+                        }
                     }
                 }
+
+                //Adds the procedure to the symboltable even if it has errors, so calls to it are still checked
+                let mut procItemType = HashItemType::newProcItem(body.clone(), signature.clone(), procTable.clone());
+                let mut procItem: HashItem = HashItem::newProc(procName.clone(), retType.clone(), procItemType);
+                if curScope != 0 {
+                    self.localTable.symTab.insert(procName.clone(), procItem.clone());
+                } else {
+                    self.globalTable.symTab.insert(procName.clone(), procItem.clone());
+                }
+                return paramsGood && procGood && forwardGood;
             }
             //For checking a variable assignment
             Stmt::Assign(valueToAssign, newValue, lineNum) => {
+                //Warns if the value has to be converted in a way that can change it
+                let targTypeCheck = match valueToAssign.clone() {
+                    Expr::VarRef(targName) => self.checkVar(targName),
+                    Expr::ArrayRef(targName, indexExpr) => self.arrayElementType(&targName),
+                    _ => None,
+                };
+                if let Some(targType) = targTypeCheck {
+                    let newType = self.exprType(&newValue);
+                    self.warnIfLossy(newType, &targType, &newValue, format!("assignment to {}", valueToAssign));
+                }
+
                 if let Expr::VarRef(ref targName) = valueToAssign {
                     //Check if variable assignment is in the local table
                     let mut targValue: HashItem; 
                     //Looks for the value in the local then global table, retrieves it if so
                     if !(self.localTable.checkItem(targName)){
                         if !(self.globalTable.checkItem(targName)){
-                            println!("Attempting to assign value to undeclared variable: {} on line: {}", targName.clone(), lineNum.clone());
+                            self.reportError(CheckCode::Undeclared, format!("Attempting to assign value to undeclared variable: {} on line: {}", targName.clone(), lineNum.clone()));
                             return false;
                         } else {
                             let gotValue = self.globalTable.get(targName);
@@ -1705,7 +1964,7 @@ impl<'a> SyntaxChecker<'a> {
                                     targValue = val.clone();
                                 }
                                 None => {
-                                    println!("Error with value {} on line: {}", targName.clone(), lineNum.clone());
+                                    self.reportError(CheckCode::Internal, format!("Error with value {} on line: {}", targName.clone(), lineNum.clone()));
                                     return false;
                                 }
                             }
@@ -1717,7 +1976,7 @@ impl<'a> SyntaxChecker<'a> {
                                 targValue = val.clone();
                             }
                             None => {
-                                println!("Error with value {} on line: {}", targName.clone(), lineNum.clone());
+                                self.reportError(CheckCode::Internal, format!("Error with value {} on line: {}", targName.clone(), lineNum.clone()));
                                 return false;
                             }
                         }
@@ -1725,627 +1984,60 @@ impl<'a> SyntaxChecker<'a> {
                     
                     //Checks if value being assigned to is a variable
                     if targValue.hashType != HashItemType::Variable {
-                        println!("On line: {}, cannot assign value to procedure", lineNum.clone());
+                        self.reportError(CheckCode::WrongKind, format!("On line: {}, cannot assign value to procedure", lineNum.clone()));
                         return false;
                     }
                     
                     //Checks to ensure that new value matches target value
                     let targType = targValue.getType();
-                    match targType{
-                        VarType::Int => {
+                    match targType.clone(){
+                        //Variables can never be declared void, this is here to cover every type
+                        VarType::Void => {
+                            self.reportError(CheckCode::WrongKind, format!("On line: {}, cannot assign a value to {} of type Void", lineNum.clone(), targName.clone()));
+                            return false;
+                        }
+                        //Ints, floats, bools and strings take any value the coercion table can convert to them
+                        VarType::Int | VarType::Float | VarType::Bool | VarType::Str => {
+                            return self.checkAssignValue(targType.clone(), targName, newValue.clone(), lineNum);
+                        }
+                        VarType::Array(targElem, targSize) => {
                             match newValue.clone(){
-                                //Literals
-                                Expr::IntLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::FloatLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::ArrayRef(name, index) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if checked {
+                                Expr::IntArrayLiteral(newSize, array) => {
+                                    if(*targElem == VarType::Int && targSize == newSize) {
                                         return true;
                                     } else {
-                                        println!("Error with array reference on line {}", lineNum.clone());
+                                        self.reportError(CheckCode::ArraySize, format!("When copying integers, sizes must be equivalent"));
                                         return false;
                                     }
                                 }
-                                Expr::BoolLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::StringLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot assign string to variable of type int", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::IntArrayLiteral(size, array) => {
-                                    println!("Cannot assign array to variable of type {}", targType.clone());
-                                    return false;
+                                //Anything else has to give a whole array of the same type and size
+                                _ => {
+                                    return self.checkAssignValue(targType.clone(), targName, newValue.clone(), lineNum);
                                 }
+                            }
+                        }
+                    }
 
-                                //Operations
-                                Expr::ArthOp(op1, op, op2) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in arithmetic operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                Expr::LogOp(op1, op, op2) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in logical operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
+                } 
+                
+                //For index value references
+                else if let Expr::ArrayRef(ref targName, targIndexExpr) = valueToAssign {
+                    //Check if variable assignment is in the local table
+                    let mut targValue: HashItem; 
+                    //Looks for the value in the local then global table, retrieves it if so
+                    if !(self.localTable.checkItem(targName)){
+                        if !(self.globalTable.checkItem(targName)){
+                            self.reportError(CheckCode::Undeclared, format!("Attempting to assign value to undeclared variable: {} on line: {}", targName.clone(), lineNum.clone()));
+                            return false;
+                        } else {
+                            let gotValue = self.globalTable.get(targName);
+                            match gotValue{
+                                Some(val) => {
+                                    targValue = val.clone();
                                 }
-                                Expr::RelOp(op1, op, op2) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in relational operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                
-                                //Calls/references
-                                Expr::ProcRef(procName, params) => {
-                                    if (self.checked.clone() == false) & (self.name.clone() == procName.clone()){
-                                        return true;
-                                    } else {    
-                                        let mut procType: VarType;
-                                        //Checks if procedure is defined
-                                        let checkLocProc = self.localTable.getType(&procName.clone());
-                                        match checkLocProc{
-                                            Some(proc) => {
-                                                procType = proc;
-                                            }
-                                            None => {
-                                                let checkGlobProc = self.globalTable.getType(&procName.clone());
-                                                match checkGlobProc{
-                                                    Some(proc) => {
-                                                        procType = proc
-                                                    }
-                                                    None => {
-                                                        println!("Error on line {}:\n Procedure {} is not defined", lineNum.clone(), procName.clone());
-                                                        return false;
-                                                        
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    
-                                        //Checks procedure type compatability with int
-                                        match procType{
-                                            VarType::Bool =>{
-                                                return true;
-                                            }
-                                            VarType::Int =>{
-                                                return true;
-                                            }
-                                            VarType::Float =>{
-                                                return true;
-                                            }
-                                            _ => {
-                                                println!("Error on line {}:\n Cannot assign {} to variable {} of type {}", lineNum.clone(), procType.clone(), targName.clone(), targType.clone());
-                                                return false;
-                                            }
-                                        }}
-                                }   
-                                Expr::VarRef(assignName) => {
-                                    let mut assignType: VarType;
-                                    //Checks if variable is defined
-                                    let checkLocVar = self.localTable.getType(&assignName.clone());
-                                    match checkLocVar{
-                                        Some(var) => {
-                                            assignType = var;
-                                        }
-                                        None => {
-                                            let checkGlobVar = self.globalTable.getType(&assignName.clone());
-                                            match checkGlobVar{
-                                                Some(var) => {
-                                                    assignType = var
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Variable {} is not defined", lineNum.clone(), assignName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks variable type compatability with int
-                                    match assignType{
-                                        VarType::Bool =>{
-                                            return true;
-                                        }
-                                        VarType::Int =>{
-                                            return true;
-                                        }
-                                        VarType::Float =>{
-                                            return true;
-                                        }
-                                        VarType::IntArray(size) => {
-                                            return true;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot assign {} to variable {} of type {}", lineNum.clone(), assignType.clone(), targName.clone(), targType.clone());
-                                            return false;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        VarType::Bool => {
-                            match newValue.clone(){
-                                //Literals
-                                Expr::IntLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::FloatLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot assign float to variable of type bool", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::ArrayRef(name, index) => {
-                                    println!("Assinging: Int Array ref");
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if checked {
-                                        return true;
-                                    } else {
-                                        println!("Error with array reference on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }Expr::BoolLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::StringLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot assign string to variable of type bool", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::IntArrayLiteral(size, array) => {
-                                    println!("Cannot assign array to variable of type {}", targType.clone());
-                                    return false;
-                                }
-                                
-                                //Operations
-                                Expr::ArthOp(op1, op, op2) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in arithmetic operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                Expr::LogOp(op1, op, op2) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in logical operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                Expr::RelOp(op1, op, op2) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in relational operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                
-                                //Calls/references
-                                Expr::ProcRef(procName, params) => {
-                                    let mut procType: VarType;
-                                    //Checks if procedure is defined
-                                    let checkLocProc = self.localTable.getType(&procName.clone());
-                                    match checkLocProc{
-                                        Some(proc) => {
-                                            procType = proc;
-                                        }
-                                        None => {
-                                            println!("Procedure does not exist locally, checking global");
-                                            let checkGlobProc = self.localTable.getType(&procName.clone());
-                                            match checkGlobProc{
-                                                Some(proc) => {
-                                                    procType = proc
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Procedure {} is not defined", lineNum.clone(), procName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks procedure type compatability with int
-                                    match procType{
-                                        VarType::Bool =>{
-                                            return true;
-                                        }
-                                        VarType::Int =>{
-                                            return true;
-                                        }
-                                        VarType::Float =>{
-                                            return true;
-                                        }
-                                        _ => {
-                                            return false;
-                                        }
-                                    }
-                                }   
-                                Expr::VarRef(assignName) => {
-                                    let mut assignType: VarType;
-                                    //Checks if variable is defined
-                                    let checkLocVar = self.localTable.getType(&assignName.clone());
-                                    match checkLocVar{
-                                        Some(var) => {
-                                            assignType = var;
-                                        }
-                                        None => {
-                                            let checkGlobVar = self.localTable.getType(&assignName.clone());
-                                            match checkGlobVar{
-                                                Some(var) => {
-                                                    assignType = var
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Variable {} is not defined", lineNum.clone(), assignName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks variable type compatability with int
-                                    match assignType{
-                                        VarType::Bool =>{
-                                            return true;
-                                        }
-                                        VarType::Int =>{
-                                            return true;
-                                        }
-                                        VarType::Float =>{
-                                            return true;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot assign {} to variable {} of type {}", lineNum.clone(), assignType.clone(), targName.clone(), targType.clone());
-                                            return false;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        VarType::Float => {
-                            match newValue.clone(){
-                                //Literals
-                                Expr::IntLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::FloatLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::ArrayRef(name, index) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if checked {
-                                        return true;
-                                    } else {
-                                        println!("Error with array reference on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                Expr::BoolLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot assign bool to variable of type float", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::StringLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot assign string to variable of type float", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::IntArrayLiteral(size, array) => {
-                                    println!("Cannot assign array to variable of type {}", targType.clone());
-                                    return false;
-                                }
-
-                                //Operations
-                                Expr::ArthOp(op1, op, op2) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in arithmetic operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }          
-                                Expr::LogOp(op1, op, op2) => {
-                                    println!("Error on line {}:\n Cannot assign output of logical operation to variable of type float", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::RelOp(op1, op, op2) => {
-                                    println!("Error on line {}:\n Cannot assign output of relational operation to variable of type float", lineNum.clone());
-                                    return false;
-                                }
-                                
-                                //Calls/references
-                                Expr::ProcRef(procName, params) => {
-                                    println!("Assigning: procedure {}", procName.clone());
-                                    let mut procType: VarType;
-                                    //Checks if procedure is defined
-                                    let checkLocProc = self.localTable.getType(&procName.clone());
-                                    match checkLocProc{
-                                        Some(proc) => {
-                                            procType = proc;
-                                        }
-                                        None => {
-                                            println!("Procedure does not exist locally, checking global");
-                                            let checkGlobProc = self.localTable.getType(&procName.clone());
-                                            match checkGlobProc{
-                                                Some(proc) => {
-                                                    println!("procedure exists globally");
-                                                    procType = proc
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Procedure {} is not defined", lineNum.clone(), procName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks procedure type compatability with int
-                                    match procType{
-                                        VarType::Bool =>{
-                                            println!("Error on line {}:\n Cannot assign output of procedure of type bool to variable of type float", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Int =>{
-                                            return true;
-                                        }
-                                        VarType::Float =>{
-                                            return true;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot assign {} to variable {} of type {}", lineNum.clone(), procType.clone(), targName.clone(), targType.clone());
-                                            return false;
-                                        }
-                                    }
-                                }   
-                                Expr::VarRef(assignName) => {
-                                    println!("Assigning: variable {}", assignName.clone());
-                                    let mut assignType: VarType;
-                                    //Checks if variable is defined
-                                    let checkLocVar = self.localTable.getType(&assignName.clone());
-                                    match checkLocVar{
-                                        Some(var) => {
-                                            println!("variable exists locally");
-                                            assignType = var;
-                                        }
-                                        None => {
-                                            println!("Variable does not exist locally, checking global");
-                                            let checkGlobVar = self.localTable.getType(&assignName.clone());
-                                            match checkGlobVar{
-                                                Some(var) => {
-                                                    println!("Variable exists globally");
-                                                    assignType = var
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Variable {} is not defined", lineNum.clone(), assignName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks variable type compatability with int
-                                    match assignType{
-                                        VarType::Bool =>{
-                                            println!("Error on line {}:\n Cannot assign value of variable of type bool to variable of type float", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Int =>{
-                                            return true;
-                                        }
-                                        VarType::Float =>{
-                                            return true;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot assign {} to variable {} of type {}", lineNum.clone(), assignType.clone(), targName.clone(), targType.clone());
-                                            return false;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        VarType::Str => {
-                            match newValue.clone(){
-                                //Literals
-                                Expr::IntLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot assign int to variable of type string", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::FloatLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot assign float to variable of type string", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::ArrayRef(name, index) => {
-                                    println!("Error on line {}:\n Cannot assign int array value to variable of type string", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::BoolLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot assign bool to variable of type string", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::StringLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::IntArrayLiteral(size, array) => {
-                                    println!("Cannot assign array to variable of type {}", targType.clone());
-                                    return false;
-                                }
-
-                                //Operations
-                                Expr::ArthOp(op1, op, op2) => {
-                                    println!("Error on line {}:\n Cannot assign output of arithmetic operation to variable of type string", lineNum.clone());
-                                    return false;
-                                }          
-                                Expr::LogOp(op1, op, op2) => {
-                                    println!("Error on line {}:\n Cannot assign output of logical operation to variable of type string", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::RelOp(op1, op, op2) => {
-                                    println!("Error on line {}:\n Cannot assign output of relational operation to variable of type string", lineNum.clone());
-                                    return false;
-                                }
-                                
-                                //Calls/references
-                                Expr::ProcRef(procName, params) => {
-                                    println!("Assigning: procedure {}", procName.clone());
-                                    let mut procType: VarType;
-                                    //Checks if procedure is defined
-                                    let checkLocProc = self.localTable.getType(&procName.clone());
-                                    match checkLocProc{
-                                        Some(proc) => {
-                                            procType = proc;
-                                        }
-                                        None => {
-                                            println!("Procedure does not exist locally, checking global");
-                                            let checkGlobProc = self.localTable.getType(&procName.clone());
-                                            match checkGlobProc{
-                                                Some(proc) => {
-                                                    println!("procedure exists globally");
-                                                    procType = proc
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Procedure {} is not defined", lineNum.clone(), procName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks procedure type compatability with int
-                                    match procType{
-                                        VarType::Bool =>{
-                                            println!("Error on line {}:\n Cannot assign output of procedure of type bool to variable of type float", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Int =>{
-                                            println!("Error on line {}:\n Cannot assign output of procedure of type integer to variable of type float", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Float =>{
-                                            println!("Error on line {}:\n Cannot assign output of procedure of float bool to variable of type float", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Str => {
-                                            return true;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot assign {} to variable {} of type {}", lineNum.clone(), procType.clone(), targName.clone(), targType.clone());
-                                            return false;
-                                        }
-                                    }
-                                }   
-                                Expr::VarRef(assignName) => {
-                                    println!("Assigning: variable {}", assignName.clone());
-                                    let mut assignType: VarType;
-                                    //Checks if variable is defined
-                                    let checkLocVar = self.localTable.getType(&assignName.clone());
-                                    match checkLocVar{
-                                        Some(var) => {
-                                            println!("variable exists locally");
-                                            assignType = var;
-                                        }
-                                        None => {
-                                            println!("Variable does not exist locally, checking global");
-                                            let checkGlobVar = self.localTable.getType(&assignName.clone());
-                                            match checkGlobVar{
-                                                Some(var) => {
-                                                    println!("Variable exists globally");
-                                                    assignType = var
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Variable {} is not defined", lineNum.clone(), assignName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks variable type compatability with int
-                                    match assignType{
-                                        VarType::Bool =>{
-                                            println!("Error on line {}:\n Cannot assign value of variable of type bool to variable of type float", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Int =>{
-                                            println!("Error on line {}:\n Cannot assign value of variable of type integer to variable of type float", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Float =>{
-                                            println!("Error on line {}:\n Cannot assign value of variable of type float to variable of type float", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Str => {
-                                            return true;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot assign {} to variable {} of type {}", lineNum.clone(), assignType.clone(), targName.clone(), targType.clone());
-                                            return false;
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        VarType::IntArray(targSize) => {
-                            match newValue.clone(){
-                                Expr::IntArrayLiteral(newSize, array) => {
-                                    if(targSize == newSize) {
-                                        return true;
-                                    } else {
-                                        println!("Error on line {}:\n When copying integers, sizes must be equivalent", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                _ => {
-                                    println!("Error on line {}:\n Cannot assign {} to integer array", lineNum.clone(), targType.clone());
-                                    return true;
-                                }
-                            }
-                        }
-                    }
-
-                } 
-                
-                //For index value references
-                else if let Expr::ArrayRef(ref targName, targIndexExpr) = valueToAssign {
-                    //Check if variable assignment is in the local table
-                    let mut targValue: HashItem; 
-                    //Looks for the value in the local then global table, retrieves it if so
-                    if !(self.localTable.checkItem(targName)){
-                        if !(self.globalTable.checkItem(targName)){
-                            println!("Attempting to assign value to undeclared variable: {} on line: {}", targName.clone(), lineNum.clone());
-                            return false;
-                        } else {
-                            let gotValue = self.globalTable.get(targName);
-                            match gotValue{
-                                Some(val) => {
-                                    targValue = val.clone();
-                                }
-                                None => {
-                                    println!("Error with value {} on line: {}", targName.clone(), lineNum.clone());
-                                    return false;
+                                None => {
+                                    self.reportError(CheckCode::Internal, format!("Error with value {} on line: {}", targName.clone(), lineNum.clone()));
+                                    return false;
                                 }
                             }
                         }
@@ -2356,7 +2048,7 @@ impl<'a> SyntaxChecker<'a> {
                                 targValue = val.clone();
                             }
                             None => {
-                                println!("Error with value {} on line: {}", targName.clone(), lineNum.clone());
+                                self.reportError(CheckCode::Internal, format!("Error with value {} on line: {}", targName.clone(), lineNum.clone()));
                                 return false;
                             }
                         }
@@ -2364,744 +2056,244 @@ impl<'a> SyntaxChecker<'a> {
                     
                     //Checks if value being assigned to is a variable
                     if targValue.hashType != HashItemType::Variable {
-                        println!("On line: {}, cannot assign value to procedure", lineNum.clone());
+                        self.reportError(CheckCode::WrongKind, format!("On line: {}, cannot assign value to procedure", lineNum.clone()));
                         return false;
                     }
                     
-                    //Checks to ensure that new value matches target value
-                    let targType = targValue.getType();
-                    match targType{
-                        //The only correct one
-                        VarType::IntArray(targSize) => {
-                            
-                            //Checks if the expression making up the index is valid
-                            let checked = self.checkExpr(*targIndexExpr.clone());
-                            if (checked){
-                            } else {
-                                println!("Error with index expression on line {}", lineNum.clone());
-                                return false;
-                            }
-                            
-                            //Reacts based on the type of expression the index expression is
-                            match *targIndexExpr{
-                                //Literals
-                                Expr::IntLiteral(val) => {
-                                    if (val > targSize.into()){
-                                        println!("Error on line {}:\n Index {} is out of bounds", lineNum.clone(), val.clone())
-                                    } else {
-                                    }
-                                    
-                                }
-                                Expr::FloatLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot use float as index value", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::ArrayRef(name, index) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if checked {
-                                        return true;
-                                    } else {
-                                        println!("Error with array reference on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                Expr::BoolLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot use bool as index value", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::StringLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot use string as index value", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::IntArrayLiteral(size, array) => {
-                                    println!("Error on line {}:\n Cannot use array as index value", lineNum.clone());
-                                    return false;
-                                }
-
-                                //Operations
-                                Expr::ArthOp(op1, op, op2) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true
-                                    } else {
-                                        println!("Error in arithmetic operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                Expr::LogOp(op1, op, op2) => {
-                                    println!("Error on line {}:\n Cannot use logical operation as index value", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::RelOp(op1, op, op2) => {
-                                    println!("Error on line {}:\n Cannot use relational operation as index value", lineNum.clone());
-                                    return false;
-                                }
-                                
-                                //Calls/references
-                                Expr::ProcRef(procName, params) => {
-                                    println!("Indexing with procedure {}", procName.clone());
-                                    let mut procType: VarType;
-                                    //Checks if procedure is defined
-                                    let checkLocProc = self.localTable.getType(&procName.clone());
-                                    match checkLocProc{
-                                        Some(proc) => {
-                                            procType = proc;
-                                        }
-                                        None => {
-                                            let checkGlobProc = self.localTable.getType(&procName.clone());
-                                            match checkGlobProc{
-                                                Some(proc) => {
-                                                    procType = proc
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Procedure {} is not defined", lineNum.clone(), procName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks procedure type compatability with int
-                                    match procType{
-                                        VarType::Bool =>{
-                                            println!("Error on line {}:\n Cannot use procedure of type bool as index value", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Int =>{
-                                            println!("Procedure type int");
-                                            
-                                        }
-                                        VarType::Float =>{
-                                            println!("Error on line {}:\n Cannot use procedure of type float as index value", lineNum.clone());
-                                            return false;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot use procedure {} to index integer array", lineNum.clone(), procName.clone());
-                                            return false;
-                                        }
-                                    }
-                                }   
-                                Expr::VarRef(indexVarName) => {
-                                    println!("indexing with variable {}", indexVarName.clone());
-                                    let mut assignType: VarType;
-                                    //Checks if variable is defined
-                                    let checkLocVar = self.localTable.getType(&indexVarName.clone());
-                                    match checkLocVar{
-                                        Some(var) => {
-                                            println!("variable exists locally");
-                                            assignType = var;
-                                        }
-                                        None => {
-                                            println!("Variable does not exist locally, checking global");
-                                            let checkGlobVar = self.localTable.getType(&indexVarName.clone());
-                                            match checkGlobVar{
-                                                Some(var) => {
-                                                    println!("Variable exists globally");
-                                                    assignType = var
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Variable {} is not defined", lineNum.clone(), indexVarName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks variable type compatability with int
-                                    match assignType{
-                                        VarType::Bool =>{
-                                            println!("Error on line {}:\n Cannot use variable of type bool as index value", lineNum.clone());
-                                            return false;
-                                        }
-                                        VarType::Int =>{
-                                            println!("variable type int");
-                                            
-                                        }
-                                        VarType::Float =>{
-                                            println!("Error on line {}:\n Cannot use variable of type float as index value", lineNum.clone());
-                                            return false;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot use variable {} to index integer array", lineNum.clone(), indexVarName.clone());
-                                            return false;
-                                        }
-                                    }
-                                }
-                            }
-                            
-
-                            //Now that we know the index is good, need to check the target assignment
-                            match newValue.clone(){
-                                //Literals
-                                Expr::IntLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::FloatLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::ArrayRef(name, index) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if checked {
-                                        return true;
-                                    } else {
-                                        println!("Error with array reference on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                Expr::BoolLiteral(val) => {
-                                    return true;
-                                }
-                                Expr::StringLiteral(val) => {
-                                    println!("Error on line {}:\n Cannot assign string to variable of type int", lineNum.clone());
-                                    return false;
-                                }
-                                Expr::IntArrayLiteral(size, array) => {
-                                    println!("Cannot assign array to variable of type {}", targType.clone());
-                                    return false;
-                                }
-
-                                //Operations
-                                Expr::ArthOp(op1, op, op2) => {
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in arithmetic operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                Expr::LogOp(op1, op, op2) => {
-                                    println!("Assigning LogOp");
-                                    println!("Checking expression");
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in logical operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                Expr::RelOp(op1, op, op2) => {
-                                    println!("Assigning RelOp");
-                                    println!("Checking expression");
-                                    let checked = self.checkExpr(newValue.clone());
-                                    if(checked){
-                                        return true;
-                                    } else {
-                                        println!("Error in relational operation on line {}", lineNum.clone());
-                                        return false;
-                                    }
-                                }
-                                
-                                //Calls/references
-                                Expr::ProcRef(procName, params) => {
-                                    println!("Assigning: procedure {}", procName.clone());
-                                    let mut procType: VarType;
-                                    //Checks if procedure is defined
-                                    let checkLocProc = self.localTable.getType(&procName.clone());
-                                    match checkLocProc{
-                                        Some(proc) => {
-                                            procType = proc;
-                                        }
-                                        None => {
-                                            let checkGlobProc = self.localTable.getType(&procName.clone());
-                                            match checkGlobProc{
-                                                Some(proc) => {
-                                                    procType = proc
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Procedure {} is not defined", lineNum.clone(), procName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks procedure type compatability with int
-                                    match procType{
-                                        VarType::Bool =>{
-                                            return true;
-                                        }
-                                        VarType::Int =>{
-                                            return true;
-                                        }
-                                        VarType::Float =>{
-                                            return true;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot assign {} to variable {} of type {}", lineNum.clone(), procType.clone(), targName.clone(), targType.clone());
-                                            return false;
-                                        }
-                                    }
-                                }   
-                                Expr::VarRef(assignName) => {
-                                    println!("Assigning: variable {}", assignName.clone());
-                                    let mut assignType: VarType;
-                                    //Checks if variable is defined
-                                    let checkLocVar = self.localTable.getType(&assignName.clone());
-                                    match checkLocVar{
-                                        Some(var) => {
-                                            println!("variable exists locally");
-                                            assignType = var;
-                                        }
-                                        None => {
-                                            println!("Variable does not exist locally, checking global");
-                                            let checkGlobVar = self.localTable.getType(&assignName.clone());
-                                            match checkGlobVar{
-                                                Some(var) => {
-                                                    println!("Variable exists globally");
-                                                    assignType = var
-                                                }
-                                                None => {
-                                                    println!("Error on line {}:\n Variable {} is not defined", lineNum.clone(), assignName.clone());
-                                                    return false;
-                                                    
-                                                }
-                                            }
-                                        }
-                                    }
-                                
-                                    //Checks variable type compatability with int
-                                    match assignType{
-                                        VarType::Bool =>{
-                                            return true;
-                                        }
-                                        VarType::Int =>{
-                                            return true;
-                                        }
-                                        VarType::Float =>{
-                                            return true;
-                                        }
-                                        _ => {
-                                            println!("Error on line {}:\n Cannot assign {} to variable {} of type {}", lineNum.clone(), assignType.clone(), targName.clone(), targType.clone());
-                                            return false;
-                                        }
-                                    }
-                                }
-                            }                        
-                        }
-                        _ => {
-                            println!("Error on line {}:\n Variable {} is not an array", lineNum.clone(), targName.clone());
-                            return false;
-                        }
-                    }
-
-                } else {
-                    println!("On line {}: cannot assign to non-variable", lineNum.clone());
-                    return false;
-                }
-
-
-            }
-            //For Stmts that are just Exprs
-            Stmt::Expr(expr, lineNum) => {
-                match (expr){
-                    _ => {
-                        let checked = self.checkExpr(expr.clone());
-                        if checked {
-                            return true;
-                        } else {
-                            println!("Error with expression statement on line {}", lineNum.clone());
-                            return false;
-                        }
-                    }
-                }
-            }
-            //For checking if statements
-            Stmt::If(condition, body, elseBody, lineNum) => {
-                //Checks the condition
-                match condition.clone() {
-                    Expr::IntArrayLiteral(size, array) => {
-                        println!("Error with if condition on line {}:\n Cannot use array as condition", lineNum.clone());
-                        return false;
-                    }
-                    Expr::FloatLiteral(val) => {
-                        println!("Error with if condition on line {}:\n Cannot use float as condition", lineNum.clone());
-                        return false;
-                    }
-                    Expr::StringLiteral(val) => {
-                        println!("Error with if condition on line {}:\n Cannot use string as condition", lineNum.clone());
-                        return false;
-                    }
-                    
-                    
-                    Expr::ProcRef(procName, params) => {
-                        let mut procType: VarType;
-                        //Checks if procedure is defined
-                        let checkLocProc = self.localTable.getType(&procName.clone());
-                        match checkLocProc{
-                            Some(proc) => {
-                                procType = proc;
-                            }
-                            None => {
-                                let checkGlobProc = self.localTable.getType(&procName.clone());
-                                match checkGlobProc{
-                                    Some(proc) => {
-                                        procType = proc
-                                    }
-                                    None => {
-                                        println!("Error on line {}:\n Procedure {} is not defined", lineNum.clone(), procName.clone());
-                                        return false;
-                                        
-                                    }
-                                }
-                            }
-                        }
-                    
-                        //Checks procedure type compatability with int
-                        match procType{
-                            VarType::Bool =>{
-                                println!("Procedure type bool");
-                            }
-                            VarType::Int =>{
-                                println!("Procedure type int");
-                            }
-                            VarType::Float =>{
-                                println!("Error with if condition on line {}:\n Cannot use float procedure as condition", lineNum.clone());
-                        return false;
-                            }
-                            _ => {
-                                println!("Error on line {}:\n Cannot use procedure of type {} as if condition", lineNum.clone(), procType.clone());
-                                return false;
-                            }
-                        }
-
-                        //Checks if the condition is good
-                        let goodCond = self.checkExpr(condition.clone());
-                        //If the condition is bad, fails here
-                        if (!goodCond){
-                            println!("Error in if condition on line {}", lineNum.clone());
-                            return false;
-                        //If the condition is good, checks the rest of the if statement
-                        } else {
-                            //Checks the if body
-                            let goodIfBody = self.checkStmt(*body);
-                            //If the body if good
-                            if(goodIfBody){
-                                //Checks if there is an else
-                                match elseBody{
-                                    //Checks the else
-                                    Some(elseStmt) => {
-                                        let goodElse = self.checkStmt(*elseStmt.clone());
-                                        if(!goodElse){
-                                            println!("Error with else in if statement on line {}", lineNum.clone());
-                                            return false;
-                                        } else {
-                                            return true;
-                                        }
-                                    }
-                                    //If statement is good here if no else
-                                    None => {
-                                        return true;
-                                    }
-
-                                }
-                            } else {
-                                println!("Error with body of if statement on line: {}", lineNum.clone());
-                                return false;
-                            }
-                        }
-
-
-                    }   
-                    
-                    Expr::VarRef(varCondName) => {
-                        println!("Assigning: variable {}", varCondName.clone());
-                        let mut ifCondType: VarType;
-                        //Checks if variable is defined
-                        let checkLocVar = self.localTable.getType(&varCondName.clone());
-                        match checkLocVar{
-                            Some(var) => {
-                                println!("variable exists locally");
-                                ifCondType = var;
-                            }
-                            None => {
-                                println!("Variable does not exist locally, checking global");
-                                let checkGlobVar = self.localTable.getType(&varCondName.clone());
-                                match checkGlobVar{
-                                    Some(var) => {
-                                        println!("Variable exists globally");
-                                        ifCondType = var
-                                    }
-                                    None => {
-                                        println!("Error on line {}:\n Variable {} is not defined", lineNum.clone(), varCondName.clone());
-                                        return false;
-                                        
-                                    }
-                                }
-                            }
-                        }
-                    
-                        //Checks variable type compatability with int
-                        match ifCondType{
-                            VarType::Bool =>{
-                                println!("Variable type bool");
-                            }
-                            VarType::Int =>{
-                                println!("Variable type int");
-                            }
-                            VarType::Float =>{
-                                println!("Error on line {}:\n Cannot use variable of type float as if condition", lineNum.clone());
-                                return false;
-                            }
-                            _ => {
-                                println!("Error on line {}:\n Cannot use variable of type {} for if condition", lineNum.clone(), ifCondType.clone());
-                                return false;
-                            }
-                        }
-
-                        //Checks if the condition is good
-                        let goodCond = self.checkExpr(condition.clone());
-                        //If the condition is bad, fails here
-                        if (!goodCond){
-                            println!("Error in if condition on line {}", lineNum.clone());
-                            return false;
-                        //If the condition is good, checks the rest of the if statement
-                        } else {
-                            //Checks the if body
-                            let goodIfBody = self.checkStmt(*body);
-                            //If the body if good
-                            if(goodIfBody){
-                                //Checks if there is an else
-                                match elseBody{
-                                    //Checks the else
-                                    Some(elseStmt) => {
-                                        let goodElse = self.checkStmt(*elseStmt.clone());
-                                        if(!goodElse){
-                                            println!("Error with else in if statement on line {}", lineNum.clone());
-                                            return false;
-                                        } else {
-                                            return true;
-                                        }
-                                    }
-                                    //If statement is good here if no else
-                                    None => {
-                                        return true;
-                                    }
-
-                                }
-                            } else {
-                                println!("Error with body of if statement on line: {}", lineNum.clone());
-                                return false;
-                            }
-                        }
-                    }
-                
-
-                    
-                    //All of the good conditions
-                    _ => {
-                        //Checks if the condition is good
-                        let goodCond = self.checkExpr(condition.clone());
-                        //If the condition is bad, fails here
-                        if (!goodCond){
-                            println!("Error in if condition on line {}", lineNum.clone());
-                            return false;
-                        //If the condition is good, checks the rest of the if statement
-                        } else {
-                            //Checks the if body
-                            let goodIfBody = self.checkStmt(*body);
-                            //If the body if good
-                            if(goodIfBody){
-                                //Checks if there is an else
-                                match elseBody{
-                                    //Checks the else
-                                    Some(elseStmt) => {
-                                        let goodElse = self.checkStmt(*elseStmt.clone());
-                                        if(!goodElse){
-                                            println!("Error with else in if statement on line {}", lineNum.clone());
-                                            return false;
-                                        } else {
-                                            return true;
-                                        }
-                                    }
-                                    //If statement is good here if no else
-                                    None => {
-                                        return true;
-                                    }
-
-                                }
-                            } else {
-                                println!("Error with body of if statement on line: {}", lineNum.clone());
-                                return false;
-                            }
-                        }
-                    }
-                }
-            }    
-            Stmt::For(assignment, condition, body, lineNum) => {
-
-                //Checks if the condition is valid
-                let checked = self.checkExpr(condition.clone());
-                if checked {
-                    //Continue
-                } else {
-                    println!("Error with for condition on line {}", lineNum.clone());
-                    return false;
-                }
-
-                //Ensures for condition is the correct type
-                match condition.clone() {
-                    Expr::IntArrayLiteral(size, array) => {
-                        println!("Error with if condition on line {}:\n Cannot use array as condition", lineNum.clone());
-                        return false;
-                    }
-                    Expr::FloatLiteral(val) => {
-                        println!("Error with if condition on line {}:\n Cannot use float as condition", lineNum.clone());
-                        return false;
-                    }
-                    Expr::StringLiteral(val) => {
-                        println!("Error with if condition on line {}:\n Cannot use string as condition", lineNum.clone());
-                        return false;
-                    }
-                    
-                    
-                    Expr::ProcRef(procName, params) => {
-                        println!("If condition procedure {}", procName.clone());
-                        let mut procType: VarType;
-                        //Checks if procedure is defined
-                        let checkLocProc = self.localTable.getType(&procName.clone());
-                        match checkLocProc{
-                            Some(proc) => {
-                                procType = proc;
-                            }
-                            None => {
-                                let checkGlobProc = self.localTable.getType(&procName.clone());
-                                match checkGlobProc{
-                                    Some(proc) => {
-                                        procType = proc
-                                    }
-                                    None => {
-                                        println!("Error on line {}:\n Procedure {} is not defined", lineNum.clone(), procName.clone());
-                                        return false;
-                                        
-                                    }
-                                }
-                            }
-                        }
-                    
-                        //Checks procedure type compatability with int
-                        match procType{
-                            VarType::Bool =>{
-                                println!("Procedure type bool");
-                            }
-                            VarType::Int =>{
-                                println!("Procedure type int");
-                            }
-                            VarType::Float =>{
-                                println!("Error with for condition on line {}:\n Cannot use float procedure as condition", lineNum.clone());
-                        return false;
-                            }
-                            _ => {
-                                println!("Error on line {}:\n Cannot use procedure of type {} as for condition", lineNum.clone(), procType.clone());
-                                return false;
-                            }
-                        }
-                    }   
-                    
-                    Expr::VarRef(varCondName) => {
-                        println!("Assigning: variable {}", varCondName.clone());
-                        let mut forCondType: VarType;
-                        //Checks if variable is defined
-                        let checkLocVar = self.localTable.getType(&varCondName.clone());
-                        match checkLocVar{
-                            Some(var) => {
-                                println!("variable exists locally");
-                                forCondType = var;
-                            }
-                            None => {
-                                println!("Variable does not exist locally, checking global");
-                                let checkGlobVar = self.localTable.getType(&varCondName.clone());
-                                match checkGlobVar{
-                                    Some(var) => {
-                                        println!("Variable exists globally");
-                                        forCondType = var
-                                    }
-                                    None => {
-                                        println!("Error on line {}:\n Variable {} is not defined", lineNum.clone(), varCondName.clone());
-                                        return false;
-                                        
-                                    }
-                                }
-                            }
-                        }
-                    
-                        //Checks variable type compatability with int
-                        match forCondType{
-                            VarType::Bool =>{
-                                println!("Variable type bool");
-                            }
-                            VarType::Int =>{
-                                println!("Variable type int");
-                            }
-                            VarType::Float =>{
-                                println!("Error on line {}:\n Cannot use variable of type float as for condition", lineNum.clone());
+                    //Checks to ensure that new value matches target value
+                    let targType = targValue.getType();
+                    match targType.clone(){
+                        //The only correct one
+                        VarType::Array(elemType, targSize) => {
+                            
+                            //Checks if the expression making up the index is valid
+                            let errCount = self.reports.errors.len();
+                            let checked = self.checkExpr(*targIndexExpr.clone());
+                            if (checked){
+                            } else {
+                                self.reportIfSilent(errCount, format!("Error with index expression on line {}", lineNum.clone()));
                                 return false;
                             }
-                            _ => {
-                                println!("Error on line {}:\n Cannot use variable of type {} as for condition", lineNum.clone(), forCondType.clone());
+                            
+                            //The index has to be an integer, and inside the array if it is known at compile time
+                            if !self.checkIndexType(targName, &targIndexExpr) || !self.checkConstIndex(targName, &targIndexExpr, targSize) {
                                 return false;
                             }
+
+                            //Now that we know the index is good, the new value has to fit the element type
+                            let elemName = format!("element of {}", targName.clone());
+                            return self.checkAssignValue(*elemType.clone(), &elemName, newValue.clone(), lineNum);
+                        }
+                        _ => {
+                            self.reportError(CheckCode::WrongKind, format!("Variable {} is not an array", targName.clone()));
+                            return false;
                         }
                     }
-                
 
-                    
-                    //All of the good conditions
+                } else {
+                    self.reportError(CheckCode::WrongKind, format!("On line {}: cannot assign to non-variable", lineNum.clone()));
+                    return false;
+                }
+
+
+            }
+            //For Stmts that are just Exprs
+            Stmt::Expr(expr, lineNum) => {
+                match (expr){
                     _ => {
-                        //Checks if the condition is good
-                        let goodCond = self.checkExpr(condition.clone());
-                        //If the condition is bad, fails here
-                        if (!goodCond){
-                            println!("Error in if condition on line {}", lineNum.clone());
-                            return false;
-                        //If the condition is good, checks the rest of the if statement
+                        let errCount = self.reports.errors.len();
+                        let checked = self.checkExpr(expr.clone());
+                        if checked {
+                            return true;
                         } else {
-                            //continue
+                            self.reportIfSilent(errCount, format!("Error with expression statement on line {}", lineNum.clone()));
+                            return false;
                         }
                     }
                 }
-
-                //Checks the for body
-                let forBodyCheck = self.checkStmt(*body);
-                //If the body for good
-                if(forBodyCheck){
-                    //Checks for there is an else
+            }
+            //A call on its own, any procedure can be called this way and whatever it returns is thrown away
+            Stmt::Call(procName, params, lineNum) => {
+                let errCount = self.reports.errors.len();
+                if self.checkCall(procName.clone(), params) {
                     return true;
                 } else {
-                    println!("Error with body of for statement on line: {}", lineNum.clone());
+                    self.reportIfSilent(errCount, format!("Error with call to {} on line {}", procName.clone(), lineNum.clone()));
                     return false;
                 }
+            }
+            //For checking if statements
+            Stmt::If(condition, body, elsifArms, elseBody, lineNum) => {
+                //Checks the condition, then keeps going into the bodies even if it is bad so their errors are found too
+                let mut good = self.checkCondition(condition.clone(), "if");
+
+                //Checks the if body
+                if !self.checkStmt(*body) {
+                    good = false;
+                }
+
+                //Checks each elsif arm the same way
+                for (elsifCond, elsifBody) in elsifArms {
+                    self.curLine = elsifBody.lineNum();
+                    if !self.checkCondition(elsifCond, "elsif") {
+                        good = false;
+                    }
+                    if !self.checkStmt(elsifBody) {
+                        good = false;
+                    }
+                }
+
+                //Checks the else if there is one
+                if let Some(elseStmt) = elseBody {
+                    if !self.checkStmt(*elseStmt) {
+                        good = false;
+                    }
+                }
+                return good;
+            }    
+            Stmt::Case(value, arms, others, lineNum) => {
+                //The value being switched on has to be an integer
+                self.curLine = lineNum.clone();
+                let errCount = self.reports.errors.len();
+                let mut good = true;
+                if !self.checkExpr(value.clone()) {
+                    self.reportIfSilent(errCount, format!("Error in case value on line {}", lineNum));
+                    good = false;
+                } else if let Some(valueType) = self.exprType(&value) {
+                    if valueType != VarType::Int {
+                        self.reportError(CheckCode::TypeMismatch, format!("case value must be an integer expression, found {}", valueType));
+                        good = false;
+                    }
+                }
+
+                //Every arm needs a constant value, and each value can only be handled once
+                let mut armLines: HashMap<i64, usize> = HashMap::new();
+                for (armValue, armBody) in arms {
+                    self.curLine = armBody.lineNum();
+                    match self.constIntValue(&armValue) {
+                        Some(constVal) => {
+                            if let Some(firstLine) = armLines.get(&constVal) {
+                                self.reportError(CheckCode::CaseArm, format!("Duplicate case arm {} on line {}, it is already handled on line {}", constVal, self.curLine.clone(), firstLine));
+                                good = false;
+                            } else {
+                                armLines.insert(constVal, self.curLine.clone());
+                            }
+                        }
+                        None => {
+                            self.reportError(CheckCode::CaseArm, format!("case arm value {} on line {} must be an integer constant", armValue, self.curLine.clone()));
+                            good = false;
+                        }
+                    }
+                    if !self.checkStmt(armBody) {
+                        good = false;
+                    }
+                }
+
+                //Checks the others arm if there is one
+                if let Some(othersBody) = others {
+                    if !self.checkStmt(*othersBody) {
+                        good = false;
+                    }
+                }
+                return good;
+            }
+            Stmt::For(assignment, condition, body, lineNum) => {
+                //Checks the loop variable assignment
+                let mut good = self.checkStmt((*assignment).clone());
+
+                //Checks the condition
+                self.curLine = lineNum.clone();
+                if !self.checkCondition(condition.clone(), "for") {
+                    good = false;
+                }
+
+                //Checks the for body
+                self.loopDepth += 1;
+                if !self.checkStmt(*body) {
+                    good = false;
+                }
+                self.loopDepth -= 1;
+                return good;
             }  
+            Stmt::While(condition, body, lineNum) => {
+                //Checks the condition
+                self.curLine = lineNum.clone();
+                let mut good = self.checkCondition(condition.clone(), "while");
+
+                //Checks the while body
+                self.loopDepth += 1;
+                if !self.checkStmt(*body) {
+                    good = false;
+                }
+                self.loopDepth -= 1;
+                return good;
+            }
+            Stmt::Break(lineNum) => {
+                if self.loopDepth == 0 {
+                    self.reportError(CheckCode::LoopControl, format!("break used outside of a loop on line {}", lineNum));
+                    return false;
+                }
+                return true;
+            }
+            Stmt::Continue(lineNum) => {
+                if self.loopDepth == 0 {
+                    self.reportError(CheckCode::LoopControl, format!("continue used outside of a loop on line {}", lineNum));
+                    return false;
+                }
+                return true;
+            }
+            //Checks every statement in the block, carrying on past bad ones so all of the errors are reported
             Stmt::Block(stmts, lineNum) => {
+                let mut good = true;
                 for instr in stmts {
-                    let good = self.checkStmt(instr.clone());
-                    if (!good){
-                        println!("Error in header:");
-                        instr.display(0);
-                        return false;
-                    } else {
-                        //continue
+                    if !self.checkStmt(instr.clone()) {
+                        good = false;
                     }
                 }
-                return true;
+                return good;
             }
             Stmt::Error(report, errMsg) => {
-                println!("Error found in AST: {}", errMsg);
+                self.reportError(CheckCode::Internal, format!("Error found in AST: {}", errMsg));
                 return false;
             }
             Stmt::Program(name, header, body, lineNum) => {
                 return true;
             }
             Stmt::Return(retVal, lineNum) => {
+                //A bare return has no value, which is only right if the procedure does not return one
+                if retVal == Expr::VarRef(String::new()) {
+                    if let Some(retType) = self.retType.clone() {
+                        if retType != VarType::Void {
+                            self.reportError(CheckCode::ReturnValue, format!("Procedure {} must return a value of type {}", self.name.clone(), retType));
+                            return false;
+                        }
+                    }
+                    return true;
+                }
+
+                let errCount = self.reports.errors.len();
                 let checked = self.checkExpr(retVal.clone());
                 if checked {
+                    //A procedure that returns nothing can only use a bare return
+                    if self.retType == Some(VarType::Void) {
+                        self.reportError(CheckCode::ReturnValue, format!("Procedure {} does not return a value, so it cannot return {}", self.name.clone(), retVal));
+                        return false;
+                    }
+
+                    //The value has to convert to the return type of the procedure, a bare return has nothing to convert
+                    if let (Some(retType), false) = (self.retType.clone(), retVal == Expr::VarRef(String::new())) {
+                        let valType = self.exprType(&retVal);
+                        if let Some(valType) = valType.clone() {
+                            if !self.checkTypeCompatability(retType.clone(), valType.clone()) {
+                                self.reportError(CheckCode::TypeMismatch, format!("Cannot return {} from procedure {} of type {}", valType, self.name.clone(), retType));
+                                return false;
+                            }
+                        }
+                        self.warnIfLossy(valType, &retType, &retVal, format!("the return value of {}", self.name.clone()));
+                    }
                     return true;
                 } else {
-                    println!("Error with return statement on line {}", lineNum.clone());
+                    self.reportIfSilent(errCount, format!("Error with return statement on line {}", lineNum.clone()));
                     return false;
                 }
             }
@@ -3112,15 +2304,120 @@ impl<'a> SyntaxChecker<'a> {
     }
 }
 
+//The implicit conversions between types allowed by the language
+//This is the one table both the SyntaxChecker and the Compiler use, so what is checked is what gets generated
+#[derive(Debug, Clone, PartialEq)]
+pub enum Coercion {
+    Same,           //The types already match
+    IntToFloat,     //sitofp
+    FloatToInt,     //fptosi, the fraction is dropped
+    IntToBool,      //icmp ne 0, any nonzero integer is true
+    BoolToInt,      //zext, true is 1 and false is 0
+    Incompatible,   //No conversion exists
+}
+impl Coercion {
+    //Looks up the conversion needed to use a value of one type as another
+    pub fn find(from: &VarType, to: &VarType) -> Coercion {
+        match (from, to) {
+            (VarType::Int, VarType::Int) => Coercion::Same,
+            (VarType::Float, VarType::Float) => Coercion::Same,
+            (VarType::Bool, VarType::Bool) => Coercion::Same,
+            (VarType::Str, VarType::Str) => Coercion::Same,
+            (VarType::Int, VarType::Float) => Coercion::IntToFloat,
+            (VarType::Float, VarType::Int) => Coercion::FloatToInt,
+            (VarType::Int, VarType::Bool) => Coercion::IntToBool,
+            (VarType::Bool, VarType::Int) => Coercion::BoolToInt,
+            //Arrays have to hold the same type and be the same size
+            (VarType::Array(fromElem, fromSize), VarType::Array(toElem, toSize)) => {
+                if (fromElem == toElem) && (fromSize == toSize) {
+                    Coercion::Same
+                } else {
+                    Coercion::Incompatible
+                }
+            }
+            _ => Coercion::Incompatible,
+        }
+    }
+
+    //Whether the value can be converted at all
+    pub fn allowed(&self) -> bool {
+        return *self != Coercion::Incompatible;
+    }
+
+    //Whether the conversion can change the value, these get a warning
+    //A float only has 24 bits of mantissa, so integers past 2^24 are rounded when converted
+    pub fn losesPrecision(&self) -> bool {
+        match self {
+            Coercion::IntToFloat => true,
+            Coercion::FloatToInt => true,
+            Coercion::IntToBool => true,
+            _ => false,
+        }
+    }
+
+    //Whether an integer converts to a float without being rounded
+    pub fn exactInFloat(val: i64) -> bool {
+        return val.abs() <= (1 << 24);
+    }
+
+    //The type both operands of an arithmetic or relational operation are converted to before it is done
+    //Floats win over integers, and bools are used as the integers 0 and 1, None if the operands can not be mixed
+    //Whole arrays are worked on element by element, with a single value used against every element
+    //Strings have no common type, even with each other, since the language has no string arithmetic or comparison
+    pub fn commonType(type1: &VarType, type2: &VarType) -> Option<VarType> {
+        match (type1, type2) {
+            (VarType::Array(elem1, size1), VarType::Array(elem2, size2)) => {
+                if size1 != size2 {
+                    return None;
+                }
+                Some(VarType::Array(Box::new(Coercion::commonType(elem1, elem2)?), *size1))
+            }
+            (VarType::Array(elem, size), single) | (single, VarType::Array(elem, size)) => {
+                Some(VarType::Array(Box::new(Coercion::commonType(elem, single)?), *size))
+            }
+            (VarType::Float, VarType::Float) | (VarType::Float, VarType::Int) | (VarType::Int, VarType::Float) => Some(VarType::Float),
+            (VarType::Int, VarType::Int) | (VarType::Int, VarType::Bool) | (VarType::Bool, VarType::Int) | (VarType::Bool, VarType::Bool) => Some(VarType::Int),
+            _ => None,
+        }
+    }
+}
+
 //Used for storing the values of a hashed item
 #[derive(Debug, Clone, PartialEq)]
 pub enum HashItemType{
-    Procedure(Box<Stmt>, Vec<String>, SymbolTable),   //For storing procedures (The procedure AST, a list of the parameter names in order, the SymbolTable populated with the parameters)
+    Procedure(Box<Stmt>, Vec<ParamSig>, SymbolTable),   //For storing procedures (The procedure AST, the signature of its parameters in order, the SymbolTable populated with the parameters)
     Variable,
 }
 impl HashItemType{
-    pub fn newProcItem(procAst: Box<Stmt>, params: Vec<String>, procST: SymbolTable) -> HashItemType{
-        return HashItemType::Procedure(procAst, params, procST);
+    pub fn newProcItem(procAst: Box<Stmt>, signature: Vec<ParamSig>, procST: SymbolTable) -> HashItemType{
+        return HashItemType::Procedure(procAst, signature, procST);
+    }
+
+    //Used for the built in functions, they have no AST or symbol table of their own, only a signature
+    pub fn newBuiltIn(signature: Vec<ParamSig>) -> HashItemType{
+        return HashItemType::Procedure(Box::new(Stmt::StringLiteral("NONE".to_string(), 0)), signature, SymbolTable::newEmpty());
+    }
+}
+
+//One parameter in the signature of a procedure, every call is checked against these
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamSig {
+    pub name: String,       //The name of the parameter
+    pub paramType: VarType, //The type the argument has to convert to
+    pub mode: ParamMode,    //How the argument is passed
+}
+impl ParamSig {
+    pub fn new(name: String, paramType: VarType, mode: ParamMode) -> ParamSig {
+        ParamSig{
+            name,
+            paramType,
+            mode,
+        }
+    }
+
+    //An in parameter of a built in function
+    pub fn builtIn(name: &str, paramType: VarType) -> ParamSig {
+        return ParamSig::new(name.to_string(), paramType, ParamMode::In);
     }
 }
 
@@ -3186,76 +2483,15 @@ impl SymbolTable{
 
         //Seeding the symbol table with the built in functions
         let builtIns = vec![
-            ("getbool", HashItem::newProc("getbool".to_string(), VarType::Bool, HashItemType::Procedure(Box::new(Stmt::StringLiteral("NONE".to_string(), ("0".to_string()))), Vec::new(), SymbolTable::newBuiltIn()))),
-            ("getinteger", HashItem::newProc("getinteger".to_string(), VarType::Int, HashItemType::Procedure(Box::new(Stmt::StringLiteral("NONE".to_string(), ("0".to_string()))), Vec::new(), SymbolTable::newBuiltIn()))),
-            ("getfloat", HashItem::newProc("getfloat".to_string(), VarType::Float, HashItemType::Procedure(Box::new(Stmt::StringLiteral("NONE".to_string(), ("0".to_string()))), Vec::new(), SymbolTable::newBuiltIn()))),
-            ("getstring", HashItem::newProc("getstring".to_string(), VarType::Str, HashItemType::Procedure(Box::new(Stmt::StringLiteral("NONE".to_string(), ("0".to_string()))), Vec::new(), SymbolTable::newBuiltIn()))),
-            ("getbool", HashItem::newProc("getBool".to_string(), VarType::Bool, HashItemType::Procedure(Box::new(Stmt::StringLiteral("NONE".to_string(), ("0".to_string()))), Vec::new(), SymbolTable::newBuiltIn()))),
-            
-            (
-                "putbool",
-                HashItem::newProc(
-                    "putbool".to_string(),
-                    VarType::Bool,
-                    HashItemType::Procedure(
-                        Box::new(Stmt::StringLiteral("NONE".to_string(), "0".to_string())),
-                        vec!["boolparam".to_string()], 
-                        SymbolTable::newBuiltIn(),
-                    ),
-                ),
-            ),
-
-            (
-                "putinteger",
-                HashItem::newProc(
-                    "putinteger".to_string(),
-                    VarType::Bool,
-                    HashItemType::Procedure(
-                        Box::new(Stmt::StringLiteral("NONE".to_string(), "0".to_string())),
-                        vec!["intparam".to_string()], 
-                        SymbolTable::newBuiltIn(),
-                    ),
-                ),
-            ),
-
-            (
-                "putfloat",
-                HashItem::newProc(
-                    "putfloat".to_string(),
-                    VarType::Bool,
-                    HashItemType::Procedure(
-                        Box::new(Stmt::StringLiteral("NONE".to_string(), "0".to_string())),
-                        vec!["floatparam".to_string()],  
-                        SymbolTable::newBuiltIn(),
-                    ),
-                ),
-            ),
-
-            (
-                "putstring",
-                HashItem::newProc(
-                    "putstring".to_string(),
-                    VarType::Bool,
-                    HashItemType::Procedure(
-                        Box::new(Stmt::StringLiteral("NONE".to_string(), "0".to_string())),
-                        vec!["stringparam".to_string()],
-                        SymbolTable::newBuiltIn(),
-                    ),
-                ),
-            ),
-
-            (
-                "sqrt",
-                HashItem::newProc(
-                    "sqrt".to_string(),
-                    VarType::Float,
-                    HashItemType::Procedure(
-                        Box::new(Stmt::StringLiteral("NONE".to_string(), "0".to_string())),
-                        vec!["intparam".to_string()],
-                        SymbolTable::newBuiltIn(),
-                    ),
-                ),
-            ),
+            ("getbool", HashItem::newProc("getbool".to_string(), VarType::Bool, HashItemType::newBuiltIn(Vec::new()))),
+            ("getinteger", HashItem::newProc("getinteger".to_string(), VarType::Int, HashItemType::newBuiltIn(Vec::new()))),
+            ("getfloat", HashItem::newProc("getfloat".to_string(), VarType::Float, HashItemType::newBuiltIn(Vec::new()))),
+            ("getstring", HashItem::newProc("getstring".to_string(), VarType::Str, HashItemType::newBuiltIn(Vec::new()))),
+            ("putbool", HashItem::newProc("putbool".to_string(), VarType::Bool, HashItemType::newBuiltIn(vec![ParamSig::builtIn("value", VarType::Bool)]))),
+            ("putinteger", HashItem::newProc("putinteger".to_string(), VarType::Bool, HashItemType::newBuiltIn(vec![ParamSig::builtIn("value", VarType::Int)]))),
+            ("putfloat", HashItem::newProc("putfloat".to_string(), VarType::Bool, HashItemType::newBuiltIn(vec![ParamSig::builtIn("value", VarType::Float)]))),
+            ("putstring", HashItem::newProc("putstring".to_string(), VarType::Bool, HashItemType::newBuiltIn(vec![ParamSig::builtIn("value", VarType::Str)]))),
+            ("sqrt", HashItem::newProc("sqrt".to_string(), VarType::Float, HashItemType::newBuiltIn(vec![ParamSig::builtIn("value", VarType::Int)]))),
         ];
         //Inserted seed values into hash table
         for (key, value) in builtIns {
@@ -3273,34 +2509,6 @@ impl SymbolTable{
         }
     }
     
-    pub fn newBuiltIn() -> SymbolTable {
-        //Creates the empty hash map
-        let mut symHash: HashMap<String, HashItem> = HashMap::new();
-
-        let mut builtInHash: HashMap<String, HashItem> = HashMap::new();
-        let builtInStmt = Stmt::StringLiteral(("NULL".to_string()), "0".to_string());
-        //Seeding the symbol table with the built in functions
-        let builtIns = vec![
-            ("boolparam", HashItem::newVar("boolparam".to_string(), VarType::Bool)),
-            ("intparam", HashItem::newVar("intparam".to_string(), VarType::Int)),
-            ("floatparam", HashItem::newVar("floatparam".to_string(), VarType::Float)),
-            ("stringparam", HashItem::newVar("stringparam".to_string(), VarType::Str)),
-
-
-        ];
-        //Inserted seed values into hash table
-        for (key, value) in builtIns {
-            symHash.insert(key.to_string(), value);
-        }
-
-        
-
-
-        SymbolTable{
-            symTab: symHash,
-        }
-    }
-
     pub fn newEmpty() -> SymbolTable {
         //Creates the empty hash map
         let mut symHash: HashMap<String, HashItem> = HashMap::new();
@@ -3366,4 +2574,408 @@ fn combine_blocks(block1: Box<Stmt>, block2: Box<Stmt>) -> Option<Vec<Stmt>> {
 }
 
 ///////////////////////// /TYPE CHECKING SECTION /////////////////////////
-    
\ No newline at end of file
+    
+
+
+
+
+///////////////////////// TESTS /////////////////////////
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::lexer::Lexer;
+    use crate::models::parser::Parser;
+    use crate::models::reporting::Severity;
+
+    //Lexes, parses and checks the source, giving back whether it passed and the diagnostics
+    fn check(source: &str) -> (bool, Reporting) {
+        let mut lexer = Lexer::fromSource("test.src", source);
+        lexer.scanThrough();
+        let mut parser = Parser::new(&mut lexer);
+        let ast = match parser.startParse() {
+            Ok((_, Some(ast))) => ast,
+            _ => panic!("test program failed to parse"),
+        };
+        let mut table = SymbolTable::new();
+        let mut checker = SyntaxChecker::new(ast, &mut table, "Main".to_string());
+        let valid = checker.checkProgram();
+        return (valid, checker.reports.clone());
+    }
+
+    //Wraps the declarations and statements in a program
+    fn program(decls: &str, body: &str) -> String {
+        return format!("program test is\n{}\nbegin\n{}\nend program.\n", decls, body);
+    }
+
+    fn intArray(size: i32) -> VarType {
+        return VarType::Array(Box::new(VarType::Int), size);
+    }
+
+    fn floatArray(size: i32) -> VarType {
+        return VarType::Array(Box::new(VarType::Float), size);
+    }
+
+    #[test]
+    fn findScalars() {
+        let scalars = [VarType::Int, VarType::Float, VarType::Bool, VarType::Str];
+        for from in scalars.iter() {
+            for to in scalars.iter() {
+                let expected = match (from, to) {
+                    _ if from == to => Coercion::Same,
+                    (VarType::Int, VarType::Float) => Coercion::IntToFloat,
+                    (VarType::Float, VarType::Int) => Coercion::FloatToInt,
+                    (VarType::Int, VarType::Bool) => Coercion::IntToBool,
+                    (VarType::Bool, VarType::Int) => Coercion::BoolToInt,
+                    _ => Coercion::Incompatible,
+                };
+                assert_eq!(Coercion::find(from, to), expected, "{} to {}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn findArrays() {
+        assert_eq!(Coercion::find(&intArray(3), &intArray(3)), Coercion::Same);
+        assert_eq!(Coercion::find(&intArray(3), &intArray(4)), Coercion::Incompatible);
+        assert_eq!(Coercion::find(&intArray(3), &floatArray(3)), Coercion::Incompatible);
+        assert_eq!(Coercion::find(&intArray(3), &VarType::Int), Coercion::Incompatible);
+        assert_eq!(Coercion::find(&VarType::Int, &intArray(3)), Coercion::Incompatible);
+        assert_eq!(Coercion::find(&VarType::Void, &VarType::Void), Coercion::Incompatible);
+        assert_eq!(Coercion::find(&VarType::Int, &VarType::Void), Coercion::Incompatible);
+    }
+
+    #[test]
+    fn allowedAndLossy() {
+        let rows = [
+            (Coercion::Same, true, false),
+            (Coercion::IntToFloat, true, true),
+            (Coercion::FloatToInt, true, true),
+            (Coercion::IntToBool, true, true),
+            (Coercion::BoolToInt, true, false),
+            (Coercion::Incompatible, false, false),
+        ];
+        for (coercion, allowed, lossy) in rows.iter() {
+            assert_eq!(coercion.allowed(), *allowed, "{:?}", coercion);
+            assert_eq!(coercion.losesPrecision(), *lossy, "{:?}", coercion);
+        }
+    }
+
+    #[test]
+    fn exactInFloat() {
+        assert!(Coercion::exactInFloat(0));
+        assert!(Coercion::exactInFloat(1 << 24));
+        assert!(Coercion::exactInFloat(-(1 << 24)));
+        assert!(!Coercion::exactInFloat((1 << 24) + 1));
+        assert!(!Coercion::exactInFloat(-(1 << 24) - 1));
+    }
+
+    #[test]
+    fn commonTypeScalars() {
+        let rows = [
+            (VarType::Int, VarType::Int, Some(VarType::Int)),
+            (VarType::Int, VarType::Float, Some(VarType::Float)),
+            (VarType::Float, VarType::Int, Some(VarType::Float)),
+            (VarType::Float, VarType::Float, Some(VarType::Float)),
+            (VarType::Int, VarType::Bool, Some(VarType::Int)),
+            (VarType::Bool, VarType::Int, Some(VarType::Int)),
+            (VarType::Bool, VarType::Bool, Some(VarType::Int)),
+            (VarType::Float, VarType::Bool, None),
+            (VarType::Bool, VarType::Float, None),
+            (VarType::Str, VarType::Str, None),
+            (VarType::Str, VarType::Int, None),
+            (VarType::Float, VarType::Str, None),
+            (VarType::Void, VarType::Int, None),
+        ];
+        for (type1, type2, expected) in rows.iter() {
+            assert_eq!(Coercion::commonType(type1, type2), *expected, "{} and {}", type1, type2);
+        }
+    }
+
+    #[test]
+    fn commonTypeArrays() {
+        assert_eq!(Coercion::commonType(&intArray(3), &intArray(3)), Some(intArray(3)));
+        assert_eq!(Coercion::commonType(&intArray(3), &floatArray(3)), Some(floatArray(3)));
+        assert_eq!(Coercion::commonType(&intArray(3), &intArray(4)), None);
+        assert_eq!(Coercion::commonType(&intArray(3), &VarType::Float), Some(floatArray(3)));
+        assert_eq!(Coercion::commonType(&VarType::Bool, &intArray(2)), Some(intArray(2)));
+        assert_eq!(Coercion::commonType(&VarType::Str, &intArray(2)), None);
+        assert_eq!(Coercion::commonType(&VarType::Array(Box::new(VarType::Str), 2), &VarType::Array(Box::new(VarType::Str), 2)), None);
+    }
+
+    #[test]
+    fn stringComparisonRejected() {
+        let decls = "variable s : string;\nvariable b : bool;";
+        for cond in ["s == s", "s != \"x\"", "\"x\" == s"] {
+            let (valid, reports) = check(&program(decls, &format!("s := \"x\";\nb := {};", cond)));
+            assert!(!valid, "{}", cond);
+            assert_eq!(reports.diagnostics.len(), 1, "{}", cond);
+            assert_eq!(reports.diagnostics[0].code, "T001", "{:?}", reports.errors);
+            assert!(reports.errors[0].contains("relational operators only work on integer, float and bool values, not strings"), "{}", reports.errors[0]);
+        }
+    }
+
+    #[test]
+    fn intToFloatWarnings() {
+        let decls = "variable f : float;\nvariable i : integer;";
+
+        //Small constants are exact as floats
+        let (valid, reports) = check(&program(decls, "f := 16777216;\nf := 3 * 4;"));
+        assert!(valid);
+        assert!(reports.warnings.is_empty(), "{:?}", reports.warnings);
+
+        //Large constants and variables can be rounded
+        let (valid, reports) = check(&program(decls, "f := 16777217;\ni := 1;\nf := i;"));
+        assert!(valid);
+        assert_eq!(reports.warnings.len(), 2, "{:?}", reports.warnings);
+        assert!(reports.diagnostics.iter().all(|diag| diag.code == "T101"));
+    }
+
+    #[test]
+    fn scalarAssignmentsFollowCoercion() {
+        let decls = "variable vi : integer;\nvariable vf : float;\nvariable vb : bool;\nvariable vs : string;";
+        let vars = [("vi", VarType::Int), ("vf", VarType::Float), ("vb", VarType::Bool), ("vs", VarType::Str)];
+        for (targName, targType) in vars.iter() {
+            for (valueName, valueType) in vars.iter() {
+                let (valid, reports) = check(&program(decls, &format!("{} := {};", targName, valueName)));
+                assert_eq!(valid, Coercion::find(valueType, targType).allowed(), "{} := {}", targName, valueName);
+                if !valid {
+                    assert_eq!(reports.diagnostics[0].code, "T001", "{:?}", reports.errors);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn arrayArithmeticIntoScalar() {
+        let decls = "variable i : integer;\nvariable f : float;\nvariable a : integer[3];\nvariable c : integer[3];";
+        for body in ["i := a + c;", "f := a * 2;", "i := 1 - c;", "a[0] := a + c;", "for (i := a + c; i < 3)\n    i := i + 1;\nend for;"] {
+            let (valid, reports) = check(&program(decls, body));
+            assert!(!valid, "{}", body);
+            assert_eq!(reports.diagnostics.len(), 1, "{}: {:?}", body, reports.errors);
+            assert_eq!(reports.diagnostics[0].code, "T001", "{:?}", reports.errors);
+            assert!(reports.errors[0].starts_with("Cannot assign Int[3] to "), "{}", reports.errors[0]);
+        }
+
+        //Element by element arithmetic is still fine when the result is a whole array
+        let (valid, reports) = check(&program(decls, "a := a + c;\nc := a * 2;"));
+        assert!(valid, "{:?}", reports.errors);
+    }
+
+    #[test]
+    fn procedureNameAsValue() {
+        let decls = "variable i : integer;\nvariable a : integer[3];\nprocedure g : integer (variable n : integer)\nbegin\n    return n;\nend procedure;";
+        for body in ["i := g;", "i := g + 1;", "a[0] := g;", "i := g(g);", "for (i := g; i < 3)\n    i := i + 1;\nend for;"] {
+            let (valid, reports) = check(&program(decls, body));
+            assert!(!valid, "{}", body);
+            assert_eq!(reports.diagnostics.len(), 1, "{}: {:?}", body, reports.errors);
+            assert_eq!(reports.diagnostics[0].code, "T003", "{:?}", reports.errors);
+            assert_eq!(reports.errors[0], "g is not a variable");
+        }
+
+        //Inside its own body the procedure is not in a table yet
+        let decls = "procedure g : integer (variable n : integer)\nbegin\n    return g;\nend procedure;";
+        let (valid, reports) = check(&program(decls, ""));
+        assert!(!valid);
+        assert_eq!(reports.diagnostics[0].code, "T003", "{:?}", reports.errors);
+    }
+
+    //Checks a program that uses the given index into a four element array, both when reading and assigning
+    fn checkIndex(index: &str) -> Vec<(bool, Reporting)> {
+        let decls = "variable a : integer[4];\nvariable i : integer;\nvariable b : bool;\nvariable f : float;\nvariable s : string;";
+        return vec![
+            check(&program(decls, &format!("i := a[{}];", index))),
+            check(&program(decls, &format!("a[{}] := 1;", index))),
+        ];
+    }
+
+    #[test]
+    fn constIndexInRange() {
+        for index in ["0", "3", "1 + 2", "6 / 2", "i", "i + 4"] {
+            for (valid, reports) in checkIndex(index) {
+                assert!(valid, "a[{}]: {:?}", index, reports.errors);
+                assert!(reports.errors.is_empty(), "a[{}]: {:?}", index, reports.errors);
+            }
+        }
+    }
+
+    #[test]
+    fn constIndexOutOfRange() {
+        let cases = [("-1", -1), ("4", 4), ("2 * 2", 4), ("0 - 1", -1), ("3 - 4", -1), ("100", 100)];
+        for (index, value) in cases.iter() {
+            for (valid, reports) in checkIndex(index) {
+                assert!(!valid, "a[{}]", index);
+                assert_eq!(reports.diagnostics.len(), 1, "a[{}]: {:?}", index, reports.errors);
+                assert_eq!(reports.diagnostics[0].code, "T008");
+                assert!(reports.errors[0].contains(&format!("Array index {} is out of bounds for a[4]", value)), "{}", reports.errors[0]);
+            }
+        }
+    }
+
+    #[test]
+    fn indexTypes() {
+        //Bools are used as 0 or 1
+        for index in ["b", "i < 2", "true", "a[i]", "a[0] + i"] {
+            for (valid, reports) in checkIndex(index) {
+                assert!(valid, "a[{}]: {:?}", index, reports.errors);
+            }
+        }
+
+        let cases = [("1.5", "Float"), ("f", "Float"), ("f * 2", "Float"), ("s", "Str"), ("\"x\"", "Str"), ("a", "Int[4]")];
+        for (index, found) in cases.iter() {
+            for (valid, reports) in checkIndex(index) {
+                assert!(!valid, "a[{}]", index);
+                assert_eq!(reports.diagnostics.len(), 1, "a[{}]: {:?}", index, reports.errors);
+                assert_eq!(reports.diagnostics[0].code, "T001");
+                assert_eq!(reports.errors[0], format!("Array index for a must be an integer, found {}", found));
+            }
+        }
+    }
+
+    #[test]
+    fn loopControlInsideLoops() {
+        let decls = "variable i : integer;";
+        let body = "while (i < 3)\n    if (i == 1) then\n        continue;\n    end if;\n    exit;\nend while;\nfor (i := 0; i < 3)\n    case (i) is\n        when 2 then\n            break;\n        when others then\n            i := i + 1;\n    end case;\nend for;";
+        let (valid, reports) = check(&program(decls, body));
+        assert!(valid, "{:?}", reports.errors);
+    }
+
+    #[test]
+    fn loopControlOutsideLoops() {
+        let decls = "variable i : integer;\nprocedure p (variable x : integer)\nbegin\n    break;\nend procedure;";
+        let body = "break;\ncontinue;\nif (i < 1) then\n    exit;\nend if;\nwhile (i < 3)\n    p(i);\nend while;";
+        let (valid, reports) = check(&program(decls, body));
+        assert!(!valid);
+        assert_eq!(reports.diagnostics.len(), 4, "{:?}", reports.errors);
+        assert!(reports.diagnostics.iter().all(|diag| diag.code == "T009"), "{:?}", reports.errors);
+        assert!(reports.errors[0].starts_with("break used outside of a loop"), "{}", reports.errors[0]);
+        assert!(reports.errors.iter().any(|err| err.starts_with("continue used outside of a loop")), "{:?}", reports.errors);
+    }
+
+    //Wraps the arms in a case over i
+    fn caseOver(arms: &[&str]) -> String {
+        let armText: Vec<String> = arms.iter().map(|arm| format!("    when {} then\n        i := 0;", arm)).collect();
+        return program("variable i : integer;", &format!("case (i) is\n{}\nend case;", armText.join("\n")));
+    }
+
+    #[test]
+    fn distinctCaseArms() {
+        let (valid, reports) = check(&caseOver(&["1", "-1", "2", "others"]));
+        assert!(valid, "{:?}", reports.errors);
+    }
+
+    #[test]
+    fn duplicateCaseArms() {
+        for arms in [vec!["1", "2", "1"], vec!["-2", "2", "-2", "others"]] {
+            let (valid, reports) = check(&caseOver(&arms));
+            assert!(!valid, "{:?}", arms);
+            assert_eq!(reports.diagnostics.len(), 1, "{:?}: {:?}", arms, reports.errors);
+            assert_eq!(reports.diagnostics[0].code, "T011");
+            assert!(reports.errors[0].starts_with(&format!("Duplicate case arm {}", arms[0])), "{}", reports.errors[0]);
+        }
+
+        //Arms have to be constants so duplicates can be found
+        let (valid, reports) = check(&caseOver(&["i"]));
+        assert!(!valid);
+        assert_eq!(reports.diagnostics[0].code, "T011", "{:?}", reports.errors);
+    }
+
+    #[test]
+    fn outArguments() {
+        let decls = "variable i : integer;\nvariable f : float;\nvariable a : integer[3];\nprocedure set (variable x : out integer, variable y : in out integer, variable z : in integer)\nbegin\n    x := y + z;\nend procedure;";
+        let (valid, reports) = check(&program(decls, "set(i, a[1], 1);\nset(a[0], i, i + 1);"));
+        assert!(valid, "{:?}", reports.errors);
+
+        //Out and in out parameters are written to, so they need something that can be assigned
+        let cases = [("set(1, i, 1);", "out parameter x", "1"), ("set(i + 1, i, 1);", "out parameter x", "(i + 1)"), ("set(i, 2, 1);", "in out parameter y", "2")];
+        for (call, param, found) in cases.iter() {
+            let (valid, reports) = check(&program(decls, call));
+            assert!(!valid, "{}", call);
+            assert_eq!(reports.diagnostics.len(), 1, "{}: {:?}", call, reports.errors);
+            assert_eq!(reports.diagnostics[0].code, "T005");
+            assert!(reports.errors[0].contains(&format!("must be a variable or array element, {} is written to, found {}", param, found)), "{}", reports.errors[0]);
+        }
+
+        //The caller's variable is written through directly, so it has to have the parameter's type
+        let (valid, reports) = check(&program(decls, "set(f, i, 1);"));
+        assert!(!valid);
+        assert_eq!(reports.diagnostics[0].code, "T005", "{:?}", reports.errors);
+        assert!(reports.errors[0].contains("out parameter x expects exactly Int"), "{}", reports.errors[0]);
+    }
+
+    #[test]
+    fn callArgumentCounts() {
+        let decls = "variable i : integer;\nprocedure f : integer (variable x : integer, variable y : bool)\nbegin\n    return x;\nend procedure;";
+        let (valid, reports) = check(&program(decls, "i := f(1, true);\nputinteger(i);"));
+        assert!(valid, "{:?}", reports.errors);
+
+        let cases = [("i := f(1);", "Call to f gives 1 arguments, but it takes 2"), ("i := f(1, true, 2);", "Call to f gives 3 arguments, but it takes 2"), ("putinteger();", "Call to putinteger gives 0 arguments, but it takes 1")];
+        for (call, message) in cases.iter() {
+            let (valid, reports) = check(&program(decls, call));
+            assert!(!valid, "{}", call);
+            assert_eq!(reports.diagnostics.len(), 1, "{}: {:?}", call, reports.errors);
+            assert_eq!(reports.diagnostics[0].code, "T004");
+            assert_eq!(reports.errors[0], *message);
+        }
+    }
+
+    #[test]
+    fn callArgumentTypes() {
+        let decls = "variable i : integer;\nvariable s : string;\nvariable a : integer[3];\nprocedure f : integer (variable x : integer, variable y : bool)\nbegin\n    return x;\nend procedure;";
+        let cases = [
+            ("i := f(s, true);", "Argument 1 of call to f has type Str, but parameter x expects Int"),
+            ("i := f(a, true);", "Argument 1 of call to f has type Int[3], but parameter x expects Int"),
+            ("i := f(1, s);", "Argument 2 of call to f has type Str, but parameter y expects Bool"),
+            ("putinteger(s);", "Argument 1 of call to putinteger has type Str, but parameter value expects Int"),
+        ];
+        for (call, message) in cases.iter() {
+            let (valid, reports) = check(&program(decls, call));
+            assert!(!valid, "{}", call);
+            assert_eq!(reports.diagnostics.len(), 1, "{}: {:?}", call, reports.errors);
+            assert_eq!(reports.diagnostics[0].code, "T005");
+            assert_eq!(reports.errors[0], *message);
+        }
+    }
+
+    //A forward declaration of f, g calling it, and then the given definition of f calling g back
+    fn mutualRecursion(definition: &str) -> String {
+        let decls = format!("variable i : integer;\nprocedure f : integer (variable x : integer) forward;\nprocedure g : integer (variable x : integer)\nbegin\n    return f(x);\nend procedure;\n{}\nbegin\n    return g(1);\nend procedure;", definition);
+        return program(&decls, "i := f(1);");
+    }
+
+    #[test]
+    fn forwardDeclarations() {
+        let (valid, reports) = check(&mutualRecursion("procedure f : integer (variable x : integer)"));
+        assert!(valid, "{:?}", reports.errors);
+    }
+
+    #[test]
+    fn forwardDeclarationMismatch() {
+        let definitions = [
+            "procedure f : float (variable x : integer)",
+            "procedure f : integer (variable x : float)",
+            "procedure f : integer (variable y : integer)",
+            "procedure f : integer (variable x : integer, variable y : integer)",
+            "procedure f : integer (variable x : out integer)",
+        ];
+        for definition in definitions.iter() {
+            let (valid, reports) = check(&mutualRecursion(definition));
+            assert!(!valid, "{}", definition);
+            let firstError = reports.diagnostics.iter().find(|diag| diag.severity == Severity::Error).unwrap();
+            assert_eq!(firstError.code, "T010", "{}: {:?}", definition, reports.errors);
+            assert_eq!(reports.errors[0], "Procedure f does not match its forward declaration on line 3");
+        }
+    }
+
+    #[test]
+    fn forwardDeclarationNeverDefined() {
+        let decls = "variable i : integer;\nprocedure g : integer (variable x : integer) forward;";
+        let (valid, reports) = check(&program(decls, "i := 1;"));
+        assert!(!valid);
+        assert_eq!(reports.diagnostics.len(), 1, "{:?}", reports.errors);
+        assert_eq!(reports.diagnostics[0].code, "T010");
+        assert_eq!(reports.errors[0], "Procedure g is declared forward but never defined in the same header");
+    }
+}
+
+///////////////////////// /TESTS /////////////////////////
@@ -0,0 +1,192 @@
+//Rules
+#![allow(non_snake_case)]
+
+//Tests that drive the whole compiler through compile_source, the way main.rs and other tools use it
+
+use compiler::{compile_source, Artifacts, Diagnostics, EmitType, Options};
+use compiler::models::reporting::Severity;
+
+//A small program with nothing to warn about
+const CLEAN: &str = "program clean is
+    variable a : integer;
+begin
+    a := 3;
+    putinteger(a);
+end program.
+";
+
+//Runs the source through the pipeline, stopping at the given emit type
+fn compile(source: &str, emit: EmitType) -> Result<Artifacts, Diagnostics> {
+    return compile_source(source, Options::new("test.src", emit));
+}
+
+//Gets the codes of every diagnostic of the given severity, in the order they were reported
+fn codes(diagnostics: &Diagnostics, severity: Severity) -> Vec<String> {
+    return diagnostics.reports.diagnostics.iter().filter(|diag| diag.severity == severity).map(|diag| diag.code.clone()).collect();
+}
+
+//Compiles source that is expected to fail and gets the diagnostics
+fn compileErr(source: &str) -> Diagnostics {
+    match compile(source, EmitType::LlvmIr) {
+        Ok(_) => panic!("expected the program to fail to compile"),
+        Err(diagnostics) => diagnostics,
+    }
+}
+
+#[test]
+fn emitTokens() {
+    let artifacts = compile(CLEAN, EmitType::Tokens).unwrap();
+    let output = String::from_utf8(artifacts.output).unwrap();
+    assert!(output.starts_with("< \"program\" , PROGRAM, 1 >"), "{}", output);
+    assert!(output.contains("< \"putinteger\" , PROCEDURE_CALL, 5 >"), "{}", output);
+    assert_eq!(artifacts.tokens.len(), output.lines().count());
+    assert!(artifacts.ast.is_none());
+}
+
+#[test]
+fn emitAst() {
+    let artifacts = compile(CLEAN, EmitType::Ast).unwrap();
+    let output = String::from_utf8(artifacts.output).unwrap();
+    assert!(output.starts_with("clean:("), "{}", output);
+    assert!(output.contains("GlobVarDecl(a, Int)"), "{}", output);
+    assert!(output.contains("Call(putinteger(a))"), "{}", output);
+    assert!(artifacts.ast.is_some());
+}
+
+#[test]
+fn emitLlvmIr() {
+    let artifacts = compile(CLEAN, EmitType::LlvmIr).unwrap();
+    let output = String::from_utf8(artifacts.output).unwrap();
+    assert!(output.contains("@a = global i32 0"), "{}", output);
+    assert!(output.contains("define i32 @main()"), "{}", output);
+    assert!(output.contains("call i1 @putinteger(i32"), "{}", output);
+    assert!(artifacts.diagnostics.reports.diagnostics.is_empty());
+}
+
+#[test]
+fn emitBitcode() {
+    let artifacts = compile(CLEAN, EmitType::Bitcode).unwrap();
+    assert!(artifacts.output.starts_with(b"BC\xC0\xDE"));
+}
+
+#[test]
+fn emitAsm() {
+    let artifacts = compile(CLEAN, EmitType::Asm).unwrap();
+    let output = String::from_utf8(artifacts.output).unwrap();
+    assert!(output.contains("main:"), "{}", output);
+    assert!(output.contains("putinteger"), "{}", output);
+}
+
+#[test]
+fn emitObjectAndExe() {
+    //Exe stops at the object code, linking is left to the caller
+    let object = compile(CLEAN, EmitType::Object).unwrap().output;
+    let exe = compile(CLEAN, EmitType::Exe).unwrap().output;
+    assert!(!object.is_empty());
+    assert_eq!(object, exe);
+    if cfg!(target_os = "linux") {
+        assert!(object.starts_with(b"\x7fELF"));
+    }
+}
+
+#[test]
+fn explicitTarget() {
+    let mut options = Options::new("test.src", EmitType::LlvmIr);
+    options.target = Some("x86_64-unknown-linux-gnu".to_string());
+    let output = String::from_utf8(compile_source(CLEAN, options).unwrap().output).unwrap();
+    assert!(output.contains("target triple = \"x86_64-unknown-linux-gnu\""), "{}", output);
+
+    let mut options = Options::new("test.src", EmitType::Object);
+    options.target = Some("not-a-real-target".to_string());
+    let diagnostics = compile_source(CLEAN, options).err().unwrap();
+    assert_eq!(codes(&diagnostics, Severity::Error), vec!["C002"]);
+}
+
+#[test]
+fn lexError() {
+    let diagnostics = compileErr("program bad is\nbegin\n    a := 1 $ 2;\nend program.\n");
+    assert_eq!(codes(&diagnostics, Severity::Error), vec!["L001"]);
+    assert!(diagnostics.ast.is_none());
+}
+
+#[test]
+fn syntaxError() {
+    let diagnostics = compileErr("program bad is\n    variable a : integer;\nbegin\n    a := 1 + ;\nend program.\n");
+    assert_eq!(codes(&diagnostics, Severity::Error), vec!["P004"]);
+    let rendered = diagnostics.render();
+    assert!(rendered.contains(" --> test.src:4"), "{}", rendered);
+    assert!(rendered.contains("4 |     a := 1 + ;"), "{}", rendered);
+
+    //The partial tree is kept so it can still be looked at
+    assert!(diagnostics.ast.is_some());
+}
+
+#[test]
+fn typeErrors() {
+    let source = "program bad is
+    variable a : integer;
+    variable s : string;
+begin
+    a := \"text\";
+    b := 1;
+    putinteger(a, a);
+    s := \"x\";
+    putstring(s);
+end program.
+";
+    let diagnostics = compileErr(source);
+    assert_eq!(codes(&diagnostics, Severity::Error), vec!["T001", "T002", "T004"]);
+    assert!(diagnostics.hasErrors());
+    assert!(diagnostics.ast.is_some());
+}
+
+#[test]
+fn warningsOnly() {
+    let source = "program warns is
+    variable a : integer;
+    variable unused : integer;
+begin
+    a := 2.5;
+    putinteger(a);
+end program.
+";
+    let artifacts = compile(source, EmitType::LlvmIr).unwrap();
+    let diagnostics = &artifacts.diagnostics;
+    assert!(!diagnostics.hasErrors());
+    assert!(codes(diagnostics, Severity::Error).is_empty());
+    assert_eq!(diagnostics.reports.warnings.len(), 2);
+    assert_eq!(codes(diagnostics, Severity::Warning), vec!["T101", "F103"]);
+    assert!(!artifacts.output.is_empty());
+}
+
+#[test]
+fn defaultOptions() {
+    let options = Options::default();
+    assert_eq!(options.fileName, "<input>");
+    assert_eq!(options.emit, EmitType::Object);
+    assert_eq!(options.target, None);
+    assert!(options.boundsChecks);
+}
+
+#[test]
+fn boundsCheckNames() {
+    let source = "program arrays is
+    variable a : integer[4];
+    variable i : integer;
+begin
+    i := 2;
+    a[i] := 1;
+    a[i + 1] := a[i];
+    putinteger(a[i]);
+end program.
+";
+    let output = String::from_utf8(compile(source, EmitType::LlvmIr).unwrap().output).unwrap();
+    //Every check on the array shares the one global holding its name
+    assert_eq!(output.matches("call void @boundserror").count(), 4, "{}", output);
+    assert_eq!(output.matches("c\"a\\00\"").count(), 1, "{}", output);
+
+    let mut options = Options::new("test.src", EmitType::LlvmIr);
+    options.boundsChecks = false;
+    let output = String::from_utf8(compile_source(source, options).unwrap().output).unwrap();
+    assert!(!output.contains("boundserror"), "{}", output);
+}